@@ -113,13 +113,13 @@ impl Info {
     }
 
     // returns whether the method can be linked in the docs
-    pub fn should_be_doc_linked(&self, env: &Env) -> bool {
-        self.should_docs_be_generated(env)
+    pub fn should_be_doc_linked(&self, library: &library::Library) -> bool {
+        self.should_docs_be_generated(library)
             && (self.status.manual() || (!self.commented && !self.hidden))
     }
 
-    pub fn should_docs_be_generated(&self, env: &Env) -> bool {
-        !self.status.ignored() && !self.is_special() && !self.is_async_finish(env)
+    pub fn should_docs_be_generated(&self, library: &library::Library) -> bool {
+        !self.status.ignored() && !self.is_special() && !self.is_async_finish(library)
     }
 
     pub fn doc_link(
@@ -128,35 +128,53 @@ impl Info {
         visible_parent: Option<&str>,
         is_self: bool,
     ) -> String {
-        if let Some(p) = parent {
-            if is_self {
-                format!("[`{f}()`][Self::{f}()]", f = self.codegen_name())
-            } else {
-                format!(
-                    "[`{visible_parent}::{f}()`][crate::{p}::{f}()]",
-                    visible_parent = visible_parent.unwrap_or(p),
-                    p = p,
-                    f = self.codegen_name()
-                )
-            }
-        } else {
-            format!(
-                "[`{fn_name}()`][crate::{fn_name}()]",
-                fn_name = self.codegen_name()
-            )
-        }
+        fn_doc_link(self.codegen_name(), parent, visible_parent, is_self)
     }
 
-    pub fn is_async_finish(&self, env: &Env) -> bool {
+    /// Link to the generated `_future` variant of an async function
+    pub fn future_doc_link(
+        &self,
+        parent: Option<&str>,
+        visible_parent: Option<&str>,
+        is_self: bool,
+    ) -> Option<String> {
+        self.async_future
+            .as_ref()
+            .map(|future| fn_doc_link(&future.name, parent, visible_parent, is_self))
+    }
+
+    pub fn is_async_finish(&self, library: &library::Library) -> bool {
         let has_async_result = self
             .parameters
             .rust_parameters
             .iter()
-            .any(|param| param.typ.full_name(&env.library) == "Gio.AsyncResult");
+            .any(|param| param.typ.full_name(library) == "Gio.AsyncResult");
         self.name.ends_with("_finish") && has_async_result
     }
 }
 
+fn fn_doc_link(
+    fn_name: &str,
+    parent: Option<&str>,
+    visible_parent: Option<&str>,
+    is_self: bool,
+) -> String {
+    if let Some(p) = parent {
+        if is_self {
+            format!("[`{f}()`][Self::{f}()]", f = fn_name)
+        } else {
+            format!(
+                "[`{visible_parent}::{f}()`][crate::{p}::{f}()]",
+                visible_parent = visible_parent.unwrap_or(p),
+                p = p,
+                f = fn_name
+            )
+        }
+    } else {
+        format!("[`{fn_name}()`][crate::{fn_name}()]", fn_name = fn_name)
+    }
+}
+
 pub fn analyze<F: Borrow<library::Function>>(
     env: &Env,
     functions: &[F],
@@ -610,12 +628,7 @@ fn analyze_function(
                 );
             }
 
-            if name.starts_with("new_from")
-                || name.starts_with("new_with")
-                || name.starts_with("new_for")
-            {
-                new_name = Some(name[4..].to_string());
-            }
+            new_name = constructor_rename(&name);
         } else {
             let nb_in_params = func
                 .parameters
@@ -1277,6 +1290,16 @@ pub fn find_function<'a>(env: &'a Env, c_identifier: &str) -> Option<&'a Functio
     None
 }
 
+/// The name gir gives to a constructor, `new_with_label` becomes `with_label` while
+/// a plain `new` is kept
+pub fn constructor_rename(name: &str) -> Option<String> {
+    if name.starts_with("new_from") || name.starts_with("new_with") || name.starts_with("new_for") {
+        Some(name[4..].to_string())
+    } else {
+        None
+    }
+}
+
 /// Given async function name tries to guess the name of finish function.
 pub fn finish_function_name(mut func_name: &str) -> String {
     if func_name.ends_with("_async") {
@@ -1309,4 +1332,50 @@ mod tests {
         );
         assert_eq!("g_bus_get_finish", &finish_function_name("g_bus_get"));
     }
+
+    #[test]
+    fn test_constructor_doc_link() {
+        let link = |name: &str, parent: &str, is_self: bool| {
+            let name = constructor_rename(name).unwrap_or_else(|| name.to_owned());
+            fn_doc_link(&name, Some(parent), None, is_self)
+        };
+        assert_eq!(
+            link("new", "Button", false),
+            "[`Button::new()`][crate::Button::new()]"
+        );
+        assert_eq!(
+            link("new_with_label", "Button", false),
+            "[`Button::with_label()`][crate::Button::with_label()]"
+        );
+        assert_eq!(
+            link("new_with_label", "Button", true),
+            "[`with_label()`][Self::with_label()]"
+        );
+        assert_eq!(
+            link("new_from_string", "RGBA", false),
+            "[`RGBA::from_string()`][crate::RGBA::from_string()]"
+        );
+        assert_eq!(constructor_rename("newline"), None);
+    }
+
+    #[test]
+    fn test_fn_doc_link() {
+        assert_eq!(
+            fn_doc_link(
+                "load_contents_future",
+                Some("prelude::FileExt"),
+                Some("FileExt"),
+                false
+            ),
+            "[`FileExt::load_contents_future()`][crate::prelude::FileExt::load_contents_future()]"
+        );
+        assert_eq!(
+            fn_doc_link("load_contents_future", Some("File"), None, true),
+            "[`load_contents_future()`][Self::load_contents_future()]"
+        );
+        assert_eq!(
+            fn_doc_link("bus_get_future", None, None, false),
+            "[`bus_get_future()`][crate::bus_get_future()]"
+        );
+    }
 }
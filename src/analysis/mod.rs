@@ -4,7 +4,7 @@ use crate::{
 };
 use imports::Imports;
 use log::error;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub mod bounds;
 pub mod c_type;
@@ -57,23 +57,55 @@ pub struct Analysis {
     pub flags_imports: Imports,
 }
 
-fn find_function<'a>(
-    env: &Env,
-    mut functions: impl Iterator<Item = &'a functions::Info>,
+/// The analyzed item owning a function, see [`Analysis::function_index`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FunctionOwner {
+    Object(String),
+    Record(String),
+    Enum(usize),
+    Flags(usize),
+    Global,
+}
+
+pub(crate) fn find_function<'a>(
+    library: &library::Library,
+    functions: impl Iterator<Item = &'a functions::Info>,
     search_fn: impl Fn(&functions::Info) -> bool + Copy,
 ) -> Option<&'a functions::Info> {
-    functions.find(|fn_info| fn_info.should_be_doc_linked(env) && search_fn(fn_info))
+    primary(
+        functions.filter(|fn_info| fn_info.should_be_doc_linked(library) && search_fn(fn_info)),
+        |a, b| a.glib_name == b.glib_name,
+        |fn_info| fn_info.new_name.is_some(),
+    )
+}
+
+/// A C function split into several Rust methods is linked to the one keeping its
+/// analyzed name, the others being renamed variants of it
+fn primary<T>(
+    mut candidates: impl Iterator<Item = T>,
+    same_c_function: impl Fn(&T, &T) -> bool,
+    is_renamed: impl Fn(&T) -> bool,
+) -> Option<T> {
+    let first = candidates.next()?;
+    if !is_renamed(&first) {
+        return Some(first);
+    }
+    Some(
+        candidates
+            .find(|c| same_c_function(c, &first) && !is_renamed(c))
+            .unwrap_or(first),
+    )
 }
 
 impl Analysis {
     pub fn find_global_function<F: Fn(&functions::Info) -> bool + Copy>(
         &self,
-        env: &Env,
+        library: &library::Library,
         search: F,
     ) -> Option<&functions::Info> {
         self.global_functions
             .as_ref()
-            .and_then(move |info| find_function(env, info.functions.iter(), search))
+            .and_then(move |info| find_function(library, info.functions.iter(), search))
     }
 
     pub fn find_record_by_function<
@@ -81,7 +113,7 @@ impl Analysis {
         G: Fn(&record::Info) -> bool + Copy,
     >(
         &self,
-        env: &Env,
+        library: &library::Library,
         search_record: G,
         search_fn: F,
     ) -> Option<(&record::Info, &functions::Info)> {
@@ -89,7 +121,7 @@ impl Analysis {
             .values()
             .filter(|r| search_record(r))
             .find_map(|record_info| {
-                find_function(env, record_info.functions.iter(), search_fn)
+                find_function(library, record_info.functions.iter(), search_fn)
                     .map(|fn_info| (record_info, fn_info))
             })
     }
@@ -99,25 +131,58 @@ impl Analysis {
         G: Fn(&object::Info) -> bool + Copy,
     >(
         &self,
-        env: &Env,
+        library: &library::Library,
+        search_obj: G,
+        search_fn: F,
+    ) -> Option<(&object::Info, &functions::Info)> {
+        self.objects
+            .values()
+            .filter(|o| search_obj(o))
+            .find_map(|obj_info| {
+                find_function(library, obj_info.functions.iter(), search_fn)
+                    .map(|fn_info| (obj_info, fn_info))
+            })
+    }
+
+    /// Like [`Self::find_object_by_function`] but looks in the parent classes and
+    /// interfaces of the matching objects, as their methods can be called from there too
+    pub fn find_object_by_inherited_function<
+        F: Fn(&functions::Info) -> bool + Copy,
+        G: Fn(&object::Info) -> bool + Copy,
+    >(
+        &self,
+        library: &library::Library,
         search_obj: G,
         search_fn: F,
     ) -> Option<(&object::Info, &functions::Info)> {
         self.objects
             .values()
             .filter(|o| search_obj(o))
+            .flat_map(|obj_info| self.supertype_objects(obj_info))
             .find_map(|obj_info| {
-                find_function(env, obj_info.functions.iter(), search_fn)
+                find_function(library, obj_info.functions.iter(), search_fn)
                     .map(|fn_info| (obj_info, fn_info))
             })
     }
 
+    /// The analyzed parent classes and interfaces of an object, closest first
+    fn supertype_objects<'a>(
+        &'a self,
+        obj_info: &'a object::Info,
+    ) -> impl Iterator<Item = &'a object::Info> + 'a {
+        obj_info.supertypes.iter().filter_map(move |supertype| {
+            self.objects
+                .values()
+                .find(|o| o.type_id == supertype.type_id)
+        })
+    }
+
     pub fn find_enum_by_function<
         F: Fn(&functions::Info) -> bool + Copy,
         G: Fn(&enums::Info) -> bool + Copy,
     >(
         &self,
-        env: &Env,
+        library: &library::Library,
         search_enum: G,
         search_fn: F,
     ) -> Option<(&enums::Info, &functions::Info)> {
@@ -125,7 +190,7 @@ impl Analysis {
             .iter()
             .filter(|o| search_enum(o))
             .find_map(|obj_info| {
-                find_function(env, obj_info.functions.iter(), search_fn)
+                find_function(library, obj_info.functions.iter(), search_fn)
                     .map(|fn_info| (obj_info, fn_info))
             })
     }
@@ -135,7 +200,7 @@ impl Analysis {
         G: Fn(&flags::Info) -> bool + Copy,
     >(
         &self,
-        env: &Env,
+        library: &library::Library,
         search_flag: G,
         search_fn: F,
     ) -> Option<(&flags::Info, &functions::Info)> {
@@ -143,10 +208,74 @@ impl Analysis {
             .iter()
             .filter(|o| search_flag(o))
             .find_map(|obj_info| {
-                find_function(env, obj_info.functions.iter(), search_fn)
+                find_function(library, obj_info.functions.iter(), search_fn)
                     .map(|fn_info| (obj_info, fn_info))
             })
     }
+
+    /// The owners and positions of the functions by C name, in the order the
+    /// `find_*_by_function` methods look for them
+    pub fn function_index(&self) -> HashMap<String, Vec<(FunctionOwner, usize)>> {
+        let objects = self
+            .objects
+            .iter()
+            .map(|(name, info)| (FunctionOwner::Object(name.clone()), &info.functions));
+        let records = self
+            .records
+            .iter()
+            .map(|(name, info)| (FunctionOwner::Record(name.clone()), &info.functions));
+        let enums = self
+            .enumerations
+            .iter()
+            .enumerate()
+            .map(|(pos, info)| (FunctionOwner::Enum(pos), &info.functions));
+        let flags = self
+            .flags
+            .iter()
+            .enumerate()
+            .map(|(pos, info)| (FunctionOwner::Flags(pos), &info.functions));
+        let globals = self
+            .global_functions
+            .iter()
+            .map(|info| (FunctionOwner::Global, &info.functions));
+        index_functions(
+            objects
+                .chain(records)
+                .chain(enums)
+                .chain(flags)
+                .chain(globals)
+                .flat_map(|(owner, functions)| {
+                    functions
+                        .iter()
+                        .enumerate()
+                        .map(move |(pos, f)| (owner.clone(), pos, f.glib_name.as_str()))
+                }),
+        )
+    }
+
+    pub fn function_at(&self, owner: &FunctionOwner, pos: usize) -> Option<&functions::Info> {
+        let functions = match owner {
+            FunctionOwner::Object(name) => &self.objects.get(name)?.functions,
+            FunctionOwner::Record(name) => &self.records.get(name)?.functions,
+            FunctionOwner::Enum(pos) => &self.enumerations.get(*pos)?.functions,
+            FunctionOwner::Flags(pos) => &self.flags.get(*pos)?.functions,
+            FunctionOwner::Global => &self.global_functions.as_ref()?.functions,
+        };
+        functions.get(pos)
+    }
+}
+
+fn index_functions<'a>(
+    functions: impl Iterator<Item = (FunctionOwner, usize, &'a str)>,
+) -> HashMap<String, Vec<(FunctionOwner, usize)>> {
+    let mut index: HashMap<String, Vec<_>> = HashMap::new();
+    for (owner, pos, glib_name) in functions {
+        index
+            .entry(glib_name.to_owned())
+            .or_default()
+            .push((owner, pos));
+    }
+    index
 }
 
 pub fn run(env: &mut Env) {
@@ -342,3 +471,116 @@ fn is_all_deps_analyzed(env: &mut Env, deps: &[TypeId]) -> bool {
 pub fn is_gpointer(s: &str) -> bool {
     s == "gpointer" || s == "void*"
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::gobjects::GStatus;
+
+    fn object(name: &str, id: u32, supertypes: &[u32]) -> object::Info {
+        object::Info {
+            base: info_base::InfoBase {
+                full_name: format!("Gtk.{}", name),
+                type_id: TypeId { ns_id: 0, id },
+                name: name.to_owned(),
+                ..Default::default()
+            },
+            supertypes: supertypes
+                .iter()
+                .map(|&id| general::StatusedTypeId {
+                    type_id: TypeId { ns_id: 0, id },
+                    name: String::new(),
+                    status: GStatus::Generate,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_primary_function() {
+        let same = |a: &(&str, Option<&str>), b: &(&str, Option<&str>)| a.0 == b.0;
+        let is_renamed = |(_, new_name): &(&str, Option<&str>)| new_name.is_some();
+        // `gtk_widget_get_size` split into `size()` and the renamed `width()`
+        let split = [
+            ("gtk_widget_get_size", Some("width")),
+            ("gtk_widget_get_size", None),
+        ];
+        assert_eq!(
+            primary(split.into_iter(), same, is_renamed),
+            Some(("gtk_widget_get_size", None))
+        );
+        // Other functions are never picked over the first one
+        let others = [
+            ("gtk_widget_get_width", Some("width")),
+            ("gtk_widget_get_size", None),
+        ];
+        assert_eq!(
+            primary(others.into_iter(), same, is_renamed),
+            Some(("gtk_widget_get_width", Some("width")))
+        );
+        assert_eq!(primary([].into_iter(), same, is_renamed), None);
+    }
+
+    #[test]
+    fn test_index_functions() {
+        let index = index_functions(
+            [
+                (
+                    FunctionOwner::Object("Gtk.Widget".to_owned()),
+                    0,
+                    "gtk_widget_show",
+                ),
+                (
+                    FunctionOwner::Object("Gtk.Widget".to_owned()),
+                    1,
+                    "gtk_widget_get_size",
+                ),
+                (
+                    FunctionOwner::Object("Gtk.Widget".to_owned()),
+                    2,
+                    "gtk_widget_get_size",
+                ),
+                (
+                    FunctionOwner::Record("Gtk.Border".to_owned()),
+                    0,
+                    "gtk_widget_show",
+                ),
+                (FunctionOwner::Global, 3, "gtk_widget_show"),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            index["gtk_widget_show"],
+            [
+                (FunctionOwner::Object("Gtk.Widget".to_owned()), 0),
+                (FunctionOwner::Record("Gtk.Border".to_owned()), 0),
+                (FunctionOwner::Global, 3),
+            ]
+        );
+        assert_eq!(index["gtk_widget_get_size"].len(), 2);
+        assert!(!index.contains_key("gtk_widget_hide"));
+    }
+
+    #[test]
+    fn test_supertype_objects() {
+        let mut analysis = Analysis::default();
+        // Box: Widget, Orientable and the not analyzed InitiallyUnowned
+        for info in [
+            object("Box", 1, &[2, 3, 4]),
+            object("Widget", 2, &[4]),
+            object("Orientable", 3, &[]),
+        ] {
+            analysis.objects.insert(info.full_name.clone(), info);
+        }
+
+        let names = |name: &str| {
+            analysis
+                .supertype_objects(&analysis.objects[name])
+                .map(|o| o.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("Gtk.Box"), ["Widget", "Orientable"]);
+        assert!(names("Gtk.Orientable").is_empty());
+    }
+}
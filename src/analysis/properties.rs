@@ -317,6 +317,7 @@ fn analyze_property(
                 is_detailed: false, // well, technically this *is* an instance of a detailed signal, but we "pre-detailed" it
                 version: prop_version,
                 deprecated_version: prop.deprecated_version,
+                stability: library::Stability::default(),
                 doc: None,
                 doc_deprecated: None,
             },
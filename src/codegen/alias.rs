@@ -36,6 +36,7 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
     let path = root_path.join("alias.rs");
     file_saver::save_to_file(path, env.config.make_backup, |w| {
         general::start_comments(w, &env.config)?;
+        general::module_doc(w, env, "alias", "Type aliases")?;
         writeln!(w)?;
         writeln!(w, "#[allow(unused_imports)]")?;
         writeln!(w, "use crate::auto::*;")?;
@@ -21,6 +21,7 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
 
     file_saver::save_to_file(path, env.config.make_backup, |w| {
         general::start_comments(w, &env.config)?;
+        general::module_doc(w, env, "constants", "Constants")?;
         general::uses(w, env, &imports, None)?;
         writeln!(w)?;
 
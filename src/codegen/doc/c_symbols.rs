@@ -0,0 +1,186 @@
+use crate::{
+    codegen::doc::format::{
+        gen_const_doc_link, gen_member_doc_link, gen_object_fn_doc_link, gen_symbol_doc_link,
+        gen_type_fn_doc_link, DocContext,
+    },
+    library::{Member, TypeId, MAIN_NAMESPACE},
+    nameutil, Env,
+};
+use std::io::{Result, Write};
+
+/// A C symbol and the link to its Rust equivalent
+type Row = (String, String);
+
+/// Writes the index of the C symbols of the library with a section for each type,
+/// see `doc.c_symbols_index`
+pub fn generate(w: &mut dyn Write, env: &Env) -> Result<()> {
+    let symbols = env.symbols.borrow();
+    let ctx = DocContext::new(env, &symbols);
+    writeln!(w, "# C symbols")?;
+    for (title, rows) in sections(&ctx) {
+        write_section(w, &title, &rows)?;
+    }
+    Ok(())
+}
+
+fn sections(env: &DocContext<'_>) -> Vec<(String, Vec<Row>)> {
+    let mut sections = Vec::new();
+
+    for info in env.analysis.objects.values() {
+        if info.type_id.ns_id != MAIN_NAMESPACE {
+            continue;
+        }
+        let mut rows = vec![(info.c_type.clone(), gen_symbol_doc_link(info.type_id, env))];
+        rows.extend(
+            info.functions
+                .iter()
+                .filter(|f| f.should_be_doc_linked(env.library))
+                .map(|f| {
+                    let link = gen_object_fn_doc_link(info, f, env, None, &info.name);
+                    (f.glib_name.clone(), link)
+                }),
+        );
+        sections.push((rust_name(env, info.type_id), rows));
+    }
+
+    for info in env.analysis.records.values() {
+        if info.type_id.ns_id != MAIN_NAMESPACE {
+            continue;
+        }
+        let c_type = info.type_(env.library).c_type.clone();
+        let mut rows = vec![(c_type, gen_symbol_doc_link(info.type_id, env))];
+        rows.extend(type_functions(env, info.type_id, &info.functions));
+        sections.push((rust_name(env, info.type_id), rows));
+    }
+
+    for info in &env.analysis.enumerations {
+        let enum_ = info.type_(env.library);
+        let mut rows = vec![(enum_.c_type.clone(), gen_symbol_doc_link(info.type_id, env))];
+        rows.extend(members(&enum_.members, |m| {
+            gen_member_doc_link(
+                info.type_id,
+                &nameutil::enum_member_name(&m.name),
+                env,
+                None,
+            )
+        }));
+        rows.extend(type_functions(env, info.type_id, &info.functions));
+        sections.push((rust_name(env, info.type_id), rows));
+    }
+
+    for info in &env.analysis.flags {
+        let flags = info.type_(env.library);
+        let mut rows = vec![(flags.c_type.clone(), gen_symbol_doc_link(info.type_id, env))];
+        rows.extend(members(&flags.members, |m| {
+            gen_member_doc_link(
+                info.type_id,
+                &nameutil::bitfield_member_name(&m.name),
+                env,
+                None,
+            )
+        }));
+        rows.extend(type_functions(env, info.type_id, &info.functions));
+        sections.push((rust_name(env, info.type_id), rows));
+    }
+
+    if let Some(ref global_functions) = env.analysis.global_functions {
+        let rows: Vec<_> = global_functions
+            .functions
+            .iter()
+            .filter(|f| f.should_be_doc_linked(env.library))
+            .map(|f| (f.glib_name.clone(), f.doc_link(None, None, false)))
+            .collect();
+        if !rows.is_empty() {
+            sections.push(("Functions".to_owned(), rows));
+        }
+    }
+
+    if !env.analysis.constants.is_empty() {
+        let rows = env
+            .analysis
+            .constants
+            .iter()
+            .map(|c| (c.glib_name.clone(), gen_const_doc_link(c)))
+            .collect();
+        sections.push(("Constants".to_owned(), rows));
+    }
+
+    sections
+}
+
+fn rust_name(env: &DocContext<'_>, type_id: TypeId) -> String {
+    env.symbols.by_tid(type_id).map_or_else(
+        || type_id.full_name(env.library),
+        |sym| sym.full_rust_name(),
+    )
+}
+
+fn type_functions<'a>(
+    env: &'a DocContext<'a>,
+    type_id: TypeId,
+    functions: &'a [crate::analysis::functions::Info],
+) -> impl Iterator<Item = Row> + 'a {
+    functions
+        .iter()
+        .filter(move |f| f.should_be_doc_linked(env.library))
+        .map(move |f| {
+            let link = gen_type_fn_doc_link(type_id, f, env, None);
+            (f.glib_name.clone(), link)
+        })
+}
+
+fn members<'a>(
+    members: &'a [Member],
+    link: impl Fn(&Member) -> String + 'a,
+) -> impl Iterator<Item = Row> + 'a {
+    members
+        .iter()
+        .filter(|m| !m.status.ignored())
+        .map(move |m| (m.c_identifier.clone(), link(m)))
+}
+
+fn write_section(w: &mut dyn Write, title: &str, rows: &[Row]) -> Result<()> {
+    writeln!(w, "\n## {}\n", title)?;
+    writeln!(w, "| C symbol | Rust |")?;
+    writeln!(w, "| --- | --- |")?;
+    for (c_symbol, link) in rows {
+        writeln!(w, "| `{}` | {} |", c_symbol, link)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_section() {
+        let mut w = Vec::new();
+        write_section(
+            &mut w,
+            "Button",
+            &[
+                (
+                    "GtkButton".to_owned(),
+                    "[`Button`][crate::Button]".to_owned(),
+                ),
+                (
+                    "gtk_button_new".to_owned(),
+                    "[`Button::new()`][crate::Button::new()]".to_owned(),
+                ),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(w).unwrap(),
+            "
+## Button
+
+| C symbol | Rust |
+| --- | --- |
+| `GtkButton` | [`Button`][crate::Button] |
+| `gtk_button_new` | [`Button::new()`][crate::Button::new()] |
+"
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{
+    analysis::object::LocationInObject, gir_version::VERSION, library::TypeId, version::Version,
+    Env,
+};
+use log::{info, warn};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+const HEADER: &str = "gir-doc-cache";
+
+/// The docs formatted by the previous runs, see `doc.cache`
+#[derive(Debug, Default)]
+pub struct DocCache(Mutex<Option<Entries>>);
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Entries {
+    /// Hash of everything the links depend on besides the doc itself
+    fingerprint: u64,
+    /// The formatted docs read from the cache file, by key
+    old: HashMap<u64, String>,
+    /// The formatted docs of this run, by key, only they are saved
+    used: BTreeMap<u64, String>,
+}
+
+impl Entries {
+    fn new(fingerprint: u64) -> Self {
+        Self {
+            fingerprint,
+            ..Default::default()
+        }
+    }
+
+    fn key(
+        &self,
+        input: &str,
+        in_type: Option<(&TypeId, Option<LocationInObject>)>,
+        item_version: Option<Version>,
+    ) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.fingerprint.hash(&mut hasher);
+        input.hash(&mut hasher);
+        format!("{:?}", in_type).hash(&mut hasher);
+        item_version.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl DocCache {
+    /// Returns the cached formatting of `input`, else formats it with `format` and
+    /// caches it when the cache is loaded. The links depend on `item_version`, the
+    /// version of the documented item, too.
+    pub fn get_or_format(
+        &self,
+        input: &str,
+        in_type: Option<(&TypeId, Option<LocationInObject>)>,
+        item_version: Option<Version>,
+        format: impl FnOnce() -> String,
+    ) -> String {
+        let key = match self.0.lock().unwrap().as_mut() {
+            Some(entries) => {
+                let key = entries.key(input, in_type, item_version);
+                if let Some(doc) = entries.old.get(&key).cloned() {
+                    entries.used.insert(key, doc.clone());
+                    return doc;
+                }
+                Some(key)
+            }
+            None => None,
+        };
+        let doc = format();
+        if let Some(key) = key {
+            if let Some(entries) = self.0.lock().unwrap().as_mut() {
+                entries.used.insert(key, doc.clone());
+            }
+        }
+        doc
+    }
+}
+
+/// The cache file, unless the unresolved references are collected since they aren't
+/// recorded again for the cached docs
+fn cache_path(env: &Env) -> Option<&PathBuf> {
+    if env.config.doc.strict || env.config.doc.unresolved_report.is_some() {
+        return None;
+    }
+    env.config.doc.cache.as_ref()
+}
+
+/// Reads the docs cached by the previous run, they are discarded when the GIR files,
+/// the config or gir itself changed since any of them can change the links
+pub(super) fn load(env: &Env) {
+    let path = match cache_path(env) {
+        Some(path) => path,
+        None => return,
+    };
+    let mut entries = Entries::new(fingerprint(env));
+    match fs::read_to_string(path) {
+        Ok(content) => match read(&content) {
+            Some(old) if old.fingerprint == entries.fingerprint => {
+                entries.old = old.used.into_iter().collect()
+            }
+            _ => info!("Discarding the outdated doc cache {:?}", path),
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => warn!("Couldn't read the doc cache {:?}: {}", path, e),
+    }
+    *env.doc_cache.0.lock().unwrap() = Some(entries);
+}
+
+/// Writes the docs formatted by this run back to the cache file
+pub(super) fn save(env: &Env) {
+    let path = match cache_path(env) {
+        Some(path) => path,
+        None => return,
+    };
+    let entries = env.doc_cache.0.lock().unwrap();
+    let entries = match *entries {
+        Some(ref entries) => entries,
+        None => return,
+    };
+    info!("Saving doc cache {:?}", path);
+    let mut content = Vec::new();
+    let res = write(&mut content, entries).and_then(|()| {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)
+    });
+    if let Err(e) = res {
+        warn!("Couldn't write the doc cache {:?}: {}", path, e);
+    }
+}
+
+/// Hash of gir itself, the config file and the GIR files of the loaded namespaces
+fn fingerprint(env: &Env) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    VERSION.hash(&mut hasher);
+    fs::read(&env.config.config_file)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    for dir in &env.config.girs_dirs {
+        let mut files: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| is_loaded_gir(env, path))
+                .collect(),
+            Err(_) => continue,
+        };
+        files.sort();
+        for file in files {
+            file.hash(&mut hasher);
+            fs::read(&file).unwrap_or_default().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+fn is_loaded_gir(env: &Env, path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == "gir")
+        && path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map_or(false, |stem| {
+                env.library.namespaces.iter().any(|ns| {
+                    stem.strip_prefix(ns.name.as_str())
+                        .map_or(false, |v| v.starts_with('-'))
+                })
+            })
+}
+
+/// Writes a `gir-doc-cache <fingerprint>` line followed by each doc as a
+/// `<key> <length>` line, its text and a newline
+fn write(w: &mut dyn Write, entries: &Entries) -> io::Result<()> {
+    writeln!(w, "{} {:016x}", HEADER, entries.fingerprint)?;
+    for (key, doc) in &entries.used {
+        writeln!(w, "{:016x} {}", key, doc.len())?;
+        w.write_all(doc.as_bytes())?;
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+/// Reads a cache file written by [`write`], with the docs in `used`
+fn read(content: &str) -> Option<Entries> {
+    let (header, mut rest) = content.split_once('\n')?;
+    let fingerprint = u64::from_str_radix(header.strip_prefix(HEADER)?.trim(), 16).ok()?;
+    let mut entries = Entries::new(fingerprint);
+    while !rest.is_empty() {
+        let (line, tail) = rest.split_once('\n')?;
+        let (key, len) = line.split_once(' ')?;
+        let key = u64::from_str_radix(key, 16).ok()?;
+        let len: usize = len.parse().ok()?;
+        let doc = tail.get(..len)?;
+        rest = tail[len..].strip_prefix('\n')?;
+        entries.used.insert(key, doc.to_owned());
+    }
+    Some(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_equals_fresh() {
+        let format = || "Formatted\n\n```rust\nlet a\t= 1;\n```\n".to_owned();
+        let fresh = format();
+
+        let cache = DocCache(Mutex::new(Some(Entries::new(42))));
+        assert_eq!(cache.get_or_format("Input", None, None, format), fresh);
+
+        let mut content = Vec::new();
+        write(&mut content, cache.0.lock().unwrap().as_ref().unwrap()).unwrap();
+        let mut entries = read(std::str::from_utf8(&content).unwrap()).unwrap();
+        assert_eq!(entries.fingerprint, 42);
+        entries.old = std::mem::take(&mut entries.used).into_iter().collect();
+
+        let cache = DocCache(Mutex::new(Some(entries)));
+        let cached = cache.get_or_format("Input", None, None, || unreachable!());
+        assert_eq!(cached, fresh);
+        assert_eq!(
+            cache.get_or_format("Other input", None, None, || "Other".to_owned()),
+            "Other"
+        );
+    }
+
+    #[test]
+    fn test_key() {
+        let entries = Entries::new(1);
+        assert_eq!(
+            entries.key("Input", None, None),
+            entries.key("Input", None, None)
+        );
+        assert_ne!(
+            entries.key("Input", None, None),
+            entries.key("Other", None, None)
+        );
+        assert_ne!(
+            entries.key("Input", None, None),
+            Entries::new(2).key("Input", None, None)
+        );
+        // The links of the docs depend on the version of their item
+        assert_ne!(
+            entries.key("Input", None, None),
+            entries.key("Input", None, Some(Version(4, 10, 0)))
+        );
+    }
+
+    #[test]
+    fn test_read() {
+        assert_eq!(read(""), None);
+        assert_eq!(read("gir-doc-cache 2a\n"), Some(Entries::new(42)));
+        assert_eq!(read("gir-doc-cache 2a\n0000000000000001 10\nshort\n"), None);
+        assert_eq!(read("other 2a\n"), None);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let cache = DocCache::default();
+        assert_eq!(
+            cache.get_or_format("Input", None, None, || "Fresh".to_owned()),
+            "Fresh"
+        );
+        assert!(cache.0.lock().unwrap().is_none());
+    }
+}
@@ -1,16 +1,40 @@
 #![allow(clippy::manual_map)]
-use super::{gi_docgen, LocationInObject};
+use super::{
+    gi_docgen,
+    unresolved::{self, Miss, RefKind},
+    LocationInObject,
+};
 use crate::{
-    analysis::functions::Info,
-    library::{FunctionKind, TypeId},
-    nameutil, Env,
+    analysis::{self, functions::Info, namespaces, FunctionOwner},
+    config::{
+        doc::{
+            DeprecatedMemberLink, DocConfig, DocLinkOverride, PropertyLinkTarget, UnresolvedStyle,
+            DEFAULT_IGNORED_TYPES,
+        },
+        gobjects::GStatus,
+        Config, ExternalLibrary,
+    },
+    library::{
+        ErrorDomain, Field, FunctionKind, Library, Member, Type, TypeId, INTERNAL_NAMESPACE,
+        MAIN_NAMESPACE,
+    },
+    nameutil,
+    version::Version,
+    Env,
 };
-use log::{info, warn};
+use log::{info, warn, Level};
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap, HashSet},
+};
 
-const LANGUAGE_SEP_BEGIN: &str = "<!-- language=\"";
-const LANGUAGE_SEP_END: &str = "\" -->";
+const LANGUAGE_SEP_BEGIN: &str = "<!--";
+const LANGUAGE_SEP_END: &str = "-->";
+/// The languages of the C docs spelled differently in markdown
+const LANGUAGE_NAMES: &[(&str, &str)] = &[("plain", "text"), ("txt", "text"), ("none", "text")];
 const LANGUAGE_BLOCK_BEGIN: &str = "|[";
 const LANGUAGE_BLOCK_END: &str = "\n]|";
 
@@ -24,12 +48,587 @@ const IGNORE_C_WARNING_FUNCS: [&str; 6] = [
     "printf",
 ];
 
+/// The function-like C macros without a Rust equivalent, never reported as unresolved
+const IGNORE_C_WARNING_MACROS: &[&str] = &[
+    "G_ADD_PRIVATE",
+    "G_DECLARE_DERIVABLE_TYPE",
+    "G_DECLARE_FINAL_TYPE",
+    "G_DECLARE_INTERFACE",
+    "G_DEFINE_ABSTRACT_TYPE",
+    "G_DEFINE_AUTOPTR_CLEANUP_FUNC",
+    "G_DEFINE_BOXED_TYPE",
+    "G_DEFINE_INTERFACE",
+    "G_DEFINE_TYPE",
+    "G_DEFINE_TYPE_WITH_CODE",
+    "G_DEFINE_TYPE_WITH_PRIVATE",
+    "G_IMPLEMENT_INTERFACE",
+    "G_N_ELEMENTS",
+    "G_TYPE_CHECK_CLASS_CAST",
+    "G_TYPE_CHECK_INSTANCE_CAST",
+    "G_TYPE_CHECK_INSTANCE_TYPE",
+    "G_TYPE_INSTANCE_GET_CLASS",
+    "GINT_TO_POINTER",
+    "GPOINTER_TO_INT",
+    "GPOINTER_TO_UINT",
+    "GUINT_TO_POINTER",
+];
+
+/// Whether the misses of this function are expected, see `doc.ignored_functions`
+fn is_ignored_function(name: &str, config: &DocConfig) -> bool {
+    IGNORE_C_WARNING_FUNCS.contains(&name)
+        || IGNORE_C_WARNING_MACROS.contains(&name)
+        || config.ignored_functions.iter().any(|f| f == name)
+}
+
+/// The lookups of the doc formatting, taken from the `Env` once
+///
+/// A `DocContext` only reads the parts of the `Env` it was built from and owns its
+/// indexes, the unresolved references of the docs it formats are returned to the
+/// caller instead of being kept in the `Env`.
+pub struct DocContext<'a> {
+    pub config: &'a Config,
+    pub library: &'a Library,
+    pub namespaces: &'a namespaces::Info,
+    pub analysis: &'a analysis::Analysis,
+    pub symbols: &'a analysis::symbols::Info,
+    /// See [`type_index`]
+    pub type_index: HashMap<String, TypeId>,
+    /// The analyzed functions by their C name, see [`analysis::Analysis::function_index`]
+    pub function_index: HashMap<String, Vec<(FunctionOwner, usize)>>,
+    /// See [`TypeLookups`]
+    pub type_lookups: TypeLookups<'a>,
+    /// The types [`is_generated_type`] already logged as not generated
+    logged_ungenerated: RefCell<HashSet<TypeId>>,
+    /// The version of the item whose docs are formatted, see [`DocContext::enter_item`]
+    item_version: Cell<Option<Version>>,
+}
+
+impl<'a> DocContext<'a> {
+    /// The `symbols` are borrowed from `env.symbols` by the caller, which keeps the
+    /// borrow for as long as the context lives
+    pub fn new(env: &'a Env, symbols: &'a analysis::symbols::Info) -> Self {
+        Self {
+            config: &env.config,
+            library: &env.library,
+            namespaces: &env.namespaces,
+            analysis: &env.analysis,
+            symbols,
+            type_index: type_index(&env.config, &env.library, &env.analysis),
+            function_index: env.analysis.function_index(),
+            type_lookups: TypeLookups::new(&env.library, &env.analysis),
+            logged_ungenerated: Default::default(),
+            item_version: Cell::new(None),
+        }
+    }
+
+    /// The docs formatted until the returned scope is dropped are the ones of an item
+    /// introduced in `version`, with `doc.version_aware_links` their links are checked
+    /// against it besides the version of their type
+    #[must_use]
+    pub fn enter_item(&self, version: Option<Version>) -> ItemScope<'_> {
+        ItemScope {
+            outer: self.item_version.replace(version),
+            item_version: &self.item_version,
+        }
+    }
+
+    pub fn item_version(&self) -> Option<Version> {
+        self.item_version.get()
+    }
+
+    pub fn main_sys_crate_name(&self) -> &str {
+        &self.namespaces[MAIN_NAMESPACE].sys_crate_name
+    }
+}
+
+/// Restores the item version of the [`DocContext`] it was entered from when dropped
+pub struct ItemScope<'c> {
+    item_version: &'c Cell<Option<Version>>,
+    outer: Option<Version>,
+}
+
+impl Drop for ItemScope<'_> {
+    fn drop(&mut self) {
+        self.item_version.set(self.outer);
+    }
+}
+
+/// A doc formatted by [`reformat_doc_with`] and the references it left unresolved
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FormattedDoc {
+    pub doc: String,
+    pub misses: Vec<Miss>,
+}
+
+/// Builds a [`DocContext`] for a single doc, the ones formatting many docs keep a
+/// context around and use [`reformat_doc_in`] instead
 pub fn reformat_doc(
     input: &str,
     env: &Env,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
 ) -> String {
-    code_blocks_transformation(input, env, in_type)
+    let symbols = env.symbols.borrow();
+    reformat_doc_in(input, env, &DocContext::new(env, &symbols), in_type)
+}
+
+/// Same as [`reformat_doc`] with the lookups of an existing context, the doc goes
+/// through the doc cache and its unresolved references are logged and recorded in `env`
+pub fn reformat_doc_in(
+    input: &str,
+    env: &Env,
+    ctx: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> String {
+    if input.trim().is_empty() {
+        return String::new();
+    }
+    env.doc_cache
+        .get_or_format(input, in_type, ctx.item_version(), || {
+            let formatted = reformat_doc_with(input, ctx, in_type);
+            unresolved::report(env, &formatted.misses, in_type);
+            formatted.doc
+        })
+}
+
+/// Formats `input` with the lookups of `env` alone, nothing is logged nor cached
+pub fn reformat_doc_with(
+    input: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> FormattedDoc {
+    if input.trim().is_empty() {
+        return FormattedDoc::default();
+    }
+    let input = &*normalize_line_endings(input);
+    // Cut before the link passes so the dropped references aren't looked up
+    let input = if summary_only(env, in_type) {
+        first_paragraph(input)
+    } else {
+        input
+    };
+    let misses = RefCell::default();
+    let doc = format_doc(input, env, in_type, &misses);
+    FormattedDoc {
+        doc,
+        misses: misses.into_inner(),
+    }
+}
+
+/// Turn the `\r\n` and `\r` line endings of the GIR files written on Windows into `\n`,
+/// the block markers and passes only expect the latter
+fn normalize_line_endings(input: &str) -> Cow<'_, str> {
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Whether only the first paragraph of the docs of `in_type` is kept, see
+/// `doc.summary_only` and the `doc_summary_only` of the objects
+fn summary_only(
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> bool {
+    in_type
+        .and_then(|(tid, _)| env.config.objects.get(&tid.full_name(env.library)))
+        .and_then(|obj| obj.doc_summary_only)
+        .unwrap_or(env.config.doc.summary_only)
+}
+
+/// The first paragraph of the C docs, up to the first blank line, code block or end of
+/// a DocBook `<para>`. The paragraphs of markdown never contain blank lines so the
+/// links aren't cut either.
+fn first_paragraph(input: &str) -> &str {
+    let input = input.trim_start();
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            break;
+        }
+        let code_block = ["```", "|[", "<programlisting", "<informalexample"]
+            .iter()
+            .filter_map(|start| line.find(start))
+            .min();
+        if let Some(start) = code_block {
+            offset += start;
+            break;
+        }
+        if let Some(end) = line.find("</para>") {
+            offset += end + "</para>".len();
+            break;
+        }
+        offset += line.len();
+    }
+    input[..offset].trim_end()
+}
+
+/// The lookups of the C references of the docs, like the ones of the doc context of a
+/// generation run, for the tools reformatting docs on their own with
+/// [`reformat_standalone_doc`]
+pub trait DocResolver {
+    /// The link to the Rust type of a C type like `GtkWidget`
+    fn resolve_type(&self, c_type: &str) -> Option<String>;
+    /// The link to the Rust function or method of a C function like `gtk_widget_show`
+    fn resolve_function(&self, c_name: &str) -> Option<String>;
+    /// The link to the Rust constant or member of a C identifier like `GTK_ALIGN_START`
+    fn resolve_constant(&self, c_identifier: &str) -> Option<String>;
+}
+
+impl DocResolver for DocContext<'_> {
+    fn resolve_type(&self, c_type: &str) -> Option<String> {
+        find_type(c_type, self, None)
+    }
+
+    fn resolve_function(&self, c_name: &str) -> Option<String> {
+        find_method_or_function_by_ctype(None, c_name, self, None)
+    }
+
+    fn resolve_constant(&self, c_identifier: &str) -> Option<String> {
+        find_constant_or_variant(c_identifier, self, None)
+    }
+}
+
+/// Reformat a doc string outside of a generation run: the code blocks, DocBook markup
+/// and entities are translated like by `reformat_doc` while the `#GtkWidget`,
+/// `gtk_widget_show()` and `%GTK_ALIGN_START` like references are linked by `resolver`.
+/// None of the `[doc]` options apply.
+pub fn reformat_standalone_doc(input: &str, resolver: &impl DocResolver) -> String {
+    if input.trim().is_empty() {
+        return String::new();
+    }
+    let input = normalize_line_endings(input);
+    let input = programlistings(&tables(&input));
+    let (out, _) = language_blocks(
+        &input,
+        &[],
+        false,
+        |prose| {
+            format_segments(
+                &orphan_language_markers(prose),
+                &[],
+                |prose| resolve_references(prose, resolver),
+                str::to_owned,
+            )
+        },
+        str::to_owned,
+    );
+    out
+}
+
+/// The prose of [`reformat_standalone_doc`], the references `resolver` can't link are
+/// kept as inline code
+fn resolve_references(input: &str, resolver: &impl DocResolver) -> String {
+    let out = ulinks(input);
+    let out = FUNCTION.replace_all(&out, |caps: &Captures<'_>| {
+        resolver.resolve_function(&caps[3]).unwrap_or_else(|| {
+            format!("`{}{}()`", caps.get(2).map_or("", |m| m.as_str()), &caps[3])
+        })
+    });
+    let out = strip_type_decorators(&out);
+    let out = SYMBOL.replace_all(&out, |caps: &Captures<'_>| match &caps[2] {
+        _ if is_c_literal(&out, caps) => caps[0].to_owned(),
+        "TRUE" => "[`true`]".to_owned(),
+        "FALSE" => "[`false`]".to_owned(),
+        "NULL" => "[`None`]".to_owned(),
+        symbol_name => {
+            let found = match (&caps[1], caps.get(3)) {
+                ("%", None) => resolver.resolve_constant(symbol_name),
+                ("#", None) => resolver.resolve_type(symbol_name),
+                ("@", None) => resolver
+                    .resolve_type(symbol_name)
+                    .or_else(|| resolver.resolve_constant(symbol_name))
+                    .or_else(|| resolver.resolve_function(symbol_name)),
+                _ => None,
+            };
+            found.unwrap_or_else(|| {
+                inline_code(&format!(
+                    "{}{}",
+                    symbol_name,
+                    caps.get(3).map_or("", |m| m.as_str())
+                ))
+            })
+        }
+    });
+    let out = docbook(&out);
+    let out = emphasis(&out);
+    let out = separate_code_spans(&out);
+    decode_entities(&out).into_owned()
+}
+
+fn format_doc(
+    input: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    misses: &RefCell<Vec<Miss>>,
+) -> String {
+    let out = code_blocks_transformation(input, env, in_type, misses);
+    let out = if env.config.doc.examples_section {
+        group_examples(&out, &env.config.doc.section_titles.examples).into_owned()
+    } else {
+        out
+    };
+    let out = if env.config.doc.trim_trailing_whitespace {
+        trim_trailing_whitespace(&out)
+    } else {
+        out
+    };
+    let out = if env.config.doc.demote_lone_headings {
+        demote_lone_heading(&out).into_owned()
+    } else {
+        out
+    };
+    let out = if env.config.doc.ascii_only {
+        ascii_only(&out)
+    } else {
+        out
+    };
+    match env.config.doc.wrap_columns {
+        Some(width) => wrap_lines(&out, width),
+        None => out,
+    }
+}
+
+/// Wrap the prose lines longer than `width` at their spaces, see `doc.wrap_columns`.
+/// The code blocks, tables, headings and link definitions are kept as they are, and
+/// the inline code and link targets are never split.
+fn wrap_lines(input: &str, width: usize) -> String {
+    let mut in_code = false;
+    let mut out = String::with_capacity(input.len());
+    for (i, line) in input.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let fence = line.trim_start().starts_with("```");
+        if fence {
+            in_code = !in_code;
+        }
+        if in_code || fence || line.chars().count() <= width || UNWRAPPED_LINE.is_match(line) {
+            out.push_str(line);
+        } else {
+            wrap_line(line, width, &mut out);
+        }
+    }
+    out
+}
+
+/// The continuation lines keep the quote marks of the line, or are indented to stay
+/// in its list item
+fn wrap_line(line: &str, width: usize, out: &mut String) {
+    let marker = LINE_MARKER.find(line).map_or("", |m| m.as_str());
+    let indent = if marker.trim_start().starts_with('>') {
+        marker.to_owned()
+    } else {
+        " ".repeat(marker.chars().count())
+    };
+    let body = &line[marker.len()..];
+    out.push_str(marker);
+    let mut column = marker.chars().count();
+    let mut first = true;
+    for word in wrap_words(body.trim_end_matches(' ')) {
+        let len = word.chars().count();
+        if first {
+            first = false;
+        } else if column + 1 + len > width {
+            out.push('\n');
+            out.push_str(&indent);
+            column = indent.chars().count();
+        } else {
+            out.push(' ');
+            column += 1;
+        }
+        out.push_str(word);
+        column += len;
+    }
+    // A hard line break
+    if body.ends_with("  ") {
+        out.push_str("  ");
+    }
+}
+
+/// The words of `line` split at the spaces outside of the inline code and of the
+/// `[text](target)` targets
+fn wrap_words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut code_ticks = 0;
+    let mut in_target = false;
+    let mut chars = line.char_indices().peekable();
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '`' if !in_target => {
+                let mut ticks = 1;
+                while chars.next_if(|&(_, c)| c == '`').is_some() {
+                    ticks += 1;
+                }
+                if code_ticks == 0 {
+                    code_ticks = ticks;
+                } else if code_ticks == ticks {
+                    code_ticks = 0;
+                }
+            }
+            '(' if code_ticks == 0 && line[..pos].ends_with(']') => in_target = true,
+            ')' if in_target => in_target = false,
+            ' ' if code_ticks == 0 && !in_target => {
+                words.push(&line[start..pos]);
+                start = pos + 1;
+            }
+            _ => (),
+        }
+    }
+    words.push(&line[start..]);
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// Replace the non ASCII characters outside of the code blocks by their closest ASCII
+/// equivalent, or by an HTML entity, see `doc.ascii_only`
+fn ascii_only(input: &str) -> String {
+    let mut in_code = false;
+    let lines = input.split('\n').map(|line| {
+        let fence = line.trim_start().starts_with("```");
+        if fence {
+            in_code = !in_code;
+        }
+        if (in_code && !fence) || line.is_ascii() {
+            return line.to_owned();
+        }
+        let mut out = String::with_capacity(line.len());
+        for c in line.chars() {
+            match c {
+                _ if c.is_ascii() => out.push(c),
+                '‘' | '’' | '′' => out.push('\''),
+                '“' | '”' | '″' => out.push('"'),
+                '–' | '—' | '‐' | '−' => out.push('-'),
+                '…' => out.push_str("..."),
+                '\u{a0}' => out.push(' '),
+                '×' => out.push('x'),
+                'à'..='å' => out.push('a'),
+                'À'..='Å' => out.push('A'),
+                'è'..='ë' => out.push('e'),
+                'È'..='Ë' => out.push('E'),
+                'ì'..='ï' => out.push('i'),
+                'Ì'..='Ï' => out.push('I'),
+                'ò'..='ö' | 'ø' => out.push('o'),
+                'Ò'..='Ö' | 'Ø' => out.push('O'),
+                'ù'..='ü' => out.push('u'),
+                'Ù'..='Ü' => out.push('U'),
+                'ç' => out.push('c'),
+                'Ç' => out.push('C'),
+                'ñ' => out.push('n'),
+                'Ñ' => out.push('N'),
+                'ý' | 'ÿ' => out.push('y'),
+                'ß' => out.push_str("ss"),
+                _ => out.push_str(&format!("&#x{:x};", c as u32)),
+            }
+        }
+        out
+    });
+    lines.collect::<Vec<_>>().join("\n")
+}
+
+/// Move the code blocks of several lines to an `# Examples` section at the end of the
+/// doc, in their order and along with the paragraph introducing them when it ends with
+/// a `:`, see `doc.examples_section`. The docs having such a section already are kept.
+fn group_examples<'a>(input: &'a str, title: &str) -> Cow<'a, str> {
+    let heading = format!("# {}", title);
+    if input.lines().any(|line| line.trim_end() == heading) {
+        return Cow::Borrowed(input);
+    }
+    let is_fence = |line: &str| line.trim_start().starts_with("```");
+    let is_blank = |line: &str| line.trim().is_empty();
+    let lines: Vec<&str> = input.split('\n').collect();
+    let mut kept: Vec<&str> = Vec::with_capacity(lines.len());
+    let mut examples: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if !is_fence(lines[i]) {
+            kept.push(lines[i]);
+            i += 1;
+            continue;
+        }
+        let end = match (i + 1..lines.len()).find(|&j| is_fence(lines[j])) {
+            Some(end) => end,
+            None => {
+                kept.extend(&lines[i..]);
+                break;
+            }
+        };
+        // The one-liners illustrate the prose around them
+        if end - i <= 2 {
+            kept.extend(&lines[i..=end]);
+            i = end + 1;
+            continue;
+        }
+        let text_end = kept
+            .iter()
+            .rposition(|line| !is_blank(line))
+            .map_or(0, |n| n + 1);
+        let intro_start = kept[..text_end]
+            .iter()
+            .rposition(|line| is_blank(line))
+            .map_or(0, |n| n + 1);
+        let mut example = Vec::new();
+        if text_end > intro_start && kept[text_end - 1].trim_end().ends_with(':') {
+            example.extend(kept.drain(intro_start..).filter(|line| !is_blank(line)));
+            example.push("");
+        }
+        example.extend(&lines[i..=end]);
+        examples.push(example.join("\n"));
+        i = end + 1;
+        // Don't leave two blank lines where the block was
+        if i < lines.len() && is_blank(lines[i]) && kept.last().map_or(true, |l| is_blank(l)) {
+            i += 1;
+        }
+    }
+    if examples.is_empty() {
+        return Cow::Borrowed(input);
+    }
+    let mut out = kept.join("\n").trim_end().to_owned();
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    out.push_str(&heading);
+    out.push_str("\n\n");
+    out.push_str(&examples.join("\n\n"));
+    if input.ends_with('\n') {
+        out.push('\n');
+    }
+    Cow::Owned(out)
+}
+
+/// A doc made of a single `# GtkButton` heading is turned into a summary line, see
+/// `doc.demote_lone_headings`
+fn demote_lone_heading(input: &str) -> Cow<'_, str> {
+    let line = input.trim();
+    if line.contains('\n') {
+        return Cow::Borrowed(input);
+    }
+    match line.trim_start_matches('#').strip_prefix(' ') {
+        Some(title) if line.starts_with('#') && !title.trim().is_empty() => {
+            Cow::Owned(title.trim().to_owned())
+        }
+        _ => Cow::Borrowed(input),
+    }
+}
+
+/// Trim the trailing whitespace of each line outside of the code blocks, see
+/// `doc.trim_trailing_whitespace`
+fn trim_trailing_whitespace(input: &str) -> String {
+    let mut in_code = false;
+    let lines = input.split('\n').map(|line| {
+        let fence = line.trim_start().starts_with("```");
+        let line = if in_code && !fence {
+            line
+        } else {
+            line.trim_end()
+        };
+        if fence {
+            in_code = !in_code;
+        }
+        line
+    });
+    lines.collect::<Vec<_>>().join("\n")
 }
 
 fn try_split<'a>(src: &'a str, needle: &str) -> (&'a str, Option<&'a str>) {
@@ -40,41 +639,298 @@ fn try_split<'a>(src: &'a str, needle: &str) -> (&'a str, Option<&'a str>) {
 }
 
 fn code_blocks_transformation(
-    mut input: &str,
-    env: &Env,
+    input: &str,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    misses: &RefCell<Vec<Miss>>,
 ) -> String {
+    let input = programlistings(&tables(input));
+    let (out, terminated) = language_blocks(
+        &input,
+        &env.config.doc.doctest_setup(),
+        env.config.doc.ignore_foreign_examples,
+        |prose| format(&orphan_language_markers(prose), env, in_type, misses),
+        |code| expand_tabs(code, env).into_owned(),
+    );
+    if !terminated {
+        warn!(
+            "Unterminated code block in the docs of {}",
+            in_type.map_or_else(
+                || "a global item".to_owned(),
+                |(tid, _)| tid.full_name(env.library)
+            )
+        );
+    }
+    out
+}
+
+/// Strip the `<!-- language="C" -->` comments left in the prose without a `|[` block,
+/// along with their line when they are alone on it
+fn orphan_language_markers(input: &str) -> Cow<'_, str> {
+    ORPHAN_LANGUAGE_MARKER.replace_all(input, "")
+}
+
+/// Turn the DocBook and HTML tables into markdown ones, the tables markdown can't
+/// represent, with cells spanning several rows or columns or containing blocks, become
+/// `|[ ... ]|` blocks of their text
+fn tables(input: &str) -> Cow<'_, str> {
+    TABLE.replace_all(input, |caps: &Captures<'_>| {
+        let m = caps.get(0).unwrap();
+        let content = &caps[1];
+        let table = markdown_table(content).unwrap_or_else(|| preformatted_table(content));
+        let title = match TABLE_TITLE.captures(content) {
+            Some(title) => format!(
+                "**{}**\n\n",
+                title[1].split_whitespace().collect::<Vec<_>>().join(" ")
+            ),
+            None => String::new(),
+        };
+        // The table is a block of its own
+        let leading = if m.start() == 0 { "" } else { "\n\n" };
+        let trailing = if m.end() == input.len() { "" } else { "\n\n" };
+        format!("{}{}{}{}", leading, title, table, trailing)
+    })
+}
+
+/// A GitHub flavored markdown table with the `<thead>` row, or else the first row, as
+/// header and the `align` of the `<colspec>` or header cells
+fn markdown_table(content: &str) -> Option<String> {
+    if NESTED_TABLE.is_match(content) {
+        return None;
+    }
+    let head_end = content.find("</thead>");
+    let mut rows = Vec::new();
+    let mut header_rows = 0;
+    for row in TABLE_ROW.captures_iter(content) {
+        let mut cells = Vec::new();
+        let mut all_th = true;
+        for cell in TABLE_CELL.captures_iter(&row[1]) {
+            let (attributes, text) = (&cell[2], &cell[3]);
+            if SPANNING_CELL.is_match(attributes)
+                || CELL_BLOCK.is_match(text)
+                || text.matches("<para").count() > 1
+            {
+                return None;
+            }
+            all_th &= &cell[1] == "th";
+            cells.push((
+                table_cell(text),
+                attribute(attributes, "align").map(str::to_owned),
+            ));
+        }
+        let in_head = head_end.map_or(false, |end| row.get(0).unwrap().start() < end);
+        if (in_head || all_th) && !cells.is_empty() {
+            header_rows += 1;
+        }
+        rows.push(cells);
+    }
+    let columns = rows.first()?.len();
+    // Markdown tables have a single header row and the same cells in each row
+    if columns == 0 || header_rows > 1 || rows.iter().any(|cells| cells.len() != columns) {
+        return None;
+    }
+    let colspecs = COLSPEC
+        .captures_iter(content)
+        .map(|colspec| attribute(&colspec[1], "align").map(str::to_owned))
+        .collect::<Vec<_>>();
+    let row_line = |cells: &[(String, Option<String>)]| {
+        let texts = cells
+            .iter()
+            .map(|(text, _)| text.as_str())
+            .collect::<Vec<_>>();
+        format!("| {} |", texts.join(" | "))
+    };
+    let alignments = rows[0]
+        .iter()
+        .enumerate()
+        .map(|(i, (_, align))| {
+            match colspecs
+                .get(i)
+                .cloned()
+                .flatten()
+                .or_else(|| align.clone())
+                .as_deref()
+            {
+                Some("left") => ":---",
+                Some("center") => ":---:",
+                Some("right") => "---:",
+                _ => "---",
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut lines = vec![
+        row_line(&rows[0]),
+        format!("| {} |", alignments.join(" | ")),
+    ];
+    lines.extend(rows[1..].iter().map(|cells| row_line(cells)));
+    Some(lines.join("\n"))
+}
+
+/// The text of a markdown table cell, on a single line and with its `|` escaped
+fn table_cell(text: &str) -> String {
+    PARA_TAG
+        .replace_all(text, " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+/// The text of the cells of a table, without their markup and aligned in columns
+fn preformatted_table(content: &str) -> String {
+    let plain = |text: &str| {
+        let text = ANY_TAG.replace_all(text, " ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        decode_entities(&text).into_owned()
+    };
+    let rows = TABLE_ROW
+        .captures_iter(content)
+        .map(|row| {
+            TABLE_CELL
+                .captures_iter(&row[1])
+                .map(|cell| plain(&cell[3]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut widths = Vec::new();
+    for cells in &rows {
+        for (i, cell) in cells.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(i) {
+                Some(w) if *w < width => *w = width,
+                Some(_) => {}
+                None => widths.push(width),
+            }
+        }
+    }
+    let lines = rows
+        .iter()
+        .map(|cells| {
+            let line = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ");
+            line.trim_end().to_owned()
+        })
+        .collect::<Vec<_>>();
+    format!("|[\n{}\n]|", lines.join("\n"))
+}
+
+/// Turn the DocBook `<programlisting>` blocks into `|[ ... ]|` ones, keeping their
+/// `language` and their content as is but for a `<![CDATA[ ... ]]>` wrapper
+fn programlistings(input: &str) -> Cow<'_, str> {
+    PROGRAMLISTING.replace_all(input, |caps: &Captures<'_>| {
+        let content = &caps[2];
+        let content = content
+            .trim()
+            .strip_prefix("<![CDATA[")
+            .and_then(|c| c.strip_suffix("]]>"))
+            .unwrap_or(content)
+            .trim_end_matches('\n');
+        let newline = if content.starts_with('\n') { "" } else { "\n" };
+        match PROGRAMLISTING_LANGUAGE.captures(&caps[1]) {
+            Some(language) => format!(
+                "|[<!-- language=\"{}\" -->{}{}\n]|",
+                &language[1], newline, content
+            ),
+            None => format!("|[{}{}\n]|", newline, content),
+        }
+    })
+}
+
+/// Turn the `|[ ... ]|` blocks into markdown code blocks, transforming the text outside
+/// of them with `prose` and their content with `code`. An unterminated block is closed
+/// at the end of the input, the returned boolean is `false` then.
+fn language_blocks(
+    mut input: &str,
+    setup: &[String],
+    ignore_foreign: bool,
+    prose: impl Fn(&str) -> String,
+    code: impl Fn(&str) -> String,
+) -> (String, bool) {
     let mut out = String::with_capacity(input.len());
 
-    loop {
+    let terminated = loop {
         input = match try_split(input, LANGUAGE_BLOCK_BEGIN) {
             (before, Some(after)) => {
-                out.push_str(&format(before, env, in_type));
-                if let (before, Some(after)) =
-                    try_split(get_language(after, &mut out), LANGUAGE_BLOCK_END)
-                {
-                    out.push_str(before);
+                out.push_str(&prose(before));
+                let block = get_language(after, setup, ignore_foreign, &mut out);
+                // The code never starts on the line of the opening fence
+                if !block.is_empty() && !block.starts_with('\n') {
+                    out.push('\n');
+                }
+                if let (before, Some(after)) = try_split(block, LANGUAGE_BLOCK_END) {
+                    out.push_str(&code(before));
                     out.push_str("\n```");
+                    // The closing fence must end its line, and be separated from a block
+                    // opened right after it
+                    if !after.is_empty() && !after.starts_with('\n') {
+                        out.push('\n');
+                    }
                     after
                 } else {
-                    after
+                    out.push_str(&code(block.trim_end()));
+                    out.push_str("\n```");
+                    break false;
                 }
             }
             (before, None) => {
-                out.push_str(&format(before, env, in_type));
-                return out;
+                out.push_str(&prose(before));
+                break true;
             }
         };
-    }
+    };
+    // The blank lines separating a block from the prose before it aren't needed at the
+    // start of the doc
+    let blank = out.len() - out.trim_start_matches('\n').len();
+    out.drain(..blank);
+    (out, terminated)
 }
 
-fn get_language<'a>(entry: &'a str, out: &mut String) -> &'a str {
-    if let (_, Some(after)) = try_split(entry, LANGUAGE_SEP_BEGIN) {
-        if let (before, Some(after)) = try_split(after, LANGUAGE_SEP_END) {
-            if !["text", "rust"].contains(&before) {
-                out.push_str(&format!("\n\n**⚠️ The following code is in {} ⚠️**", before));
+fn get_language<'a>(
+    entry: &'a str,
+    setup: &[String],
+    ignore_foreign: bool,
+    out: &mut String,
+) -> &'a str {
+    // Only a comment at the start of the block, maybe on the next line, can define its
+    // language
+    if let Some(comment) = entry.trim_start().strip_prefix(LANGUAGE_SEP_BEGIN) {
+        if let (comment, Some(after)) = try_split(comment, LANGUAGE_SEP_END) {
+            let language = PROGRAMLISTING_LANGUAGE
+                .captures(comment)
+                .map(|caps| caps.get(1).unwrap().as_str())
+                .map(|l| {
+                    LANGUAGE_NAMES
+                        .iter()
+                        .find(|(name, _)| *name == l)
+                        .map_or(l, |(_, markdown)| markdown)
+                });
+            if let Some(language) = language {
+                let foreign = !["text", "rust"].contains(&language);
+                if foreign {
+                    out.push_str(&format!(
+                        "\n\n**⚠️ The following code is in {} ⚠️**",
+                        language
+                    ));
+                }
+                out.push_str(&format!("\n\n```{}", language));
+                // Tell rustdoc explicitly that the foreign code is never to be compiled
+                if foreign && ignore_foreign {
+                    out.push_str(",ignore");
+                }
+                if language == "rust" {
+                    for line in setup {
+                        out.push('\n');
+                        out.push_str(&hidden_line(line));
+                    }
+                }
+            } else {
+                // Empty or malformed comments like `<!-- -->` are dropped
+                out.push_str("\n```text");
             }
-            out.push_str(&format!("\n\n```{}", before));
             return after;
         }
     }
@@ -82,6 +938,15 @@ fn get_language<'a>(entry: &'a str, out: &mut String) -> &'a str {
     entry
 }
 
+/// Hide a line of a Rust example from the rendered docs, see `doc.example_setup`
+pub(super) fn hidden_line(line: &str) -> String {
+    if line.is_empty() {
+        "#".to_owned()
+    } else {
+        format!("# {}", line)
+    }
+}
+
 // try to get the language if any is defined or fallback to text
 fn get_markdown_language(input: &str) -> (&str, &str) {
     let (lang, after) = if let Some((lang, after)) = input.split_once('\n') {
@@ -95,16 +960,59 @@ fn get_markdown_language(input: &str) -> (&str, &str) {
 
 // Re-format codeblocks & replaces the C types and GI-docgen with proper links
 fn format(
-    mut input: &str,
-    env: &Env,
+    input: &str,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    misses: &RefCell<Vec<Miss>>,
+) -> String {
+    format_segments(
+        input,
+        &env.config.doc.doctest_setup(),
+        |prose| {
+            let prose = expand_tabs(prose, env);
+            let prose = ulinks(&prose);
+            let prose = if env.config.doc.autolink_urls {
+                replace_symbols(&autolink_urls(&prose), env, in_type, misses)
+            } else {
+                replace_symbols(&prose, env, in_type, misses)
+            };
+            let prose = separate_code_spans(&prose);
+            let prose = decode_entities(&prose).into_owned();
+            let prose = if env.config.doc.see_also_lists {
+                see_also_lists(&prose).into_owned()
+            } else {
+                prose
+            };
+            let prose = if env.config.doc.inline_file_paths {
+                inline_file_paths(&prose).into_owned()
+            } else {
+                prose
+            };
+            // Last so that the markdown generated by the other passes is complete
+            if env.config.doc.escape_markdown {
+                escape_markdown(&prose)
+            } else {
+                prose
+            }
+        },
+        |code| expand_tabs(code, env).into_owned(),
+    )
+}
+
+/// Re-format the markdown codeblocks, transforming the text outside of them with
+/// `prose` and their content with `code`
+fn format_segments(
+    mut input: &str,
+    setup: &[String],
+    prose: impl Fn(&str) -> String,
+    code: impl Fn(&str) -> String,
 ) -> String {
     let mut ret = String::with_capacity(input.len());
     loop {
         input = match try_split(input, "```") {
             (before, Some(after)) => {
                 // if we are inside a codeblock
-                ret.push_str(&replace_symbols(before, env, in_type));
+                ret.push_str(&prose(before));
 
                 let (lang, after) = get_markdown_language(after);
                 if !["text", "rust", "xml", "css", "json", "html"].contains(&lang)
@@ -113,9 +1021,15 @@ fn format(
                     ret.push_str(&format!("**⚠️ The following code is in {0} ⚠️**\n\n", lang));
                 }
                 ret.push_str(&format!("```{}\n", lang));
+                if lang == "rust" || lang.starts_with("rust,") {
+                    for line in setup {
+                        ret.push_str(&hidden_line(line));
+                        ret.push('\n');
+                    }
+                }
 
                 if let (before, Some(after)) = try_split(after, "```") {
-                    ret.push_str(before);
+                    ret.push_str(&code(before));
                     ret.push_str("```");
                     after
                 } else {
@@ -123,85 +1037,741 @@ fn format(
                 }
             }
             (before, None) => {
-                ret.push_str(&replace_symbols(before, env, in_type));
+                ret.push_str(&prose(before));
                 return ret;
             }
         }
     }
 }
 
-fn replace_symbols(
-    input: &str,
-    env: &Env,
-    in_type: Option<(&TypeId, Option<LocationInObject>)>,
-) -> String {
-    if env.config.use_gi_docgen {
-        let out = gi_docgen::replace_c_types(input, env, in_type);
-        let out = GI_DOCGEN_SYMBOL.replace_all(&out, |caps: &Captures<'_>| match &caps[2] {
-            "TRUE" => "[`true`]".to_string(),
-            "FALSE" => "[`false`]".to_string(),
-            "NULL" => "[`None`]".to_string(),
-            symbol_name => match &caps[1] {
-                // Opt-in only for the %SYMBOLS, @/# causes breakages
-                "%" => find_constant_or_variant_wrapper(symbol_name, env, in_type),
-                s => panic!("Unknown symbol prefix `{}`", s),
-            },
-        });
-        let out = GDK_GTK.replace_all(&out, |caps: &Captures<'_>| {
-            find_type(&caps[2], env).unwrap_or_else(|| format!("`{}`", &caps[2]))
-        });
-
-        out.to_string()
+/// Render `content` as inline code, with a fence longer than its runs of backticks and
+/// padded with spaces when it starts or ends with one
+pub(crate) fn inline_code(content: &str) -> String {
+    let longest = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    if content.starts_with('`') || content.ends_with('`') {
+        format!("{0} {1} {0}", fence, content)
     } else {
-        replace_c_types(input, env, in_type)
+        format!("{0}{1}{0}", fence, content)
     }
 }
 
-static SYMBOL: Lazy<Regex> = Lazy::new(|| Regex::new(r"([@#%])(\w+\b)([:.]+[\w-]+\b)?").unwrap());
-static GI_DOCGEN_SYMBOL: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"([%])(\w+\b)([:.]+[\w-]+\b)?").unwrap());
-static FUNCTION: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"([@#%])?(\w+\b[:.]+)?(\b[a-z0-9_]+)\(\)").unwrap());
-// **note**
-// The optional . at the end is to make the regex more relaxed for some weird broken cases on gtk3's docs
-// it doesn't hurt other docs so please don't drop it
-static GDK_GTK: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"`([^\(:])?((G[dts]k|Pango|cairo_|graphene_|Adw|Hdy|GtkSource)\w+\b)(\.)?`")
-        .unwrap()
-});
-static TAGS: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[\w/-]+>").unwrap());
-static SPACES: Lazy<Regex> = Lazy::new(|| Regex::new(r"[ ]{2,}").unwrap());
+/// Separate the inline code spans left touching by the substitutions, `` `A``B` `` would
+/// otherwise be read as a single span containing `` A``B ``
+fn separate_code_spans(input: &str) -> Cow<'_, str> {
+    if !input.contains("``") {
+        return Cow::Borrowed(input);
+    }
+    let touches = |c: Option<char>| matches!(c, Some(c) if !c.is_whitespace());
+    let mut out = String::with_capacity(input.len() + 2);
+    // The fence of the code span being read
+    let mut open = None;
+    let mut rest = input;
+    while let Some(start) = rest.find('`') {
+        let (before, from) = rest.split_at(start);
+        let after = from.trim_start_matches('`');
+        let fence = &from[..from.len() - after.len()];
+        out.push_str(before);
+        match open {
+            // The end of the span directly followed by the start of another one
+            Some(len)
+                if fence.len() > len
+                    && touches(before.chars().next_back())
+                    && touches(after.chars().next()) =>
+            {
+                out.push_str(&fence[..len]);
+                out.push(' ');
+                out.push_str(&fence[len..]);
+                open = Some(fence.len() - len);
+            }
+            Some(len) if len == fence.len() => {
+                out.push_str(fence);
+                open = None;
+            }
+            Some(_) => out.push_str(fence),
+            None => {
+                out.push_str(fence);
+                open = Some(fence.len());
+            }
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
 
-fn replace_c_types(
-    entry: &str,
-    env: &Env,
-    in_type: Option<(&TypeId, Option<LocationInObject>)>,
-) -> String {
-    let out = FUNCTION.replace_all(entry, |caps: &Captures<'_>| {
-        let name = &caps[3];
-        find_method_or_function_by_ctype(None, name, env, in_type).unwrap_or_else(|| {
-            if !IGNORE_C_WARNING_FUNCS.contains(&name) {
-                info!("No function found for `{}()`", name);
+/// Backslash-escape the markdown characters of the plain text, see `doc.escape_markdown`.
+/// The links, inline code and autolinks, the `*emphasis*` pairs and the list bullets
+/// are kept as they are.
+fn escape_markdown(input: &str) -> String {
+    replace_outside(input, &MARKDOWN_CONSTRUCT, |plain| {
+        replace_outside(plain, &EMPHASIS_RUN, escape_markdown_chars)
+    })
+}
+
+fn escape_markdown_chars(plain: &str) -> String {
+    let chars = plain.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(plain.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let escape = match c {
+            '*' | '[' | ']' | '`' => true,
+            // Only the underscores at the edges of words can start or end emphasis
+            '_' => {
+                let alphanumeric = |c: Option<&char>| matches!(c, Some(c) if c.is_alphanumeric());
+                !(i > 0 && alphanumeric(chars.get(i - 1)) && alphanumeric(chars.get(i + 1)))
             }
-            format!("`{}{}()`", caps.get(2).map_or("", |m| m.as_str()), name)
-        })
-    });
+            _ => false,
+        };
+        if escape {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
 
+/// Turn the bare URLs into `<https://...>` autolinks so they are clickable, see
+/// `doc.autolink_urls`
+fn autolink_urls(input: &str) -> Cow<'_, str> {
+    BARE_URL.replace_all(input, |caps: &Captures<'_>| match caps.get(1) {
+        Some(url) => {
+            let url = url.as_str();
+            // A trailing punctuation mark ends the sentence rather than the URL
+            let link = url.trim_end_matches(&['.', ',', ';', ':', '!', '?', '\''][..]);
+            format!("<{}>{}", link, &url[link.len()..])
+        }
+        // URLs in markdown links, autolinks and inline code are kept as is
+        None => caps[0].to_owned(),
+    })
+}
+
+/// Turn the "See A, B and C." sentences made only of links into a list of the links, see
+/// `doc.see_also_lists`
+fn see_also_lists(input: &str) -> Cow<'_, str> {
+    SEE_ALSO.replace_all(input, |caps: &Captures<'_>| {
+        let mut out = "See also:\n".to_owned();
+        for link in DOC_LINK.find_iter(&caps[1]) {
+            out.push_str("\n- ");
+            out.push_str(link.as_str());
+        }
+        // The list must be followed by a blank line or the end of the docs
+        out.push_str(&caps[2]);
+        out
+    })
+}
+
+/// Render the absolute file paths like `/etc/foo.conf` as inline code, see
+/// `doc.inline_file_paths`
+fn inline_file_paths(input: &str) -> Cow<'_, str> {
+    FILE_PATH.replace_all(input, |caps: &Captures<'_>| match caps.get(2) {
+        Some(path) => format!("{}`{}`", &caps[1], path.as_str()),
+        // Paths in links, URLs and inline code are kept as is
+        None => caps[0].to_owned(),
+    })
+}
+
+/// Render the DocBook `<keycombo><keycap>Ctrl</keycap><keycap>C</keycap></keycombo>` key
+/// combinations as `` `Ctrl`+`C` ``, see `doc.key_combos`
+fn key_combos(input: &str) -> Cow<'_, str> {
+    KEYCOMBO.replace_all(input, |caps: &Captures<'_>| match caps.get(2) {
+        Some(key) => inline_code(key.as_str().trim()),
+        None => KEYCAP
+            .captures_iter(&caps[1])
+            .map(|key| inline_code(key[1].trim()))
+            .collect::<Vec<_>>()
+            .join("+"),
+    })
+}
+
+/// Turn the DocBook `<ulink url="...">` into markdown links, their url is kept in
+/// `<>` so it isn't taken for references
+fn ulinks(input: &str) -> Cow<'_, str> {
+    ULINK.replace_all(input, |caps: &Captures<'_>| {
+        let url = match attribute(&caps[1], "url") {
+            Some(url) => url,
+            None => return caps[0].to_owned(),
+        };
+        match link_text(caps.get(2)) {
+            Some(text) => format!("[{}](<{}>)", text, url),
+            None => format!("<{}>", url),
+        }
+    })
+}
+
+/// Turn the DocBook `<link linkend="...">` into intra-doc links when `resolve` finds
+/// their target, else into their text
+fn linkends(input: &str, resolve: impl Fn(&str) -> Option<String>) -> Cow<'_, str> {
+    LINK.replace_all(input, |caps: &Captures<'_>| {
+        let linkend = match attribute(&caps[1], "linkend") {
+            Some(linkend) => linkend,
+            None => return caps[0].to_owned(),
+        };
+        let text = link_text(caps.get(2));
+        match (resolve(linkend), text) {
+            (Some(link), Some(text)) => match link.rsplit_once("][") {
+                Some((_, target)) => format!("[{}][{}", text, target),
+                None => link,
+            },
+            (Some(link), None) => link,
+            (None, Some(text)) => text,
+            (None, None) => inline_code(linkend),
+        }
+    })
+}
+
+/// The value of an attribute of a tag, quoted either way
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    ATTRIBUTE
+        .captures_iter(attributes)
+        .find(|caps| &caps[1] == name)
+        .and_then(|caps| caps.get(2).or_else(|| caps.get(3)))
+        .map(|value| value.as_str())
+}
+
+/// The text of a link on a single line, `None` if empty
+fn link_text(text: Option<regex::Match<'_>>) -> Option<String> {
+    let text = text?
+        .as_str()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Translate the DocBook paragraphs, lists and `<literal>` to markdown
+fn docbook(input: &str) -> Cow<'_, str> {
+    if !input.contains("<para>") && !input.contains("<listitem>") && !LITERAL.is_match(input) {
+        return Cow::Borrowed(input);
+    }
+    let out = LITERAL.replace_all(input, |caps: &Captures<'_>| inline_code(&caps[1]));
+    let out = LIST_ITEM.replace_all(&out, |caps: &Captures<'_>| {
+        let item = PARA_TAG.replace_all(&caps[1], " ");
+        format!(
+            "\n- {}",
+            item.split_whitespace().collect::<Vec<_>>().join(" ")
+        )
+    });
+    let out = ITEMIZED_LIST.replace_all(&out, |caps: &Captures<'_>| {
+        format!("\n\n{}\n\n", caps[1].trim())
+    });
+    let out = PARA.replace_all(&out, |caps: &Captures<'_>| {
+        format!("\n\n{}\n\n", caps[1].trim())
+    });
+    let out = BLANK_LINES.replace_all(&out, "\n\n");
+    // The paragraph breaks are only added between the blocks
+    let leading = &input[..input.len() - input.trim_start_matches('\n').len()];
+    let trailing = &input[input.trim_end_matches('\n').len()..];
+    Cow::Owned(format!("{}{}{}", leading, out.trim_matches('\n'), trailing))
+}
+
+/// Render the DocBook `<emphasis>` as italics, or bold with the `strong` and `bold` roles
+fn emphasis(input: &str) -> Cow<'_, str> {
+    EMPHASIS.replace_all(input, |caps: &Captures<'_>| {
+        let marker = match caps.get(1).map(|role| role.as_str()) {
+            Some("strong") | Some("bold") => "**",
+            _ => "*",
+        };
+        format!("{0}{1}{0}", marker, caps[2].trim())
+    })
+}
+
+/// Expand the tab characters according to `doc.tab_width`
+fn expand_tabs<'a>(input: &'a str, env: &DocContext<'_>) -> Cow<'a, str> {
+    match env.config.doc.tab_width {
+        Some(width) if input.contains('\t') => Cow::Owned(expand_tabs_to(input, width)),
+        _ => Cow::Borrowed(input),
+    }
+}
+
+/// Replace every tab with spaces up to the next tab stop, so the indentation
+/// and alignment of code is kept
+fn expand_tabs_to(input: &str, width: usize) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut column = 0;
+    for c in input.chars() {
+        match c {
+            '\t' => {
+                let spaces = width - column % width;
+                out.extend(std::iter::repeat(' ').take(spaces));
+                column += spaces;
+            }
+            '\n' => {
+                out.push(c);
+                column = 0;
+            }
+            _ => {
+                out.push(c);
+                column += 1;
+            }
+        }
+    }
+    out
+}
+
+fn replace_symbols(
+    input: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    misses: &RefCell<Vec<Miss>>,
+) -> String {
+    // The `#` of the numeric entities like `&#35;` would be taken for a reference too
+    replace_outside(input, &NUMERIC_ENTITY, |input| {
+        replace_outside_links(input, |input| {
+            replace_symbols_in(input, env, in_type, misses)
+        })
+    })
+}
+
+/// Apply `replace` to everything but the `<https://...>` and `<user@example.com>`
+/// autolinks, the bare URLs and the markdown links already in the docs, as their `#` or
+/// `@` would be taken for references and their text would be linked again
+fn replace_outside_links(input: &str, replace: impl Fn(&str) -> String) -> String {
+    replace_outside(input, &EXISTING_LINK, replace)
+}
+
+/// Apply `replace` to everything but the matches of `skipped`
+fn replace_outside(input: &str, skipped: &Regex, replace: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut last = 0;
+    for m in skipped.find_iter(input) {
+        out.push_str(&replace(&input[last..m.start()]));
+        out.push_str(m.as_str());
+        last = m.end();
+    }
+    out.push_str(&replace(&input[last..]));
+    out
+}
+
+/// The characters of the named HTML entities the C docs use, mostly to escape the
+/// `#`, `%` and `@` sigils
+const HTML_ENTITIES: &[(&str, char)] = &[
+    ("num", '#'),
+    ("percnt", '%'),
+    ("commat", '@'),
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("nbsp", '\u{a0}'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("hellip", '…'),
+    ("copy", '©'),
+];
+
+/// Replace the HTML entities like `&num;` or `&#35;` by their character, after the
+/// references are linked so that an escaped sigil isn't taken for one. A `<` starting
+/// a tag and a `&` starting another entity are kept escaped.
+fn decode_entities(input: &str) -> Cow<'_, str> {
+    ENTITY.replace_all(input, |caps: &Captures<'_>| {
+        let c = if let Some(code) = caps.get(1) {
+            code.as_str().parse().ok().and_then(char::from_u32)
+        } else if let Some(code) = caps.get(2) {
+            u32::from_str_radix(code.as_str(), 16)
+                .ok()
+                .and_then(char::from_u32)
+        } else {
+            HTML_ENTITIES
+                .iter()
+                .find(|(name, _)| *name == &caps[3])
+                .map(|&(_, c)| c)
+        };
+        let after = &input[caps.get(0).unwrap().end()..];
+        match c {
+            Some('<') if after.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') => {
+                caps[0].to_owned()
+            }
+            Some('&') if ENTITY.find(after).map(|m| m.start()) == Some(0) => caps[0].to_owned(),
+            Some(c) => c.to_string(),
+            None => caps[0].to_owned(),
+        }
+    })
+}
+
+/// Whether a `SYMBOL` like match is C syntax mentioned in the prose rather than a
+/// reference, see [`is_c_literal_at`]
+fn is_c_literal(input: &str, caps: &Captures<'_>) -> bool {
+    is_c_literal_at(input, caps.get(0).unwrap().start(), &caps[2])
+}
+
+/// Whether the `%` or `#` at `start` followed by `name` is a printf format specifier like
+/// `%d` or `%02x`, the `%` of `%%` or a percentage like `100%`, or a preprocessor
+/// directive like `#ifdef`
+fn is_c_literal_at(input: &str, start: usize, name: &str) -> bool {
+    const FORMAT_SPECIFIERS: &[&str] = &[
+        "d", "i", "u", "x", "X", "o", "s", "c", "p", "f", "e", "g", "ld", "li", "lu", "lx", "lld",
+        "llu", "zu", "zd", "hhx",
+    ];
+    const DIRECTIVES: &[&str] = &[
+        "if", "ifdef", "ifndef", "elif", "else", "endif", "define", "undef", "include", "pragma",
+        "error", "warning",
+    ];
+    let before = input[..start].chars().next_back();
+    match &input[start..=start] {
+        "%" => {
+            before.map_or(false, |c| c == '%' || c.is_ascii_digit())
+                || FORMAT_SPECIFIERS
+                    .contains(&name.trim_start_matches(|c: char| c.is_ascii_digit()))
+        }
+        "#" => DIRECTIVES.contains(&name),
+        _ => false,
+    }
+}
+
+/// The `%NULL-terminated` like C idioms are kept as prose, a `None` link in the middle
+/// of them would make no sense
+fn c_idioms(input: &str) -> Cow<'_, str> {
+    C_IDIOM.replace_all(input, "$1$2")
+}
+
+/// Drop the pointer and array decorators of the `#GtkWidget*`, `#GtkWidget **` and
+/// `#guint8[]` like references as the Rust API has no raw pointers, but not the `*`
+/// closing an emphasis like `*#GtkWidget*`
+fn strip_type_decorators(input: &str) -> Cow<'_, str> {
+    TYPE_DECORATORS.replace_all(input, |caps: &Captures<'_>| {
+        if caps.get(3).is_some() && &caps[1] == "*" {
+            return caps[0].to_owned();
+        }
+        format!(
+            "{}#{}{}",
+            &caps[1],
+            &caps[2],
+            caps.get(4).map_or("", |m| m.as_str())
+        )
+    })
+}
+
+fn replace_symbols_in(
+    input: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    misses: &RefCell<Vec<Miss>>,
+) -> String {
+    let input = c_idioms(input);
+    let input = strip_type_decorators(&input);
+    let input = &*input;
+    if uses_gi_docgen(env, in_type) {
+        let prefixes = namespace_prefixes(env.library);
+        // Only the text between the gi-docgen links goes through the other passes
+        gi_docgen::replace_c_types(input, env, in_type, |text| {
+            let out = GI_DOCGEN_SYMBOL.replace_all(text, |caps: &Captures<'_>| match &caps[2] {
+                _ if is_c_literal(text, caps) => caps[0].to_owned(),
+                "TRUE" => "[`true`]".to_string(),
+                "FALSE" => "[`false`]".to_string(),
+                "NULL" => "[`None`]".to_string(),
+                symbol_name => match &caps[1] {
+                    // Opt-in only for the %SYMBOLS, @/# causes breakages
+                    "%" => find_constant_or_variant_wrapper(
+                        symbol_name,
+                        caps.get(3).map(|m| m.as_str()),
+                        env,
+                        in_type,
+                        misses,
+                    ),
+                    s => panic!("Unknown symbol prefix `{}`", s),
+                },
+            });
+            let out = GDK_GTK.replace_all(&out, |caps: &Captures<'_>| {
+                if has_namespace_prefix(&caps[2], &prefixes) {
+                    replace_gdk_gtk(caps, |type_| find_backticked_type(type_, env, in_type))
+                } else {
+                    caps[0].to_owned()
+                }
+            });
+            if env.config.doc.bare_type_links {
+                bare_types(&out, |name| find_bare_type(name, env, in_type)).into_owned()
+            } else {
+                out.into_owned()
+            }
+        })
+    } else {
+        // The legacy pass would take the `@` of the gi-docgen links for parameters
+        let input = gi_docgen::links_as_code(input);
+        let input = join_wrapped_symbols(&input, |name| find_type(name, env, in_type).is_some());
+        replace_c_types(&input, env, in_type, misses)
+    }
+}
+
+/// Whether the docs of `in_type`, or of the main namespace without one, go through the
+/// gi-docgen pass, see `options.use_gi_docgen` and `doc.skip_gi_docgen`
+fn uses_gi_docgen(
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> bool {
+    let ns_id = in_type.map_or(MAIN_NAMESPACE, |(tid, _)| tid.ns_id);
+    gi_docgen_enabled(
+        env.config.use_gi_docgen,
+        &env.config.doc.skip_gi_docgen,
+        &env.library.namespace(ns_id).name,
+    )
+}
+
+fn gi_docgen_enabled(use_gi_docgen: bool, skipped: &[String], namespace: &str) -> bool {
+    use_gi_docgen && !skipped.iter().any(|ns| ns == namespace)
+}
+
+/// Join the `#GtkWidget` references soft-wrapped as `#Gtk\nWidget`, only when the
+/// joined name (but not its first part) is a known type
+fn join_wrapped_symbols(input: &str, is_type: impl Fn(&str) -> bool) -> Cow<'_, str> {
+    WRAPPED_SYMBOL.replace_all(input, |caps: &Captures<'_>| {
+        let joined = format!("{}{}", &caps[2], &caps[3]);
+        if !is_type(&caps[2]) && is_type(&joined) {
+            format!("{}#{}", &caps[1], joined)
+        } else {
+            caps[0].to_owned()
+        }
+    })
+}
+
+static UNWRAPPED_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?: {4}|\t|\s*(?:\||#|\[[^\]]+\]:))").unwrap());
+static LINE_MARKER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:(?:> ?)+|(?:[-*+]|\d+[.)]) )?").unwrap());
+static SYMBOL: Lazy<Regex> = Lazy::new(|| Regex::new(r"([@#%])(\w+\b)([:.]+[\w-]+\b)?").unwrap());
+static C_IDIOM: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"%(NULL|TRUE|FALSE)(-(?:terminated|separated)\b)").unwrap());
+// The `*` are only pointers when they aren't followed by a word, like emphasis
+static TYPE_DECORATORS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(^|[^\w#])#(\w+)(?: ?(\*+)(\W|$)|\[\d*\])").unwrap());
+static GI_DOCGEN_SYMBOL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([%])(\w+\b)([:.]+[\w-]+\b)?").unwrap());
+static FUNCTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([@#%])?(\w+\b[:.]+)?(\b[a-z0-9_]+)\(\)").unwrap());
+// **note**
+// The backticked identifiers, only the ones with the prefix of a loaded namespace are
+// resolved, see `has_namespace_prefix`
+// The optional punctuation at the end is to make the regex more relaxed for some weird broken
+// cases on gtk3's docs like `` `GtkWidget.` ``, it doesn't hurt other docs so please don't drop it
+static BARE_TYPE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\[[^\]]*\](?:\[[^\]]*\]|\([^)]*\))?|`[^`]*`|<[^>]*>|\bhttps?://\S+|([#%@.:/])?\b([A-Za-z_]\w*)\b",
+    )
+    .unwrap()
+});
+static GDK_GTK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"`([^\w\(:`])?([A-Za-z_]\w*\b)([.,;:])?`").unwrap());
+// Function-like macros, like the GObject type check and cast ones `GTK_IS_WIDGET()` and
+// `GTK_WIDGET()`, with the backticks around them if any
+static MACRO: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(`)?\b([A-Z][A-Za-z0-9]*_[A-Za-z0-9_]*[A-Za-z0-9])\(\)(`)?").unwrap()
+});
+static ENTITY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(?:#([0-9]+)|#[xX]([0-9a-fA-F]+)|([a-zA-Z]+));").unwrap());
+static NUMERIC_ENTITY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&#(?:[0-9]+|[xX][0-9a-fA-F]+);").unwrap());
+static PROGRAMLISTING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<programlisting\b([^>]*)>(.*?)</programlisting>").unwrap());
+static TABLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)\s*<(?:informaltable|table)\b[^>]*>(.*?)</(?:informaltable|table)>\s*")
+        .unwrap()
+});
+static NESTED_TABLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"<(?:informaltable|table)\b").unwrap());
+static TABLE_TITLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<title>(.*?)</title>").unwrap());
+static TABLE_ROW: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<(?:row|tr)\b[^>]*>(.*?)</(?:row|tr)>").unwrap());
+static TABLE_CELL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<(entry|td|th)\b([^>]*)>(.*?)</(?:entry|td|th)>").unwrap());
+static COLSPEC: Lazy<Regex> = Lazy::new(|| Regex::new(r"<colspec\b([^>]*)>").unwrap());
+static SPANNING_CELL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:rowspan|colspan|morerows|namest|nameend|spanname)\s*=").unwrap()
+});
+static CELL_BLOCK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"<(?:itemizedlist|orderedlist|variablelist|programlisting|(?:informal)?example)\b|\n\s*\n",
+    )
+    .unwrap()
+});
+static ANY_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>").unwrap());
+static ORPHAN_LANGUAGE_MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*<!--\s*language\s*=\s*["'][^"']*["']\s*-->[ \t]*\n|[ \t]*<!--\s*language\s*=\s*["'][^"']*["']\s*-->"#,
+    )
+    .unwrap()
+});
+static PROGRAMLISTING_LANGUAGE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\blanguage\s*=\s*["']([\w+-]+)["']"#).unwrap());
+// The markdown gir generates or keeps: links, inline code, autolinks, escaped characters
+// and list bullets
+static MARKDOWN_CONSTRUCT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)\[`[^`]*`\](?:\[[^\]]*\])?|\[[^\]]*\]\([^)]*\)|``[^`]*``|`[^`]*`|<[^>\s]+>|\\[[:punct:]]|^[ \t]*[*+-][ \t]",
+    )
+    .unwrap()
+});
+static EMPHASIS_RUN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\*\*[^\s*](?:[^*]*[^\s*])?\*\*|\*[^\s*](?:[^*]*[^\s*])?\*").unwrap());
+// The autolinks, `[text](url)` links, `[label]: url` definitions and bare URLs
+static EXISTING_LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)<(?:(?:https?|ftp|file|mailto):[^\s<>]+|[\w.+-]+@[\w-]+(?:\.[\w-]+)+)>|\[[^\[\]]*\]\([^()\s]*\)|^[ \t]*\[[^\[\]]+\]:[ \t]+\S+|\bhttps?://[^\s<>()\[\]`]+",
+    )
+    .unwrap()
+});
+static BARE_URL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[[^\]]*\]\([^)]*\)|<[^>]*>|`[^`]*`|\b(https?://[^\s<>()\[\]`]+)").unwrap()
+});
+static FILE_PATH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\[[^\]]*\]\([^)]*\)|<[^>]*>|`[^`]*`|\bhttps?://\S+|(^|[\s("'])(/(?:[\w.+-]+/)+(?:[\w.+-]*\w)?)"#,
+    )
+    .unwrap()
+});
+static DOC_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[`[^`\]]+`\](?:\[[^\]]+\])?").unwrap());
+static SEE_ALSO: Lazy<Regex> = Lazy::new(|| {
+    let link = r"\[`[^`\]]+`\](?:\[[^\]]+\])?";
+    Regex::new(&format!(
+        r"(?m)^See ({l}(?:(?:,|,? and) {l})+)(?: for (?:more )?details)?\.[ \t]*(\n\n|\n?\z)",
+        l = link
+    ))
+    .unwrap()
+});
+static WRAPPED_SYMBOL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(^|[^\w#])#([A-Z]\w*)\n([A-Z]\w*)").unwrap());
+static CALLBACK_POINTER: Lazy<Regex> = Lazy::new(|| Regex::new(r"#(\w+) ?\*").unwrap());
+static QUALIFIED_TYPE: Lazy<Regex> = Lazy::new(|| {
+    let c_type = r"(?:(?:const|volatile)\s+)+\w+(?:\s*\*)*";
+    Regex::new(&format!("#({0})|`({0})`", c_type)).unwrap()
+});
+static KEYCOMBO: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<keycombo>((?:\s*<keycap>[^<]*</keycap>\s*)+)</keycombo>|<keycap>([^<]*)</keycap>")
+        .unwrap()
+});
+static KEYCAP: Lazy<Regex> = Lazy::new(|| Regex::new(r"<keycap>([^<]*)</keycap>").unwrap());
+static EMPHASIS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<emphasis(?:\s+role="(\w+)")?\s*>([^<]*)</emphasis>"#).unwrap());
+static ULINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<ulink\b([^>]*?)(?:/>|>(.*?)</ulink>)").unwrap());
+static LINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<link\b([^>]*?)(?:/>|>(.*?)</link>)").unwrap());
+static ATTRIBUTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(\w+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+static LITERAL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<(?:literal|code)>([^<]*)</(?:literal|code)>").unwrap());
+static LIST_ITEM: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)\s*<listitem>(.*?)</listitem>\s*").unwrap());
+static ITEMIZED_LIST: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)\s*<itemizedlist>(.*?)</itemizedlist>\s*").unwrap());
+static PARA: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)\s*<para>(.*?)</para>\s*").unwrap());
+static PARA_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"</?para>").unwrap());
+static BLANK_LINES: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n{3,}").unwrap());
+static TAGS: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[\w/-]+>").unwrap());
+// The inline code spans are matched to keep their spaces
+static SPACES: Lazy<Regex> = Lazy::new(|| Regex::new(r"`[^`\n]*`|[ ]{2,}").unwrap());
+
+fn replace_c_types(
+    entry: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    misses: &RefCell<Vec<Miss>>,
+) -> String {
+    // Before the functions and types, the text of the links could contain some
+    let entry = linkends(entry, |linkend| {
+        let linkend = linkend.trim_end_matches("-struct");
+        find_type(linkend, env, in_type).or_else(|| {
+            find_method_or_function_by_ctype(None, &linkend.replace('-', "_"), env, in_type)
+        })
+    });
+    let out = FUNCTION.replace_all(&entry, |caps: &Captures<'_>| {
+        let name = &caps[3];
+        let is_glib_crate = env.library.is_glib_crate();
+        link_override(name, &env.config.doc.link_overrides, true)
+            .map(|path| gen_core_function_doc_link(path, is_glib_crate))
+            .or_else(|| find_static_type(name, env))
+            .or_else(|| match caps.get(2) {
+                // The unqualified references are first looked for in the methods of `in_type`
+                None => find_in_type_method(name, env, in_type),
+                Some(qualifier) => find_qualified_method(qualifier.as_str(), name, env, in_type),
+            })
+            .or_else(|| {
+                function_link(name, &env.config.doc.function_links)
+                    .map(|path| gen_core_function_doc_link(path, is_glib_crate))
+            })
+            .or_else(|| find_method_or_function_by_ctype(None, name, env, in_type))
+            .or_else(|| {
+                let type_ids = env.analysis.enumerations.iter().map(|e| e.type_id);
+                find_error_domain(env.library, type_ids, name)
+                    .map(|ty| gen_symbol_doc_link(ty, env))
+            })
+            .or_else(|| {
+                link_override(name, &env.config.doc.link_overrides, false)
+                    .map(|path| gen_core_function_doc_link(path, is_glib_crate))
+            })
+            .map(|link| {
+                let function = format!("{}{}()", caps.get(2).map_or("", |m| m.as_str()), name);
+                with_link_text(link, &function, env.config.doc.link_text)
+            })
+            .map(|link| with_availability(link, function_versions(name, env), env))
+            .or_else(|| {
+                let code = format!("`{}{}()`", caps.get(2).map_or("", |m| m.as_str()), name);
+                with_removed_note(&code, name, &env.config.doc.removed_items)
+            })
+            .unwrap_or_else(|| {
+                if !is_ignored_function(name, &env.config.doc) {
+                    misses.borrow_mut().push(Miss {
+                        kind: RefKind::Function,
+                        symbol: name.to_owned(),
+                        // Only this very common miss can be silenced, see `doc.missing_function_log`
+                        log: env
+                            .config
+                            .doc
+                            .missing_function_log
+                            .level()
+                            .map(|level| (level, format!("No function found for `{}()`", name))),
+                        record: true,
+                    });
+                }
+                let function = format!("{}{}()", caps.get(2).map_or("", |m| m.as_str()), name);
+                unresolved_text(&function, env.config.doc.unresolved_style)
+            })
+    });
+
+    let out = replace_callback_pointers(&out, |name| {
+        env.type_lookups.callbacks.get(name).map(|c_type| {
+            gen_sys_type_doc_link(
+                env.main_sys_crate_name(),
+                c_type,
+                env.library.is_glib_crate(),
+            )
+        })
+    });
+    let out = replace_qualified_types(&out, |name| find_type(name, env, in_type));
     let out = SYMBOL.replace_all(&out, |caps: &Captures<'_>| match &caps[2] {
+        _ if is_c_literal(&out, caps) => caps[0].to_owned(),
         "TRUE" => "[`true`]".to_string(),
         "FALSE" => "[`false`]".to_string(),
         "NULL" => "[`None`]".to_string(),
         symbol_name => match &caps[1] {
-            "%" => find_constant_or_variant_wrapper(symbol_name, env, in_type),
+            "%" => find_constant_or_variant_wrapper(
+                symbol_name,
+                caps.get(3).map(|m| m.as_str()),
+                env,
+                in_type,
+                misses,
+            ),
             "#" => {
                 if let Some(member_path) = caps.get(3).map(|m| m.as_str()) {
-                    let method_name = member_path.trim_start_matches('.');
-                    find_member(symbol_name, method_name, env, in_type).unwrap_or_else(|| {
-                        info!("`#{}` not found as method", symbol_name);
+                    find_member(symbol_name, member_path, env, in_type).unwrap_or_else(|| {
+                        misses.borrow_mut().push(Miss {
+                            kind: RefKind::Member,
+                            symbol: format!("{}{}", symbol_name, member_path),
+                            log: Some((
+                                Level::Info,
+                                format!("`#{}` not found as method", symbol_name),
+                            )),
+                            record: true,
+                        });
                         format!("`{}{}`", symbol_name, member_path)
                     })
-                } else if let Some(type_) = find_type(symbol_name, env) {
-                    type_
+                } else if let Some(constant) = find_macro_constant(symbol_name, env, in_type) {
+                    with_link_text(constant, symbol_name, env.config.doc.link_text)
+                } else if let Some(type_) = find_type(symbol_name, env, in_type) {
+                    let type_ = with_link_text(type_, symbol_name, env.config.doc.link_text);
+                    with_availability(type_, type_versions(symbol_name, env), env)
                 } else if let Some(constant_or_variant) =
                     find_constant_or_variant(symbol_name, env, in_type)
                 {
@@ -210,15 +1780,29 @@ fn replace_c_types(
                         symbol_name
                     );
                     constant_or_variant
+                } else if let Some(type_) = find_plural_type(symbol_name, env, in_type) {
+                    type_
+                } else if let Some(removed) = with_removed_note(
+                    &format!("`{}`", symbol_name),
+                    symbol_name,
+                    &env.config.doc.removed_items,
+                ) {
+                    removed
                 } else {
-                    info!("Type `#{}` not found", symbol_name);
-                    format!("`{}`", symbol_name)
+                    let namespace = c_name_namespace(env.library, symbol_name);
+                    misses.borrow_mut().push(Miss {
+                        kind: RefKind::Type,
+                        symbol: symbol_name.to_owned(),
+                        log: Some((Level::Info, format!("Type `#{}` not found", symbol_name))),
+                        record: !is_ignored_type(symbol_name, namespace, &env.config.doc),
+                    });
+                    unresolved_text(symbol_name, env.config.doc.unresolved_style)
                 }
             }
             "@" => {
                 // XXX: Theoretically this code should check if the resulting
                 // symbol truly belongs to `in_type`!
-                if let Some(type_) = find_type(symbol_name, env) {
+                if let Some(type_) = find_type(symbol_name, env, in_type) {
                     warn!(
                         "`{}` matches a type and should use `#` prefix instead of `%`",
                         symbol_name
@@ -240,321 +1824,5052 @@ fn replace_c_types(
             s => panic!("Unknown symbol prefix `{}`", s),
         },
     });
+    let prefixes = namespace_prefixes(env.library);
     let out = GDK_GTK.replace_all(&out, |caps: &Captures<'_>| {
-        find_type(&caps[2], env).unwrap_or_else(|| format!("`{}`", &caps[2]))
+        if has_namespace_prefix(&caps[2], &prefixes) {
+            replace_gdk_gtk(caps, |type_| find_backticked_type(type_, env, in_type))
+        } else {
+            caps[0].to_owned()
+        }
     });
+    let out = if env.config.doc.key_combos {
+        key_combos(&out)
+    } else {
+        Cow::Borrowed(&*out)
+    };
+    let out = docbook(&out);
+    let out = emphasis(&out);
+    // The unknown tags are kept as inline code
     let out = TAGS.replace_all(&out, "`$0`");
-    SPACES.replace_all(&out, " ").into_owned()
+    let out = if env.config.doc.bare_type_links {
+        bare_types(&out, |name| find_bare_type(name, env, in_type))
+    } else {
+        out
+    };
+    // Done after the tags as the generated links contain generics
+    let out = replace_macros(&out, |name| {
+        let is_glib_crate = env.library.is_glib_crate();
+        link_override(name, &env.config.doc.link_overrides, true)
+            .map(|path| gen_core_function_doc_link(path, is_glib_crate))
+            .or_else(|| find_type_macro(name, env))
+            .or_else(|| {
+                link_override(name, &env.config.doc.link_overrides, false)
+                    .map(|path| gen_core_function_doc_link(path, is_glib_crate))
+            })
+            .or_else(|| {
+                let code = format!("`{}()`", name);
+                with_removed_note(&code, name, &env.config.doc.removed_items)
+            })
+            .or_else(|| {
+                if !is_ignored_function(name, &env.config.doc) {
+                    misses.borrow_mut().push(Miss {
+                        kind: RefKind::Function,
+                        symbol: name.to_owned(),
+                        log: Some((
+                            Level::Info,
+                            format!("No Rust equivalent found for the macro `{}()`", name),
+                        )),
+                        record: true,
+                    });
+                }
+                None
+            })
+    });
+    collapse_spaces(&out).into_owned()
 }
 
-/// Wrapper around [`find_constant_or_variant`] that fallbacks to returning
-/// the `symbol_name`
-fn find_constant_or_variant_wrapper(
-    symbol_name: &str,
-    env: &Env,
-    in_type: Option<(&TypeId, Option<LocationInObject>)>,
-) -> String {
-    find_constant_or_variant(symbol_name, env, in_type).unwrap_or_else(|| {
-        info!("Constant or variant `%{}` not found", symbol_name);
-        format!("`{}`", symbol_name)
+/// Link the words of the prose `find` knows as types, outside of the links, inline code,
+/// tags and URLs, see `doc.bare_type_links`. The words after a sigil or a `.` are kept.
+fn bare_types(input: &str, find: impl Fn(&str) -> Option<String>) -> Cow<'_, str> {
+    BARE_TYPE.replace_all(input, |caps: &Captures<'_>| {
+        match (caps.get(1), caps.get(2)) {
+            (None, Some(name)) => find(name.as_str()).unwrap_or_else(|| caps[0].to_owned()),
+            _ => caps[0].to_owned(),
+        }
     })
 }
 
-fn find_member(
-    type_: &str,
-    method_name: &str,
-    env: &Env,
+/// Only the exact C names of the types of the namespace are linked without a sigil
+fn find_bare_type(
+    name: &str,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
 ) -> Option<String> {
-    let symbols = env.symbols.borrow();
-    let is_signal = method_name.starts_with("::");
-    let is_property = !is_signal && method_name.starts_with(':');
-    if !is_signal && !is_property {
-        find_method_or_function_by_ctype(Some(type_), method_name, env, in_type)
-    } else {
-        env.analysis
-            .objects
-            .values()
-            .find(|o| o.c_type == type_)
-            .map(|info| {
-                let sym = symbols.by_tid(info.type_id).unwrap(); // we are sure the object exists
-                let name = method_name.trim_start_matches(':');
-                if is_property {
-                    gen_property_doc_link(&sym.full_rust_name(), name)
-                } else {
-                    gen_signal_doc_link(&sym.full_rust_name(), name)
-                }
-            })
+    if !env.type_index.contains_key(name) {
+        return None;
     }
+    find_type(name, env, in_type)
 }
 
-fn find_constant_or_variant(
-    symbol: &str,
-    env: &Env,
-    in_type: Option<(&TypeId, Option<LocationInObject>)>,
-) -> Option<String> {
-    if let Some((flag_info, member_info)) = env.analysis.flags.iter().find_map(|f| {
-        f.type_(&env.library)
-            .members
-            .iter()
-            .find(|m| m.c_identifier == symbol && !m.status.ignored())
-            .map(|m| (f, m))
-    }) {
-        Some(gen_member_doc_link(
-            flag_info.type_id,
-            &nameutil::bitfield_member_name(&member_info.name),
-            env,
-            in_type,
-        ))
-    } else if let Some((enum_info, member_info)) = env.analysis.enumerations.iter().find_map(|e| {
-        e.type_(&env.library)
-            .members
-            .iter()
-            .find(|m| m.c_identifier == symbol && !m.status.ignored())
-            .map(|m| (e, m))
-    }) {
-        Some(gen_member_doc_link(
-            enum_info.type_id,
-            &nameutil::enum_member_name(&member_info.name),
-            env,
-            in_type,
-        ))
-    } else if let Some(const_info) = env
+/// Link the `GTK_IS_WIDGET()` like function-like macros, replacing the inline code
+/// they may be in, the ones `find` has no link for become inline code unless they
+/// already are
+fn replace_macros(input: &str, find: impl Fn(&str) -> Option<String>) -> Cow<'_, str> {
+    MACRO.replace_all(input, |caps: &Captures<'_>| {
+        let (open, close) = (caps.get(1), caps.get(3));
+        match find(&caps[2]) {
+            Some(link) if open.is_some() && close.is_some() => link,
+            _ if open.is_some() && close.is_some() => caps[0].to_owned(),
+            link => format!(
+                "{}{}{}",
+                open.map_or("", |m| m.as_str()),
+                link.unwrap_or_else(|| format!("`{}()`", &caps[2])),
+                close.map_or("", |m| m.as_str())
+            ),
+        }
+    })
+}
+
+/// Collapse the runs of spaces outside of inline code, the code blocks are never
+/// given to the prose transformations
+fn collapse_spaces(input: &str) -> Cow<'_, str> {
+    SPACES.replace_all(input, |caps: &Captures<'_>| {
+        if caps[0].starts_with('`') {
+            caps[0].to_owned()
+        } else {
+            " ".to_owned()
+        }
+    })
+}
+
+/// Link a GObject type check/cast macro to the corresponding `Cast` method, see
+/// `doc.type_check_macro_link` and `doc.type_cast_macro_link`
+fn find_type_macro(name: &str, env: &DocContext<'_>) -> Option<String> {
+    let (is_check, get_type) = parse_type_macro(name);
+    let obj_info = env
         .analysis
-        .constants
-        .iter()
-        .find(|c| c.glib_name == symbol)
-    {
-        Some(gen_const_doc_link(const_info))
+        .objects
+        .values()
+        .find(|o| o.get_type == get_type)?;
+    let (configured, default) = if is_check {
+        (&env.config.doc.type_check_macro_link, "prelude::Cast::is")
     } else {
-        None
+        (
+            &env.config.doc.type_cast_macro_link,
+            "prelude::Cast::dynamic_cast",
+        )
+    };
+    let path = configured.clone().unwrap_or_else(|| {
+        let glib = if env.library.is_glib_crate() {
+            "crate"
+        } else {
+            "glib"
+        };
+        format!("{}::{}", glib, default)
+    });
+    if path.is_empty() {
+        return Some(format!("`{}()`", name));
     }
+    let symbols = env.symbols;
+    let sym = symbols.by_tid(obj_info.type_id)?;
+    Some(gen_type_macro_doc_link(&path, &sym.full_rust_name()))
 }
 
-// A list of types that are automatically ignored by the `find_type` function
-const IGNORED_C_TYPES: [&str; 6] = [
-    "gconstpointer",
-    "guint16",
-    "guint",
-    "gunicode",
-    "gchararray",
-    "GList",
-];
-/// either an object/interface, record, enum or a flag
-fn find_type(type_: &str, env: &Env) -> Option<String> {
-    if IGNORED_C_TYPES.contains(&type_) {
+/// Link the `_get_type` function of a type to its `StaticType::static_type`
+fn find_static_type(get_type: &str, env: &DocContext<'_>) -> Option<String> {
+    if !get_type.ends_with("_get_type") {
         return None;
     }
-
-    let type_id = if let Some(obj) = env.analysis.objects.values().find(|o| o.c_type == type_) {
-        Some(obj.type_id)
-    } else if let Some(record) = env
+    let is_get_type = |glib_get_type: Option<&str>| glib_get_type == Some(get_type);
+    let type_id = env
         .analysis
-        .records
+        .objects
         .values()
-        .find(|r| r.type_(&env.library).c_type == type_)
-    {
-        Some(record.type_id)
-    } else if let Some(enum_) = env
-        .analysis
-        .enumerations
-        .iter()
-        .find(|e| e.type_(&env.library).c_type == type_)
-    {
-        Some(enum_.type_id)
-    } else if let Some(flag) = env
-        .analysis
-        .flags
-        .iter()
-        .find(|f| f.type_(&env.library).c_type == type_)
-    {
-        Some(flag.type_id)
-    } else {
-        None
-    };
+        .find(|o| o.get_type == get_type)
+        .map(|o| o.type_id)
+        .or_else(|| {
+            env.analysis
+                .records
+                .values()
+                .find(|r| is_get_type(r.glib_get_type.as_ref().map(|(f, _)| f.as_str())))
+                .map(|r| r.type_id)
+        })
+        .or_else(|| {
+            env.analysis
+                .enumerations
+                .iter()
+                .find(|e| is_get_type(e.type_(env.library).glib_get_type.as_deref()))
+                .map(|e| e.type_id)
+        })
+        .or_else(|| {
+            env.analysis
+                .flags
+                .iter()
+                .find(|f| is_get_type(f.type_(env.library).glib_get_type.as_deref()))
+                .map(|f| f.type_id)
+        })?;
+    let symbols = env.symbols;
+    let sym = symbols.by_tid(type_id)?;
+    Some(gen_static_type_doc_link(&sym.full_rust_name()))
+}
 
-    type_id.map(|ty| gen_symbol_doc_link(ty, env))
+/// The `_error_quark` functions are only generated as the `ErrorDomain` implementation
+/// of the error enum with their quark as `error-domain`
+fn find_error_domain(
+    library: &Library,
+    type_ids: impl IntoIterator<Item = TypeId>,
+    quark_fn: &str,
+) -> Option<TypeId> {
+    if !quark_fn.ends_with("_error_quark") {
+        return None;
+    }
+    let quark = quark_fn.replace('_', "-");
+    type_ids.into_iter().find(|&tid| match library.type_(tid) {
+        Type::Enumeration(enum_) => {
+            matches!(enum_.error_domain, Some(ErrorDomain::Quark(ref q)) if *q == quark)
+        }
+        _ => false,
+    })
 }
 
-fn find_method_or_function_by_ctype(
-    c_type: Option<&str>,
+fn gen_static_type_doc_link(type_: &str) -> String {
+    format!("[`{t}::static_type`][crate::{t}::static_type]", t = type_)
+}
+
+/// Splits a type macro into whether it is a type check and the name of the `get_type`
+/// function of the type it is about
+fn parse_type_macro(name: &str) -> (bool, String) {
+    match name.split_once("_IS_") {
+        Some((prefix, type_)) => (
+            true,
+            format!("{}_{}_get_type", prefix, type_).to_lowercase(),
+        ),
+        None => (false, format!("{}_get_type", name).to_lowercase()),
+    }
+}
+
+fn gen_type_macro_doc_link(path: &str, type_: &str) -> String {
+    let method = path.rsplit("::").next().unwrap_or(path);
+    format!("[`{}::<{}>()`][{}()]", method, type_, path)
+}
+
+/// Wrapper around [`find_constant_or_variant`] that fallbacks to returning
+/// the `symbol_name`, a `%GtkAlign.START` like `member_path` names a member of the type
+fn find_constant_or_variant_wrapper(
+    symbol_name: &str,
+    member_path: Option<&str>,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    misses: &RefCell<Vec<Miss>>,
+) -> String {
+    let type_member = member_path
+        .and_then(|path| path.strip_prefix('.'))
+        .and_then(|member| find_type_member(symbol_name, member, env, in_type));
+    if let Some(link) = type_member {
+        let constant = format!("{}{}", symbol_name, member_path.unwrap_or_default());
+        return with_link_text(link, &constant, env.config.doc.link_text);
+    }
+    let constant = find_constant_or_variant(symbol_name, env, in_type)
+        .map(|link| with_link_text(link, symbol_name, env.config.doc.link_text));
+    constant.unwrap_or_else(|| {
+        let namespace = c_name_namespace(env.library, symbol_name);
+        if !is_ignored_constant(symbol_name, namespace, &env.config.doc) {
+            misses.borrow_mut().push(Miss {
+                kind: RefKind::Constant,
+                symbol: symbol_name.to_owned(),
+                log: Some((
+                    Level::Info,
+                    format!("Constant or variant `%{}` not found", symbol_name),
+                )),
+                record: true,
+            });
+        }
+        unresolved_text(symbol_name, env.config.doc.unresolved_style)
+    })
+}
+
+fn find_constant(
+    symbol: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    find_constant_info(&env.analysis.constants, symbol, |version| {
+        is_available_in(env, in_type, version)
+    })
+    .map(gen_const_doc_link)
+}
+
+fn find_constant_info<'a>(
+    constants: &'a [analysis::constants::Info],
+    symbol: &str,
+    is_available: impl Fn(Option<Version>) -> bool,
+) -> Option<&'a analysis::constants::Info> {
+    constants
+        .iter()
+        .find(|c| c.glib_name == symbol && is_available(c.version))
+}
+
+/// A `#GTK_MAJOR_VERSION` like reference to a `#define` constant, it is linked before
+/// looking for a type and without a warning about its sigil
+fn find_macro_constant(
+    symbol: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    if !is_macro_name(symbol) {
+        return None;
+    }
+    find_constant(symbol, env, in_type)
+}
+
+/// Whether the name is spelled like a C macro, `GTK_MAJOR_VERSION`
+fn is_macro_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// A member of an error domain enum of another namespace, like `%G_FILE_ERROR_NOENT` in
+/// the gio docs, as these are the errors returned by the throwing functions
+fn find_error_domain_member(
+    symbol: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    env.library
+        .types()
+        .filter(|(tid, _)| tid.ns_id != MAIN_NAMESPACE)
+        .find_map(|(tid, type_)| match type_ {
+            Type::Enumeration(enum_) if enum_.error_domain.is_some() => enum_
+                .members
+                .iter()
+                .find(|m| m.c_identifier == symbol && !m.status.ignored())
+                .map(|m| (tid, m)),
+            _ => None,
+        })
+        .filter(|&(tid, _)| is_generated_type(env, tid))
+        .map(|(tid, member)| {
+            gen_member_doc_link(tid, &nameutil::enum_member_name(&member.name), env, in_type)
+        })
+}
+
+/// What follows the type of a `#Type` reference
+#[derive(Debug, PartialEq, Eq)]
+enum MemberRef<'a> {
+    /// `#GtkButton.set_label`
+    Method(&'a str),
+    /// `#GtkButton:label`
+    Property(&'a str),
+    /// `#GtkButton::clicked`
+    Signal(&'a str),
+}
+
+fn parse_member_ref(member_path: &str) -> MemberRef<'_> {
+    if let Some(signal) = member_path.strip_prefix("::") {
+        MemberRef::Signal(signal)
+    } else if let Some(property) = member_path.strip_prefix(':') {
+        MemberRef::Property(property)
+    } else {
+        MemberRef::Method(member_path.trim_start_matches('.'))
+    }
+}
+
+fn find_member(
+    type_: &str,
+    member_path: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let (name, is_signal) = match parse_member_ref(member_path) {
+        MemberRef::Method(method_name) => {
+            return find_vfunc(type_, method_name, env, in_type)
+                .or_else(|| {
+                    find_method_or_function_by_ctype(Some(type_), method_name, env, in_type)
+                })
+                .or_else(|| find_qualified_method(type_, method_name, env, in_type))
+                .or_else(|| find_type_member(type_, method_name, env, in_type))
+                .or_else(|| find_record_field(type_, method_name, env, in_type))
+        }
+        MemberRef::Property(name) => (name, false),
+        MemberRef::Signal(name) => (name, true),
+    };
+    let symbols = env.symbols;
+    env.analysis
+        .objects
+        .values()
+        .find(|o| o.c_type == type_)
+        .map(|info| {
+            let sym = symbols.by_tid(info.type_id).unwrap(); // we are sure the object exists
+            if is_signal {
+                let owner = signal_owner(info, name, &env.analysis.objects);
+                find_signal_connect(owner, name, env, in_type).unwrap_or_else(|| {
+                    let sym = symbols.by_tid(owner.type_id).unwrap_or(sym);
+                    gen_signal_doc_link(&sym.full_rust_name(), name)
+                })
+            } else {
+                find_property_accessor(info, name, env, in_type)
+                    .unwrap_or_else(|| gen_property_doc_link(&sym.full_rust_name(), name))
+            }
+        })
+}
+
+/// Link the `[id@gtk_widget_show]` like references to the type, function or constant
+/// with this C identifier
+pub(crate) fn find_c_identifier(
+    c_name: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    find_type(c_name, env, in_type)
+        .or_else(|| find_method_or_function_by_ctype(None, c_name, env, in_type))
+        .or_else(|| find_constant_or_variant(c_name, env, in_type))
+}
+
+/// Link the `#GtkWidgetClass.snapshot` like references to the vfuncs of the class
+/// structs, see [`find_vfunc_method`]
+fn find_vfunc(
+    class_struct: &str,
     name: &str,
-    env: &Env,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
 ) -> Option<String> {
-    find_method_or_function(
-        name,
-        env,
-        in_type,
-        |f| f.glib_name == name,
-        |o| c_type.map_or(true, |t| o.c_type == t),
-        |r| c_type.map_or(true, |t| r.type_(&env.library).c_type == t),
-        |r| c_type.map_or(true, |t| r.type_(&env.library).c_type == t),
-        |r| c_type.map_or(true, |t| r.type_(&env.library).c_type == t),
-        c_type.map_or(false, |t| t.ends_with("Class")),
+    let obj_info = vfunc_owner(&env.analysis.objects, class_struct)?;
+    Some(
+        find_vfunc_method(name, env, in_type, |o| {
+            o.type_id == obj_info.type_id && gi_docgen::provides_vfunc_methods(o, &obj_info.name)
+        })
+        .unwrap_or_else(|| {
+            info!(
+                "No method generated for the vfunc `{}.{}`",
+                class_struct, name
+            );
+            gen_vfunc_doc_link(&obj_info.name, name)
+        }),
     )
 }
 
-/// Find a function in all the possible items, if not found return the original name surrounded with backticks.
-/// A function can either be a struct/interface/record method, a global function or maybe a virtual function
-///
-/// This function is generic so it can be de-duplicated between a
-/// - [`find_method_or_function_by_ctype()`] where the object/records are looked by their C name
-/// - [`gi_docgen::find_method_or_function_by_name()`] where the object/records are looked by their name
-pub(crate) fn find_method_or_function(
+/// The object whose class or interface struct is `class_struct`
+fn vfunc_owner<'a>(
+    objects: &'a BTreeMap<String, crate::analysis::object::Info>,
+    class_struct: &str,
+) -> Option<&'a crate::analysis::object::Info> {
+    objects
+        .values()
+        .find(|o| o.c_class_type.as_deref() == Some(class_struct))
+}
+
+/// Link the vfunc `name` to the method gir generates for it in the objects matching
+/// `search_obj`, only the interfaces get one in their `Ext` trait
+pub(crate) fn find_vfunc_method(
     name: &str,
-    env: &Env,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
-    search_fn: impl Fn(&crate::analysis::functions::Info) -> bool + Copy,
     search_obj: impl Fn(&crate::analysis::object::Info) -> bool + Copy,
-    search_record: impl Fn(&crate::analysis::record::Info) -> bool + Copy,
-    search_enum: impl Fn(&crate::analysis::enums::Info) -> bool + Copy,
-    search_flag: impl Fn(&crate::analysis::flags::Info) -> bool + Copy,
-    is_class_method: bool,
 ) -> Option<String> {
-    if is_class_method {
-        info!("Class methods are not supported yet `{}`", name);
-        return None;
+    let mangled = nameutil::mangle_keywords(name);
+    env.analysis
+        .find_object_by_function(env.library, search_obj, |f| f.name == mangled)
+        .map(|(obj_info, fn_info)| {
+            gen_object_fn_doc_link(obj_info, fn_info, env, in_type, &obj_info.name)
+        })
+}
+
+/// The object declaring the signal `name` of `info`, either `info` itself, one of its
+/// parents or one of the interfaces it implements
+pub(crate) fn signal_owner<'a>(
+    info: &'a crate::analysis::object::Info,
+    name: &str,
+    objects: &'a BTreeMap<String, crate::analysis::object::Info>,
+) -> &'a crate::analysis::object::Info {
+    inherited_owner(info, objects, |o| {
+        o.signals
+            .iter()
+            .chain(&o.notify_signals)
+            .any(|s| s.signal_name == name)
+    })
+    .unwrap_or(info)
+}
+
+/// The first of `info`, its parents and the interfaces it implements, closest first,
+/// that `declares` an item
+fn inherited_owner<'a>(
+    info: &'a crate::analysis::object::Info,
+    objects: &'a BTreeMap<String, crate::analysis::object::Info>,
+    declares: impl Fn(&crate::analysis::object::Info) -> bool,
+) -> Option<&'a crate::analysis::object::Info> {
+    if declares(info) {
+        return Some(info);
     }
+    info.supertypes
+        .iter()
+        .filter_map(|s| objects.values().find(|o| o.type_id == s.type_id))
+        .find(|o| declares(o))
+}
 
-    // if we can find the function in an object
-    if let Some((obj_info, fn_info)) = env
-        .analysis
-        .find_object_by_function(env, search_obj, search_fn)
+/// Link a property to its generated getter or setter, see `doc.property_link_target`
+pub(crate) fn find_property_accessor(
+    obj_info: &crate::analysis::object::Info,
+    property: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let is_setter = env.config.doc.property_link_target == PropertyLinkTarget::Setter;
+    let prop_info = obj_info
+        .properties
+        .iter()
+        .find(|p| p.name == property && p.is_get != is_setter);
+    let accessor = prop_info.map_or_else(
+        || property_accessor_name(property, is_setter),
+        |p| p.func_name.clone(),
+    );
+    // Without a generated property, the C accessor can also be renamed, like the
+    // `is_visible` getter of a `visible` property
+    let is_accessor = |f: &Info| {
+        f.codegen_name() == accessor
+            || (prop_info.is_none() && is_c_property_accessor(&f.name, property, is_setter))
+    };
+
+    // The accessor can either be generated from a C function
+    if let Some(fn_info) = obj_info
+        .functions
+        .iter()
+        .find(|f| is_accessor(f) && f.should_be_doc_linked(env.library))
     {
-        Some(gen_object_fn_doc_link(
+        return Some(gen_object_fn_doc_link(
             obj_info,
             fn_info,
             env,
             in_type,
             &obj_info.name,
-        ))
-    // or in a record
-    } else if let Some((record_info, fn_info)) =
-        env.analysis
-            .find_record_by_function(env, search_record, search_fn)
-    {
-        Some(gen_type_fn_doc_link(
-            record_info.type_id,
-            fn_info,
-            env,
-            in_type,
-        ))
-    } else if let Some((enum_info, fn_info)) =
-        env.analysis
-            .find_enum_by_function(env, search_enum, search_fn)
-    {
-        Some(gen_type_fn_doc_link(
-            enum_info.type_id,
-            fn_info,
-            env,
-            in_type,
-        ))
-    } else if let Some((flag_info, fn_info)) =
-        env.analysis
-            .find_flag_by_function(env, search_flag, search_fn)
-    {
-        Some(gen_type_fn_doc_link(
-            flag_info.type_id,
-            fn_info,
-            env,
-            in_type,
-        ))
-    // or as a global function
-    } else if let Some(fn_info) = env.analysis.find_global_function(env, search_fn) {
-        Some(fn_info.doc_link(None, None, false))
-    } else {
-        None
+        ));
+    }
+    // or from the property itself
+    if !obj_info.properties.iter().any(|p| p.func_name == accessor) {
+        return None;
     }
+    gen_object_method_doc_link(obj_info, &accessor, env, in_type)
 }
 
-pub(crate) fn gen_type_fn_doc_link(
-    type_id: TypeId,
-    fn_info: &Info,
-    env: &Env,
+/// The generated `connect_` method of the signal `name` declared by `owner`, see
+/// `signal_owner`
+pub(crate) fn find_signal_connect(
+    owner: &crate::analysis::object::Info,
+    name: &str,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
-) -> String {
-    let symbols = env.symbols.borrow();
-    let sym_name = symbols.by_tid(type_id).unwrap().full_rust_name();
-    let is_self = in_type == Some((&type_id, None));
+) -> Option<String> {
+    let signal = generated_signal(owner, name)?;
+    if !is_available_in(env, in_type, signal.version) {
+        return None;
+    }
+    gen_object_method_doc_link(owner, &signal.connect_name, env, in_type)
+}
 
-    fn_info.doc_link(Some(&sym_name), None, is_self)
+/// The signal `name` of `info` if its `connect_` method is generated
+fn generated_signal<'a>(
+    info: &'a crate::analysis::object::Info,
+    name: &str,
+) -> Option<&'a crate::analysis::signals::Info> {
+    info.signals
+        .iter()
+        .find(|s| s.signal_name == name && !s.doc_hidden && s.trampoline.is_ok())
 }
 
-pub(crate) fn gen_object_fn_doc_link(
+/// Link a method generated on the type or its `Ext` trait without a C function, like
+/// the property accessors and the signal `connect_` methods
+fn gen_object_method_doc_link(
     obj_info: &crate::analysis::object::Info,
-    fn_info: &Info,
-    env: &Env,
+    method: &str,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
-    visible_name: &str,
-) -> String {
-    let symbols = env.symbols.borrow();
-    let sym = symbols.by_tid(obj_info.type_id).unwrap();
-    let is_self = in_type == Some((&obj_info.type_id, Some(obj_info.function_location(fn_info))));
-
-    if fn_info.kind == FunctionKind::Method {
-        let (type_name, visible_type_name) = obj_info.generate_doc_link_info(fn_info);
-
-        fn_info.doc_link(
-            Some(&sym.full_rust_name().replace(visible_name, &type_name)),
-            Some(&visible_type_name),
-            is_self,
+) -> Option<String> {
+    let symbols = env.symbols;
+    let sym = symbols.by_tid(obj_info.type_id)?;
+    let (location, type_name) = if obj_info.generate_trait {
+        (
+            LocationInObject::Ext,
+            format!("prelude::{}", obj_info.trait_name),
         )
     } else {
-        fn_info.doc_link(Some(&sym.full_rust_name()), None, is_self)
+        (LocationInObject::Impl, obj_info.name.clone())
+    };
+    let visible_name = type_name.trim_start_matches("prelude::");
+    Some(gen_accessor_doc_link(
+        &sym.full_rust_name().replace(&obj_info.name, &type_name),
+        visible_name,
+        method,
+        in_type == Some((&obj_info.type_id, Some(location))),
+    ))
+}
+
+/// The name gir gives to a property accessor if it isn't renamed
+fn property_accessor_name(property: &str, is_setter: bool) -> String {
+    let name = nameutil::signal_to_snake(property);
+    if is_setter {
+        format!("set_{}", name)
+    } else {
+        name
     }
 }
 
-// Helper function to generate a doc link for an enum member/bitfield variant
-pub(crate) fn gen_member_doc_link(
-    type_id: TypeId,
-    member_name: &str,
-    env: &Env,
-    in_type: Option<(&TypeId, Option<LocationInObject>)>,
-) -> String {
-    let symbols = env.symbols.borrow();
-    let sym = symbols.by_tid(type_id).unwrap().full_rust_name();
-    let is_self = in_type == Some((&type_id, None));
+/// Whether the name of a C function, before it is renamed, is the one of a getter
+/// or setter of `property`
+fn is_c_property_accessor(fn_name: &str, property: &str, is_setter: bool) -> bool {
+    let name = nameutil::signal_to_snake(property);
+    let prefixes: &[&str] = if is_setter {
+        &["set_"]
+    } else {
+        &["get_", "is_"]
+    };
+    prefixes
+        .iter()
+        .any(|prefix| fn_name.strip_prefix(prefix) == Some(name.as_str()))
+}
 
+fn gen_accessor_doc_link(
+    parent: &str,
+    visible_parent: &str,
+    accessor: &str,
+    is_self: bool,
+) -> String {
     if is_self {
-        format!("[`{m}`][Self::{m}]", m = member_name)
+        format!("[`{f}()`][Self::{f}()]", f = accessor)
     } else {
-        format!("[`{s}::{m}`][crate::{s}::{m}]", s = sym, m = member_name)
+        format!(
+            "[`{v}::{f}()`][crate::{p}::{f}()]",
+            v = visible_parent,
+            p = parent,
+            f = accessor
+        )
     }
 }
 
-pub(crate) fn gen_const_doc_link(const_info: &crate::analysis::constants::Info) -> String {
-    // for whatever reason constants are not part of the symbols list
-    format!("[`{n}`][crate::{n}]", n = const_info.name)
+fn find_constant_or_variant(
+    symbol: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    // Neither the ignored members nor the members of the types left out are linked
+    if let Some((flag_info, member_info)) = env
+        .analysis
+        .flags
+        .iter()
+        .find_map(|f| {
+            f.type_(env.library)
+                .members
+                .iter()
+                .find(|m| {
+                    m.c_identifier == symbol
+                        && !m.status.ignored()
+                        && is_available_in(env, in_type, m.version)
+                })
+                .map(|m| (f, m))
+        })
+        .filter(|(f, _)| is_generated_type(env, f.type_id))
+    {
+        Some(gen_member_doc_link(
+            flag_info.type_id,
+            &nameutil::bitfield_member_name(&member_info.name),
+            env,
+            in_type,
+        ))
+    } else if let Some((enum_info, member_info)) = env
+        .analysis
+        .enumerations
+        .iter()
+        .find_map(|e| {
+            e.type_(env.library)
+                .members
+                .iter()
+                .find(|m| {
+                    m.c_identifier == symbol
+                        && !m.status.ignored()
+                        && is_available_in(env, in_type, m.version)
+                })
+                .map(|m| (e, m))
+        })
+        .filter(|(e, _)| is_generated_type(env, e.type_id))
+    {
+        Some(gen_member_doc_link(
+            enum_info.type_id,
+            &nameutil::enum_member_name(&member_info.name),
+            env,
+            in_type,
+        ))
+    } else if let Some(constant) = find_constant(symbol, env, in_type) {
+        Some(constant)
+    } else if let Some(member) = find_error_domain_member(symbol, env, in_type) {
+        Some(member)
+    } else if let Some(member) = find_deprecated_member(symbol, env, in_type) {
+        Some(member)
+    } else {
+        let flags: Vec<_> = env
+            .analysis
+            .flags
+            .iter()
+            .filter(|f| is_available_in(env, in_type, f.type_(env.library).version))
+            .map(|f| f.type_id)
+            .collect();
+        find_combined_flags(env.library, &flags, symbol)
+            .filter(|&tid| is_generated_type(env, tid))
+            .map(|tid| gen_symbol_doc_link(tid, env))
+            .or_else(|| {
+                env.config
+                    .doc
+                    .symbol_links
+                    .get(symbol)
+                    .map(|path| gen_core_type_doc_link(path, env.library.is_glib_crate()))
+            })
+    }
 }
 
-pub(crate) fn gen_signal_doc_link(symbol: &str, signal: &str) -> String {
-    format!("`signal::{}::{}`", symbol, signal)
+/// Link the `#GtkAlign.START` like references naming an enumeration or flags type and
+/// one of its members
+fn find_type_member(
+    type_: &str,
+    member: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let type_id = *env.type_index.get(type_)?;
+    let (members, is_flags) = match env.library.type_(type_id) {
+        Type::Enumeration(enum_) => (&enum_.members, false),
+        Type::Bitfield(bitfield) => (&bitfield.members, true),
+        _ => return None,
+    };
+    let member_info =
+        find_named_member(members, member).filter(|m| is_available_in(env, in_type, m.version))?;
+    let name = if is_flags {
+        nameutil::bitfield_member_name(&member_info.name)
+    } else {
+        nameutil::enum_member_name(&member_info.name)
+    };
+    Some(gen_member_doc_link(type_id, &name, env, in_type))
 }
 
-pub(crate) fn gen_property_doc_link(symbol: &str, property: &str) -> String {
-    format!("`property::{}::{}`", symbol, property)
+/// A `#GtkTextIter.line` like reference to a field of a record
+fn find_record_field(
+    type_: &str,
+    field: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let type_id = *env.type_index.get(type_)?;
+    let fields = match env.library.type_(type_id) {
+        Type::Record(record) => &record.fields,
+        _ => return None,
+    };
+    field_reference(fields, field, |getter| {
+        find_type_method(getter, type_id, env, in_type)
+    })
 }
 
-pub(crate) fn gen_vfunc_doc_link(symbol: &str, vfunc: &str) -> String {
-    format!("`vfunc::{}::{}`", symbol, vfunc)
+/// Link a field to its getter, looked for by `find_getter` under the Rust name of the
+/// field, or render its name as inline code. The private fields aren't referenced.
+fn field_reference(
+    fields: &[Field],
+    name: &str,
+    find_getter: impl FnOnce(&str) -> Option<String>,
+) -> Option<String> {
+    let field = fields.iter().find(|f| f.name == name && !f.private)?;
+    let getter = nameutil::mangle_keywords(&*field.name);
+    Some(find_getter(&getter).unwrap_or_else(|| format!("`{}`", field.name)))
 }
 
-pub(crate) fn gen_callback_doc_link(callback: &str) -> String {
-    format!("`callback::{}", callback)
+/// The member referenced by its name, in any case, or by its C identifier, the ignored
+/// ones excepted
+fn find_named_member<'a>(members: &'a [Member], member: &str) -> Option<&'a Member> {
+    members
+        .iter()
+        .filter(|m| !m.status.ignored())
+        .find(|m| m.name.eq_ignore_ascii_case(member) || m.c_identifier == member)
 }
 
-pub(crate) fn gen_alias_doc_link(alias: &str) -> String {
-    format!("`alias::{}`", alias)
-}
+/// Resolve the members that aren't generated, see `doc.deprecated_member_links`
+fn find_deprecated_member(
+    symbol: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let mode = env.config.doc.deprecated_member_links;
+    if mode == DeprecatedMemberLink::Off {
+        return None;
+    }
+    let is_deprecated = |m: &&Member| m.c_identifier == symbol && m.status.ignored();
+    let link = if let Some((flag_info, member_info)) = env.analysis.flags.iter().find_map(|f| {
+        f.type_(env.library)
+            .members
+            .iter()
+            .find(is_deprecated)
+            .map(|m| (f, m))
+    }) {
+        gen_member_doc_link(
+            flag_info.type_id,
+            &nameutil::bitfield_member_name(&member_info.name),
+            env,
+            in_type,
+        )
+    } else {
+        let (enum_info, member_info) = env.analysis.enumerations.iter().find_map(|e| {
+            e.type_(env.library)
+                .members
+                .iter()
+                .find(is_deprecated)
+                .map(|m| (e, m))
+        })?;
+        gen_member_doc_link(
+            enum_info.type_id,
+            &nameutil::enum_member_name(&member_info.name),
+            env,
+            in_type,
+        )
+    };
+    Some(gen_deprecated_member_doc(symbol, link, mode))
+}
 
-pub(crate) fn gen_symbol_doc_link(type_id: TypeId, env: &Env) -> String {
-    let symbols = env.symbols.borrow();
-    let sym = symbols.by_tid(type_id).unwrap();
-    // Workaround the case of glib::Variant being a derive macro and a struct
-    if sym.name() == "Variant" && (sym.crate_name().is_none() || sym.crate_name() == Some("glib")) {
-        format!("[`{n}`][struct@crate::{n}]", n = sym.full_rust_name())
+fn gen_deprecated_member_doc(symbol: &str, link: String, mode: DeprecatedMemberLink) -> String {
+    match mode {
+        DeprecatedMemberLink::Variant => link,
+        DeprecatedMemberLink::Note | DeprecatedMemberLink::Off => {
+            format!("`{}` (deprecated)", symbol)
+        }
+    }
+}
+
+/// Find the flags type of a combined value like `%GTK_FOO_DEFAULT = A | B`, either through
+/// the type of the C constant or through the common prefix of the flags' members
+fn find_combined_flags(library: &Library, flags: &[TypeId], symbol: &str) -> Option<TypeId> {
+    let constant_type = library
+        .namespace(MAIN_NAMESPACE)
+        .constants
+        .iter()
+        .find(|c| c.c_identifier == symbol)
+        .map(|c| c.typ);
+    if let Some(tid) = constant_type.filter(|tid| flags.contains(tid)) {
+        return Some(tid);
+    }
+
+    flags
+        .iter()
+        .filter_map(|&tid| match library.type_(tid) {
+            Type::Bitfield(bitfield) => {
+                let members: Vec<_> = bitfield
+                    .members
+                    .iter()
+                    .map(|m| m.c_identifier.as_str())
+                    .collect();
+                flags_combination_prefix(symbol, &members).map(|prefix| (prefix.len(), tid))
+            }
+            _ => None,
+        })
+        .max_by_key(|&(len, _)| len)
+        .map(|(_, tid)| tid)
+}
+
+/// The prefix shared by the members of a flags type, like `GTK_FOO_`, if `symbol` starts with it
+fn flags_combination_prefix<'a>(symbol: &'a str, members: &[&str]) -> Option<&'a str> {
+    let (first, rest) = members.split_first()?;
+    let mut len = rest.iter().fold(first.len(), |len, member| {
+        first
+            .bytes()
+            .zip(member.bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    // Only keep whole words of the prefix
+    len = first[..len].rfind('_').map_or(0, |pos| pos + 1);
+    if len == 0 || symbol.len() <= len || symbol.as_bytes()[..len] != first.as_bytes()[..len] {
+        None
+    } else {
+        Some(&symbol[..len])
+    }
+}
+
+/// Whether this type of `namespace` is never linked, see `doc.ignored_types`, the
+/// `!GList` like entries allow linking a default one
+fn is_ignored_type(type_: &str, namespace: Option<&str>, config: &DocConfig) -> bool {
+    let mut ignored = DEFAULT_IGNORED_TYPES.contains(&type_);
+    for t in &config.ignored_types {
+        let (removed, t) = match t.strip_prefix('!') {
+            Some(t) => (true, t),
+            None => (false, t.as_str()),
+        };
+        if scoped_entry(t, namespace) == Some(type_) {
+            ignored = !removed;
+        }
+    }
+    ignored
+}
+
+/// Whether a `%CONSTANT` of `namespace` is expected to have no Rust equivalent, the
+/// `GST_*` like entries of `doc.ignored_constants` cover all the constants with their prefix
+fn is_ignored_constant(constant: &str, namespace: Option<&str>, config: &DocConfig) -> bool {
+    config
+        .ignored_constants
+        .iter()
+        .filter_map(|c| scoped_entry(c, namespace))
+        .any(|c| match c.strip_suffix('*') {
+            Some(prefix) => constant.starts_with(prefix),
+            None => c == constant,
+        })
+}
+
+/// An entry of the `doc.ignored_*` lists as it applies to the symbols of `namespace`, the
+/// `Gst.GST_PAD_*` like entries only apply to the symbols of their namespace
+fn scoped_entry<'a>(entry: &'a str, namespace: Option<&str>) -> Option<&'a str> {
+    match entry.split_once('.') {
+        Some((ns, entry)) => (Some(ns) == namespace).then(|| entry),
+        None => Some(entry),
+    }
+}
+
+/// The namespace of a C type or constant, the one declaring it or else the one whose
+/// prefix it starts with, like `Gst` for `GstElement` and `GST_PAD_LINK_OK`
+fn c_name_namespace<'a>(library: &'a Library, name: &str) -> Option<&'a str> {
+    let namespaces = || {
+        library
+            .namespaces
+            .iter()
+            .enumerate()
+            .filter(|&(ns_id, _)| ns_id as u16 != INTERNAL_NAMESPACE)
+            .map(|(_, ns)| ns)
+    };
+    namespaces()
+        .find(|ns| ns.glib_name_index.contains_key(name))
+        .or_else(|| {
+            namespaces()
+                .filter_map(|ns| {
+                    let types = ns
+                        .identifier_prefixes
+                        .iter()
+                        .filter(|p| !p.is_empty() && has_namespace_prefix(name, &[p.as_str()]));
+                    let constants = ns.symbol_prefixes.iter().filter(|p| {
+                        !p.is_empty()
+                            && name
+                                .strip_prefix(&*p.to_uppercase())
+                                .map_or(false, |rest| rest.starts_with('_'))
+                    });
+                    Some((types.chain(constants).map(String::len).max()?, ns))
+                })
+                .max_by_key(|&(len, _)| len)
+                .map(|(_, ns)| ns)
+        })
+        .map(|ns| ns.name.as_str())
+}
+
+/// How a C symbol nothing was found for is written, see `doc.unresolved_style`
+fn unresolved_text(symbol: &str, style: UnresolvedStyle) -> String {
+    match style {
+        UnresolvedStyle::Backticks => format!("`{}`", symbol),
+        UnresolvedStyle::Plain => symbol.to_owned(),
+        UnresolvedStyle::Drop => String::new(),
+    }
+}
+
+/// Whether the type exists in the generated crates so that it can be linked, the ones
+/// of the other namespaces are only left out when ignored in the config
+fn is_generated_type(env: &DocContext<'_>, type_id: TypeId) -> bool {
+    let name = type_id.full_name(env.library);
+    let status = env.config.objects.get(&name).map(|o| o.status);
+    let generated = is_generated_status(status, type_id.ns_id == MAIN_NAMESPACE);
+    // Like the unresolved references, only the first one is logged
+    if !generated
+        && (env.logged_ungenerated.borrow_mut().insert(type_id) || env.config.doc.verbose_warnings)
+    {
+        info!("`{}` isn't generated, its references aren't linked", name);
+    }
+    generated
+}
+
+fn is_generated_status(status: Option<GStatus>, is_main_namespace: bool) -> bool {
+    match status {
+        Some(status) => !status.ignored(),
+        None => !is_main_namespace,
+    }
+}
+
+// The glib types that are referenced all over the docs of the other crates
+const GLIB_CORE_TYPES: [(&str, &str); 16] = [
+    ("GBytes", "glib::Bytes"),
+    ("GClosure", "glib::Closure"),
+    ("GDateTime", "glib::DateTime"),
+    ("GError", "glib::Error"),
+    ("GKeyFile", "glib::KeyFile"),
+    ("GMainContext", "glib::MainContext"),
+    ("GMainLoop", "glib::MainLoop"),
+    ("GObject", "glib::Object"),
+    ("GParamSpec", "glib::ParamSpec"),
+    ("GSource", "glib::Source"),
+    ("GString", "glib::GString"),
+    ("GTimeZone", "glib::TimeZone"),
+    ("GType", "glib::Type"),
+    ("GValue", "glib::Value"),
+    ("GVariant", "glib::Variant"),
+    ("GVariantType", "glib::VariantType"),
+];
+
+/// The analyzed objects, records, enumerations and flags by their C type, the ignored
+/// ones excepted
+fn type_index(
+    config: &Config,
+    library: &Library,
+    analysis: &analysis::Analysis,
+) -> HashMap<String, TypeId> {
+    let objects = analysis
+        .objects
+        .values()
+        .map(|o| (o.c_type.as_str(), o.type_id));
+    let records = analysis
+        .records
+        .values()
+        .map(|r| (r.type_(library).c_type.as_str(), r.type_id));
+    let enums = analysis
+        .enumerations
+        .iter()
+        .map(|e| (e.type_(library).c_type.as_str(), e.type_id));
+    let flags = analysis
+        .flags
+        .iter()
+        .map(|f| (f.type_(library).c_type.as_str(), f.type_id));
+    let is_ignored = |tid: TypeId| {
+        config
+            .objects
+            .get(&tid.full_name(library))
+            .map(|o| o.status)
+            .unwrap_or_default()
+            .ignored()
+    };
+    c_type_index(
+        objects
+            .chain(records)
+            .chain(enums)
+            .chain(flags)
+            .filter(|&(_, tid)| !is_ignored(tid)),
+    )
+}
+
+/// Index the types by their C type, the first one wins when several share it
+fn c_type_index<'a>(types: impl Iterator<Item = (&'a str, TypeId)>) -> HashMap<String, TypeId> {
+    let mut index = HashMap::new();
+    for (c_type, type_id) in types {
+        index.entry(c_type.to_owned()).or_insert(type_id);
+    }
+    index
+}
+
+/// The glib functions of the signals API, which are implemented by hand
+const GLIB_SIGNAL_FUNCTIONS: [(&str, &str); 13] = [
+    ("g_signal_connect", "glib::prelude::ObjectExt::connect"),
+    (
+        "g_signal_connect_after",
+        "glib::prelude::ObjectExt::connect",
+    ),
+    (
+        "g_signal_connect_closure",
+        "glib::prelude::ObjectExt::connect_closure",
+    ),
+    ("g_signal_connect_data", "glib::prelude::ObjectExt::connect"),
+    (
+        "g_signal_connect_object",
+        "glib::prelude::ObjectExt::connect",
+    ),
+    (
+        "g_signal_connect_swapped",
+        "glib::prelude::ObjectExt::connect",
+    ),
+    ("g_signal_emit", "glib::prelude::ObjectExt::emit"),
+    (
+        "g_signal_emit_by_name",
+        "glib::prelude::ObjectExt::emit_by_name",
+    ),
+    (
+        "g_signal_handler_block",
+        "glib::prelude::ObjectExt::block_signal",
+    ),
+    (
+        "g_signal_handler_disconnect",
+        "glib::prelude::ObjectExt::disconnect",
+    ),
+    (
+        "g_signal_handler_unblock",
+        "glib::prelude::ObjectExt::unblock_signal",
+    ),
+    (
+        "g_signal_stop_emission",
+        "glib::prelude::ObjectExt::stop_signal_emission",
+    ),
+    (
+        "g_signal_stop_emission_by_name",
+        "glib::prelude::ObjectExt::stop_signal_emission_by_name",
+    ),
+];
+
+/// The Rust path of a function without generated equivalent, from `doc.function_links`
+/// or the glib signal functions
+fn function_link<'a>(name: &str, function_links: &'a BTreeMap<String, String>) -> Option<&'a str> {
+    function_links.get(name).map(String::as_str).or_else(|| {
+        GLIB_SIGNAL_FUNCTIONS
+            .iter()
+            .find(|(c_name, _)| *c_name == name)
+            .map(|(_, path)| *path)
+    })
+}
+
+/// The Rust path of a symbol from `doc.link_overrides`, the forced ones are used
+/// before looking for the symbol and the others once it couldn't be found
+fn link_override<'a>(
+    c_name: &str,
+    link_overrides: &'a BTreeMap<String, DocLinkOverride>,
+    forced: bool,
+) -> Option<&'a str> {
+    link_overrides
+        .get(c_name)
+        .filter(|o| o.force == forced)
+        .map(|o| o.rust_link.as_str())
+}
+
+/// either an object/interface, record, enum or a flag
+fn find_type(
+    type_: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    if let Some(path) = link_override(type_, &env.config.doc.link_overrides, true) {
+        return Some(gen_core_type_doc_link(path, env.library.is_glib_crate()));
+    }
+    if is_ignored_type(type_, c_name_namespace(env.library, type_), &env.config.doc) {
+        return None;
+    }
+    let lookups = &env.type_lookups;
+    // Interface structs are linked to the interface trait
+    if let Some(&iface) = lookups.interface_structs.get(type_) {
+        if !is_available_in(env, in_type, iface.version) {
+            return None;
+        }
+        return Some(if iface.generate_trait {
+            gen_interface_trait_doc_link(&iface.trait_name)
+        } else {
+            gen_symbol_doc_link(iface.type_id, env)
+        });
+    }
+
+    let type_id = env
+        .type_index
+        .get(type_)
+        .or_else(|| lookups.glib_type_names.get(type_))
+        .or_else(|| lookups.aliases.get(type_))
+        .copied();
+
+    type_id
+        .or_else(|| lookups.nested_types.get(type_).copied())
+        .filter(|&ty| is_available_in(env, in_type, env.library.type_(ty).get_version()))
+        .filter(|&ty| is_generated_type(env, ty))
+        .map(|ty| gen_symbol_doc_link(ty, env))
+        .or_else(|| find_builder(type_, env, in_type))
+        .or_else(|| {
+            lookups
+                .unions
+                .get(type_)
+                .or_else(|| lookups.callbacks.get(type_))
+                .map(|c_type| {
+                    gen_sys_type_doc_link(
+                        env.main_sys_crate_name(),
+                        c_type,
+                        env.library.is_glib_crate(),
+                    )
+                })
+        })
+        .or_else(|| {
+            env.config
+                .doc
+                .type_links
+                .get(type_)
+                .map(|path| gen_core_type_doc_link(path, env.library.is_glib_crate()))
+        })
+        .or_else(|| {
+            lookups
+                .external_types
+                .get(type_)
+                .copied()
+                .filter(|ty| {
+                    is_dependency(env.namespaces, &env.config.external_libraries, ty.ns_id)
+                })
+                .filter(|&ty| env.symbols.by_tid(ty).is_some() && is_generated_type(env, ty))
+                .map(|ty| gen_symbol_doc_link(ty, env))
+        })
+        .or_else(|| {
+            glib_core_type_path(type_)
+                .map(|path| gen_core_type_doc_link(path, env.library.is_glib_crate()))
+        })
+        .or_else(|| {
+            link_override(type_, &env.config.doc.link_overrides, false)
+                .map(|path| gen_core_type_doc_link(path, env.library.is_glib_crate()))
+        })
+}
+
+/// Link the `GtkButtonBuilder` like references to the builder gir generates for the
+/// object, they aren't part of the GIR
+fn find_builder(
+    type_: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let object_type = type_.strip_suffix("Builder")?;
+    let type_id = *env.type_index.get(object_type)?;
+    env.analysis
+        .objects
+        .get(&type_id.full_name(env.library))
+        .filter(|o| analysis::object::has_builder_properties(&o.builder_properties))
+        .filter(|o| is_available_in(env, in_type, o.version))
+        .map(|o| gen_builder_doc_link(&o.name))
+}
+
+fn gen_builder_doc_link(object: &str) -> String {
+    let builder = format!("{}Builder", object);
+    let path = crate_path(&format!("builders::{}", builder), false);
+    format!("[`{}`][{}]", builder, path)
+}
+
+/// Show the C name of the reference as the text of its link, see `doc.link_text`. The
+/// links without a target like `` [`true`] `` are named after it and kept as they are.
+fn with_link_text(link: String, c_name: &str, style: LinkText) -> String {
+    if style == LinkText::Rust {
+        return link;
+    }
+    match link
+        .strip_prefix("[`")
+        .and_then(|rest| rest.split_once("`]["))
+    {
+        Some((_, target)) => format!("[`{}`][{}", c_name, target),
+        None => link,
+    }
+}
+
+/// Follow the link to a type or function with its availability, see
+/// `doc.availability_notes`. `versions` are the version the item was introduced in and
+/// the one it is deprecated since.
+fn with_availability(
+    link: String,
+    versions: Option<(Option<Version>, Option<Version>)>,
+    env: &DocContext<'_>,
+) -> String {
+    if !env.config.doc.availability_notes {
+        return link;
+    }
+    match versions.and_then(|(version, deprecated_version)| {
+        availability_note(env.config.min_cfg_version, version, deprecated_version)
+    }) {
+        Some(note) => format!("{} {}", link, note),
+        None => link,
+    }
+}
+
+/// The `(since 4.10)` like note of an item newer than `min_cfg_version` or deprecated
+fn availability_note(
+    min_cfg_version: Version,
+    version: Option<Version>,
+    deprecated_version: Option<Version>,
+) -> Option<String> {
+    match (version.filter(|v| *v > min_cfg_version), deprecated_version) {
+        (Some(since), Some(deprecated)) => Some(format!(
+            "(since {}, deprecated since {})",
+            since, deprecated
+        )),
+        (Some(since), None) => Some(format!("(since {})", since)),
+        (None, Some(deprecated)) => Some(format!("(deprecated since {})", deprecated)),
+        (None, None) => None,
+    }
+}
+
+/// The versions of the type with this C name, see [`with_availability`]
+fn type_versions(c_type: &str, env: &DocContext<'_>) -> Option<(Option<Version>, Option<Version>)> {
+    let type_ = env.library.type_(*env.type_index.get(c_type)?);
+    Some((type_.get_version(), type_.get_deprecated_version()))
+}
+
+/// The versions of the function with this C name, see [`with_availability`]
+fn function_versions(
+    c_name: &str,
+    env: &DocContext<'_>,
+) -> Option<(Option<Version>, Option<Version>)> {
+    let (owner, pos) = env.function_index.get(c_name)?.first()?;
+    let fn_info = env.analysis.function_at(owner, *pos)?;
+    Some((fn_info.version, fn_info.deprecated_version))
+}
+
+/// The inline `code` of a reference to an item of `doc.removed_items`, with the
+/// version it was removed in
+fn with_removed_note(
+    code: &str,
+    symbol: &str,
+    removed_items: &BTreeMap<String, Version>,
+) -> Option<String> {
+    removed_items
+        .get(symbol)
+        .map(|version| format!("{} (removed in {})", code, version))
+}
+
+/// The C identifier prefixes of the main namespace and its dependencies, like `Gtk`,
+/// `Gdk` or `cairo`
+fn namespace_prefixes(library: &Library) -> Vec<&str> {
+    library
+        .namespaces
+        .iter()
+        .enumerate()
+        .filter(|&(ns_id, _)| ns_id as u16 != INTERNAL_NAMESPACE)
+        .flat_map(|(_, ns)| ns.identifier_prefixes.iter().map(String::as_str))
+        .filter(|prefix| !prefix.is_empty())
+        .collect()
+}
+
+/// Whether a backticked identifier is named like a C type of one of the namespaces,
+/// `GtkWidget` for `Gtk` and `cairo_t` for `cairo`, other words are never resolved
+fn has_namespace_prefix(name: &str, prefixes: &[&str]) -> bool {
+    prefixes
+        .iter()
+        .any(|prefix| match name.strip_prefix(prefix) {
+            Some(rest) if prefix.starts_with(char::is_uppercase) => {
+                rest.starts_with(char::is_uppercase)
+            }
+            Some(rest) => rest.starts_with('_') && rest.len() > 1,
+            None => false,
+        })
+}
+
+/// Replace a `` `GtkWidget` `` reference matched by `GDK_GTK`, the `.` or `,` like
+/// punctuation some docs put in the backticks is moved out of the link or inline code
+fn replace_gdk_gtk(caps: &Captures<'_>, find: impl FnOnce(&str) -> Option<String>) -> String {
+    let out = find(&caps[2]).unwrap_or_else(|| format!("`{}`", &caps[2]));
+    match caps.get(3) {
+        Some(punctuation) => out + punctuation.as_str(),
+        None => out,
+    }
+}
+
+/// The backticked types are only stripped of their `.` if `doc.link_backticked_types`
+/// isn't set
+fn find_backticked_type(
+    type_: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    if env.config.doc.link_backticked_types {
+        find_type(type_, env, in_type)
+    } else {
+        None
+    }
+}
+
+/// The other C names [`find_type`] links, besides the ones of the [`type_index`], they
+/// are indexed once with the doc context too
+pub struct TypeLookups<'a> {
+    /// The interfaces by the C type of their struct, like `GtkOrientableIface`
+    pub interface_structs: HashMap<&'a str, &'a analysis::object::Info>,
+    /// The analyzed objects and records by their `glib:type-name`
+    pub glib_type_names: HashMap<&'a str, TypeId>,
+    /// The analyzed records by the C name of their boxed aliases
+    pub aliases: HashMap<&'a str, TypeId>,
+    /// The analyzed records and objects by the C type of the types nested in them
+    pub nested_types: HashMap<&'a str, TypeId>,
+    /// The unions, only generated in the sys crate
+    pub unions: HashSet<&'a str>,
+    /// The callbacks, only generated as type aliases in the sys crate
+    pub callbacks: HashSet<&'a str>,
+    /// The types of the other namespaces by their C type
+    pub external_types: HashMap<&'a str, TypeId>,
+}
+
+impl<'a> TypeLookups<'a> {
+    fn new(library: &'a Library, analysis: &'a analysis::Analysis) -> Self {
+        let objects = analysis.objects.values().map(|o| o.type_id);
+        let records = analysis.records.values().map(|r| r.type_id);
+        let record_ids: HashSet<TypeId> = records.clone().collect();
+        let mut aliases = alias_targets(library);
+        aliases.retain(|_, tid| record_ids.contains(tid));
+        Self {
+            interface_structs: interface_structs(analysis.objects.values()),
+            glib_type_names: glib_type_names(library, objects.clone().chain(records.clone())),
+            aliases,
+            nested_types: nested_types(library, records.chain(objects)),
+            unions: unions(library),
+            callbacks: callbacks(library),
+            external_types: external_types(library),
+        }
+    }
+}
+
+/// The interfaces by the C type of their struct, the first one wins
+fn interface_structs<'a>(
+    objects: impl Iterator<Item = &'a analysis::object::Info>,
+) -> HashMap<&'a str, &'a analysis::object::Info> {
+    let mut index = HashMap::new();
+    for object in objects.filter(|o| o.is_interface) {
+        if let Some(c_class_type) = object.c_class_type.as_deref() {
+            index.entry(c_class_type).or_insert(object);
+        }
+    }
+    index
+}
+
+/// Unions are only generated in the sys crate, the nested ones are linked through
+/// [`nested_types`]
+fn unions(library: &Library) -> HashSet<&str> {
+    let ns = library.namespace(MAIN_NAMESPACE);
+    // The nested types are indexed by their fields like `#[TypeId { .. }]`
+    ns.index
+        .iter()
+        .filter(|(name, _)| !name.starts_with('#'))
+        .filter_map(|(_, &id)| match ns.types[id as usize] {
+            Some(Type::Union(ref union)) => union.c_type.as_deref(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Callbacks are only generated as type aliases in the sys crate
+fn callbacks(library: &Library) -> HashSet<&str> {
+    library
+        .namespace(MAIN_NAMESPACE)
+        .types
+        .iter()
+        .filter_map(|typ| match typ {
+            Some(Type::Function(func)) => func.c_identifier.as_deref(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Link the `#GtkTickCallback *` like references to callback pointers, the `*` would
+/// otherwise be left over
+fn replace_callback_pointers(input: &str, find: impl Fn(&str) -> Option<String>) -> Cow<'_, str> {
+    CALLBACK_POINTER.replace_all(input, |caps: &Captures<'_>| {
+        find(&caps[1]).unwrap_or_else(|| caps[0].to_owned())
+    })
+}
+
+/// Link the `#const GtkWidget *` and `` `const GtkWidget *` `` like references to
+/// the type without its qualifiers and pointers
+fn replace_qualified_types(input: &str, find: impl Fn(&str) -> Option<String>) -> Cow<'_, str> {
+    QUALIFIED_TYPE.replace_all(input, |caps: &Captures<'_>| {
+        let c_type = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+        find(c_type_name(c_type)).unwrap_or_else(|| caps[0].to_owned())
+    })
+}
+
+/// The sys crate is re-exported at the root of the crate, like `ffi`
+fn gen_sys_type_doc_link(sys_crate_name: &str, c_type: &str, is_glib_crate: bool) -> String {
+    gen_core_type_doc_link(&format!("{}::{}", sys_crate_name, c_type), is_glib_crate)
+}
+
+/// The boxed aliases like `typedef GtkBar GtkFoo` link to the record they alias
+fn alias_targets(library: &Library) -> HashMap<&str, TypeId> {
+    let mut index = HashMap::new();
+    for (_, typ) in library.namespace_types(MAIN_NAMESPACE) {
+        if let Type::Alias(alias) = typ {
+            index
+                .entry(alias.c_identifier.as_str())
+                .or_insert(alias.typ);
+        }
+    }
+    index
+}
+
+/// Some types, like the fundamental ones, are referenced by their `glib:type-name`
+fn glib_type_names(
+    library: &Library,
+    type_ids: impl IntoIterator<Item = TypeId>,
+) -> HashMap<&str, TypeId> {
+    let mut index = HashMap::new();
+    for tid in type_ids {
+        if let Some(name) = library.type_(tid).glib_type_name() {
+            index.entry(name).or_insert(tid);
+        }
+    }
+    index
+}
+
+/// The types of the other namespaces, they are linked through the crate of their
+/// namespace, like `gdk_pixbuf::Pixbuf`
+fn external_types(library: &Library) -> HashMap<&str, TypeId> {
+    let mut index = HashMap::new();
+    let namespaces = library
+        .namespaces
+        .iter()
+        .enumerate()
+        .map(|(ns_id, ns)| (ns_id as u16, ns))
+        .filter(|&(ns_id, _)| ns_id != INTERNAL_NAMESPACE && ns_id != MAIN_NAMESPACE);
+    for (ns_id, ns) in namespaces {
+        for (c_type, &id) in &ns.glib_name_index {
+            let ty = TypeId { ns_id, id };
+            if matches!(
+                library.type_(ty),
+                Type::Class(_)
+                    | Type::Interface(_)
+                    | Type::Record(_)
+                    | Type::Enumeration(_)
+                    | Type::Bitfield(_)
+            ) {
+                index.entry(c_type.as_str()).or_insert(ty);
+            }
+        }
+    }
+    index
+}
+
+/// Whether the crate of a namespace is a dependency of the generated crate, the other
+/// namespaces are only included by the GIR files
+fn is_dependency(
+    namespaces: &namespaces::Info,
+    external_libraries: &[ExternalLibrary],
+    ns_id: namespaces::NsId,
+) -> bool {
+    let ns = &namespaces[ns_id];
+    ns_id == namespaces.glib_ns_id
+        || ns.higher_crate_name == "glib"
+        || external_libraries
+            .iter()
+            .any(|lib| lib.crate_name == ns.crate_name)
+}
+
+/// The nested records and unions are not generated, so they link to the record or object
+/// they are defined in
+fn nested_types(
+    library: &Library,
+    parents: impl IntoIterator<Item = TypeId>,
+) -> HashMap<&str, TypeId> {
+    let mut index = HashMap::new();
+    for parent in parents {
+        let fields = match library.type_(parent) {
+            Type::Record(r) => &r.fields,
+            Type::Class(c) => &c.fields,
+            _ => continue,
+        };
+        for c_type in nested_c_types(library, fields) {
+            index.entry(c_type).or_insert(parent);
+        }
+    }
+    index
+}
+
+/// The C types of the records and unions nested in `fields`
+fn nested_c_types<'a>(library: &'a Library, fields: &'a [Field]) -> Vec<&'a str> {
+    let mut c_types = Vec::new();
+    for field in fields {
+        let (name, nested_c_type, nested_fields) = match library.type_(field.typ) {
+            Type::Record(r) => (&r.name, Some(r.c_type.as_str()), &r.fields),
+            Type::Union(u) => (&u.name, u.c_type.as_deref(), &u.fields),
+            _ => continue,
+        };
+        // Only the nested types can't be found by their name
+        if library.find_type(field.typ.ns_id, name) == Some(field.typ) {
+            continue;
+        }
+        c_types.extend(nested_c_type);
+        c_types.extend(nested_c_types(library, nested_fields));
+    }
+    c_types
+}
+
+/// The Rust path of the most common glib types, for when GLib isn't loaded
+fn glib_core_type_path(type_: &str) -> Option<&'static str> {
+    GLIB_CORE_TYPES
+        .iter()
+        .find(|(c_type, _)| *c_type == type_)
+        .map(|(_, path)| *path)
+}
+
+/// The path of an item relative to the root of the generated crate, from its path in
+/// the main crate like `prelude::WidgetExt` or through a dependency like `gdk::Rectangle`.
+/// The `glib::` items are at the root of the glib crate, which also contains the gobject
+/// bindings the other crates re-export as `glib::`.
+fn crate_relative_path(path: &str, is_glib_crate: bool) -> &str {
+    if is_glib_crate {
+        path.strip_prefix("glib::").unwrap_or(path)
     } else {
-        format!("[`{n}`][crate::{n}]", n = sym.full_rust_name())
+        path
+    }
+}
+
+/// The intra-doc link target of an item, see [`crate_relative_path`]
+fn crate_path(path: &str, is_glib_crate: bool) -> String {
+    format!("crate::{}", crate_relative_path(path, is_glib_crate))
+}
+
+/// The intra-doc link target of the item generated for a type, in a module like
+/// `prelude` or in the crate of another namespace
+fn type_path(type_id: TypeId, env: &DocContext<'_>) -> String {
+    let sym = env.symbols.by_tid(type_id).unwrap();
+    crate_path(&sym.full_rust_name(), env.library.is_glib_crate())
+}
+
+fn gen_core_type_doc_link(path: &str, is_glib_crate: bool) -> String {
+    format!(
+        "[`{}`][{}]",
+        crate_relative_path(path, is_glib_crate),
+        crate_path(path, is_glib_crate)
+    )
+}
+
+fn gen_core_function_doc_link(path: &str, is_glib_crate: bool) -> String {
+    let path = crate_relative_path(path, is_glib_crate);
+    // Only the `ObjectExt::emit_by_name` like end of the path is shown
+    let visible = path
+        .rsplitn(3, "::")
+        .nth(2)
+        .map_or(path, |prefix| &path[prefix.len() + 2..]);
+    format!("[`{}()`][{}()]", visible, crate_path(path, is_glib_crate))
+}
+
+/// Link to the type of a parameter, falling back to its C type
+pub(super) fn find_param_type(
+    c_type: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> String {
+    find_type(c_type_name(c_type), env, in_type).unwrap_or_else(|| format!("`{}`", c_type))
+}
+
+/// Strip the qualifiers and pointers of a C type, like `const GtkWidget*`
+fn c_type_name(c_type: &str) -> &str {
+    let mut name = c_type.trim_start();
+    while let Some(rest) = name
+        .strip_prefix("const ")
+        .or_else(|| name.strip_prefix("volatile "))
+    {
+        name = rest.trim_start();
+    }
+    name.trim_end_matches(|c: char| c == '*' || c.is_whitespace())
+}
+
+fn gen_interface_trait_doc_link(trait_name: &str) -> String {
+    format!("[`{0}`][trait@crate::prelude::{0}]", trait_name)
+}
+
+/// Docs sometimes use plurals like `#GtkWidgets`, link those to the singular
+/// type and keep the "s" outside of the link
+fn find_plural_type(
+    type_: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let singular = singular_type_name(type_)?;
+    find_type(singular, env, in_type).map(|link| gen_plural_doc_link(&link))
+}
+
+fn singular_type_name(type_: &str) -> Option<&str> {
+    type_
+        .strip_suffix('s')
+        .filter(|singular| !singular.is_empty() && !singular.ends_with('s'))
+}
+
+fn gen_plural_doc_link(link: &str) -> String {
+    format!("{}s", link)
+}
+
+/// Whether an item introduced in `target_version` can be linked from the docs of the
+/// item entered in `env` and of `in_type`, see `doc.version_aware_links`
+pub(super) fn is_available_in(
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    target_version: Option<Version>,
+) -> bool {
+    if !env.config.doc.version_aware_links {
+        return true;
+    }
+    let type_version = in_type.and_then(|(tid, _)| env.library.type_(*tid).get_version());
+    // The item is only available once both it and its type are
+    let item_version = env.item_version().max(type_version);
+    is_version_compatible(env.config.min_cfg_version, target_version, item_version)
+}
+
+/// A target is compatible if it doesn't require a newer version than the item
+/// referencing it. Versions up to `min_cfg_version` are always available.
+fn is_version_compatible(
+    min_cfg_version: Version,
+    target_version: Option<Version>,
+    item_version: Option<Version>,
+) -> bool {
+    let filter = |v: Option<Version>| v.filter(|v| *v > min_cfg_version);
+    Version::if_stricter_than(filter(target_version), filter(item_version)).is_none()
+}
+
+fn find_method_or_function_by_ctype(
+    c_type: Option<&str>,
+    name: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    if c_type.is_none() {
+        return find_function_by_glib_name(name, env, in_type);
+    }
+    find_method_or_function(
+        name,
+        env,
+        in_type,
+        |f| f.glib_name == name,
+        |o| c_type.map_or(true, |t| o.c_type == t),
+        |r| c_type.map_or(true, |t| r.type_(env.library).c_type == t),
+        |r| c_type.map_or(true, |t| r.type_(env.library).c_type == t),
+        |r| c_type.map_or(true, |t| r.type_(env.library).c_type == t),
+        c_type.map_or(false, |t| t.ends_with("Class")),
+    )
+}
+
+/// Same as [`find_method_or_function`] for any type, but through the functions index
+/// instead of a scan of every analyzed function
+fn find_function_by_glib_name(
+    name: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let locations = env.function_index.get(name)?;
+    let search_fn = |f: &Info| is_available_in(env, in_type, f.version);
+    let mut owners: Vec<&FunctionOwner> = locations.iter().map(|(owner, _)| owner).collect();
+    owners.dedup();
+    // The first owner with a linkable function wins, the locations are in lookup order
+    owners.into_iter().find_map(|owner| {
+        let functions = locations
+            .iter()
+            .filter(|(o, _)| o == owner)
+            .filter_map(|(_, pos)| env.analysis.function_at(owner, *pos));
+        let fn_info = analysis::find_function(env.library, functions, search_fn)?;
+        Some(match owner {
+            FunctionOwner::Object(name) => {
+                let obj_info = &env.analysis.objects[name];
+                gen_object_fn_doc_link(obj_info, fn_info, env, in_type, &obj_info.name)
+            }
+            FunctionOwner::Record(name) => {
+                gen_type_fn_doc_link(env.analysis.records[name].type_id, fn_info, env, in_type)
+            }
+            FunctionOwner::Enum(pos) => gen_type_fn_doc_link(
+                env.analysis.enumerations[*pos].type_id,
+                fn_info,
+                env,
+                in_type,
+            ),
+            FunctionOwner::Flags(pos) => {
+                gen_type_fn_doc_link(env.analysis.flags[*pos].type_id, fn_info, env, in_type)
+            }
+            FunctionOwner::Global => gen_fn_doc_link(fn_info, env, None, None, false),
+        })
+    })
+}
+
+/// Look for an unqualified `get_label()` like reference in the methods of `in_type`,
+/// an object or a record, and of its parents. They take precedence over the global
+/// functions of the same name.
+fn find_in_type_method(
+    name: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let (tid, _) = in_type?;
+    find_type_method(name, *tid, env, in_type)
+}
+
+/// Look for the `name` method of the object or record `tid`. The methods an object
+/// inherits are linked on the parent or interface declaring them.
+fn find_type_method(
+    name: &str,
+    tid: TypeId,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let search_fn = |f: &Info| f.name == name && is_available_in(env, in_type, f.version);
+    let objects = &env.analysis.objects;
+    if let Some(obj_info) = objects.values().find(|o| o.type_id == tid) {
+        let owner = inherited_owner(obj_info, objects, |o| {
+            analysis::find_function(env.library, o.functions.iter(), search_fn).is_some()
+        })?;
+        let fn_info = analysis::find_function(env.library, owner.functions.iter(), search_fn)?;
+        return Some(gen_object_fn_doc_link(
+            owner,
+            fn_info,
+            env,
+            in_type,
+            &owner.name,
+        ));
+    }
+    env.analysis
+        .find_record_by_function(env.library, |r| r.type_id == tid, search_fn)
+        .map(|(record_info, fn_info)| {
+            gen_type_fn_doc_link(record_info.type_id, fn_info, env, in_type)
+        })
+}
+
+/// Look for a `#GtkButton.show()` or `GtkButton::show()` like reference in the
+/// methods of the qualifying type and of its ancestry
+fn find_qualified_method(
+    qualifier: &str,
+    name: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let c_type = qualifier.trim_end_matches(|c| c == ':' || c == '.');
+    let tid = *env.type_index.get(c_type)?;
+    find_type_method(name, tid, env, in_type)
+}
+
+/// Find a function in all the possible items, if not found return the original name surrounded with backticks.
+/// A function can either be a struct/interface/record method, a global function or maybe a virtual function
+///
+/// This function is generic so it can be de-duplicated between a
+/// - [`find_method_or_function_by_ctype()`] where the object/records are looked by their C name
+/// - [`gi_docgen::find_method_or_function_by_name()`] where the object/records are looked by their name
+pub(crate) fn find_method_or_function(
+    name: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    search_fn: impl Fn(&crate::analysis::functions::Info) -> bool + Copy,
+    search_obj: impl Fn(&crate::analysis::object::Info) -> bool + Copy,
+    search_record: impl Fn(&crate::analysis::record::Info) -> bool + Copy,
+    search_enum: impl Fn(&crate::analysis::enums::Info) -> bool + Copy,
+    search_flag: impl Fn(&crate::analysis::flags::Info) -> bool + Copy,
+    is_class_method: bool,
+) -> Option<String> {
+    if is_class_method {
+        info!("Class methods are not supported yet `{}`", name);
+        return None;
+    }
+    let search_fn = move |f: &Info| search_fn(f) && is_available_in(env, in_type, f.version);
+
+    // if we can find the function in an object
+    if let Some((obj_info, fn_info)) =
+        env.analysis
+            .find_object_by_function(env.library, search_obj, search_fn)
+    {
+        Some(gen_object_fn_doc_link(
+            obj_info,
+            fn_info,
+            env,
+            in_type,
+            &obj_info.name,
+        ))
+    // or in a record
+    } else if let Some((record_info, fn_info)) =
+        env.analysis
+            .find_record_by_function(env.library, search_record, search_fn)
+    {
+        Some(gen_type_fn_doc_link(
+            record_info.type_id,
+            fn_info,
+            env,
+            in_type,
+        ))
+    } else if let Some((enum_info, fn_info)) =
+        env.analysis
+            .find_enum_by_function(env.library, search_enum, search_fn)
+    {
+        Some(gen_type_fn_doc_link(
+            enum_info.type_id,
+            fn_info,
+            env,
+            in_type,
+        ))
+    } else if let Some((flag_info, fn_info)) =
+        env.analysis
+            .find_flag_by_function(env.library, search_flag, search_fn)
+    {
+        Some(gen_type_fn_doc_link(
+            flag_info.type_id,
+            fn_info,
+            env,
+            in_type,
+        ))
+    // or as a global function
+    } else if let Some(fn_info) = env.analysis.find_global_function(env.library, search_fn) {
+        Some(gen_fn_doc_link(fn_info, env, None, None, false))
+    // or in a parent class or interface of the object
+    } else if let Some((obj_info, fn_info)) =
+        env.analysis
+            .find_object_by_inherited_function(env.library, search_obj, search_fn)
+    {
+        Some(gen_object_fn_doc_link(
+            obj_info,
+            fn_info,
+            env,
+            in_type,
+            &obj_info.name,
+        ))
+    } else {
+        None
+    }
+}
+
+/// Link to a function, or to its `_future` variant if `doc.prefer_future_links` is set
+fn gen_fn_doc_link(
+    fn_info: &Info,
+    env: &DocContext<'_>,
+    parent: Option<&str>,
+    visible_parent: Option<&str>,
+    is_self: bool,
+) -> String {
+    if env.config.doc.prefer_future_links {
+        if let Some(link) = fn_info.future_doc_link(parent, visible_parent, is_self) {
+            return link;
+        }
+    }
+    fn_info.doc_link(parent, visible_parent, is_self)
+}
+
+pub(crate) fn gen_type_fn_doc_link(
+    type_id: TypeId,
+    fn_info: &Info,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> String {
+    let symbols = env.symbols;
+    let sym_name = symbols.by_tid(type_id).unwrap().full_rust_name();
+    let is_self = in_type == Some((&type_id, None));
+
+    gen_fn_doc_link(fn_info, env, Some(&sym_name), None, is_self)
+}
+
+pub(crate) fn gen_object_fn_doc_link(
+    obj_info: &crate::analysis::object::Info,
+    fn_info: &Info,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    visible_name: &str,
+) -> String {
+    let symbols = env.symbols;
+    let sym = symbols.by_tid(obj_info.type_id).unwrap();
+    let is_self = in_type == Some((&obj_info.type_id, Some(obj_info.function_location(fn_info))));
+
+    if fn_info.kind == FunctionKind::Method {
+        let (type_name, visible_type_name) = obj_info.generate_doc_link_info(fn_info);
+
+        gen_fn_doc_link(
+            fn_info,
+            env,
+            Some(&sym.full_rust_name().replace(visible_name, &type_name)),
+            Some(&visible_type_name),
+            is_self,
+        )
+    } else {
+        gen_fn_doc_link(fn_info, env, Some(&sym.full_rust_name()), None, is_self)
+    }
+}
+
+// Helper function to generate a doc link for an enum member/bitfield variant
+pub(crate) fn gen_member_doc_link(
+    type_id: TypeId,
+    member_name: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> String {
+    let symbols = env.symbols;
+    let sym = symbols.by_tid(type_id).unwrap().full_rust_name();
+    let is_self = in_type == Some((&type_id, None));
+
+    if is_self {
+        format!("[`{m}`][Self::{m}]", m = member_name)
+    } else {
+        format!(
+            "[`{s}::{m}`][{p}::{m}]",
+            s = sym,
+            p = type_path(type_id, env),
+            m = member_name
+        )
+    }
+}
+
+pub(crate) fn gen_const_doc_link(const_info: &crate::analysis::constants::Info) -> String {
+    // for whatever reason constants are not part of the symbols list, they are all
+    // at the root of the main crate
+    format!(
+        "[`{}`][{}]",
+        const_info.name,
+        crate_path(&const_info.name, false)
+    )
+}
+
+pub(crate) fn gen_signal_doc_link(symbol: &str, signal: &str) -> String {
+    format!("`signal::{}::{}`", symbol, signal)
+}
+
+pub(crate) fn gen_property_doc_link(symbol: &str, property: &str) -> String {
+    format!("`property::{}::{}`", symbol, property)
+}
+
+pub(crate) fn gen_vfunc_doc_link(symbol: &str, vfunc: &str) -> String {
+    format!("`vfunc::{}::{}`", symbol, vfunc)
+}
+
+pub(crate) fn gen_callback_doc_link(callback: &str) -> String {
+    format!("`callback::{}", callback)
+}
+
+pub(crate) fn gen_alias_doc_link(alias: &str) -> String {
+    format!("`alias::{}`", alias)
+}
+
+pub(crate) fn gen_symbol_doc_link(type_id: TypeId, env: &DocContext<'_>) -> String {
+    let symbols = env.symbols;
+    let sym = symbols.by_tid(type_id).unwrap();
+    let path = type_path(type_id, env);
+    // Workaround the case of glib::Variant being a derive macro and a struct
+    if sym.name() == "Variant" && (sym.crate_name().is_none() || sym.crate_name() == Some("glib")) {
+        format!("[`{}`][struct@{}]", sym.full_rust_name(), path)
+    } else {
+        format!("[`{}`][{}]", sym.full_rust_name(), path)
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_version_compatible() {
+        let v4_0 = Version(4, 0, 0);
+        let v4_2 = Version(4, 2, 0);
+        let v4_10 = Version(4, 10, 0);
+
+        assert!(is_version_compatible(v4_0, None, None));
+        assert!(is_version_compatible(v4_0, None, Some(v4_10)));
+        assert!(is_version_compatible(v4_0, Some(v4_2), Some(v4_10)));
+        assert!(is_version_compatible(v4_0, Some(v4_10), Some(v4_10)));
+        assert!(!is_version_compatible(v4_0, Some(v4_10), Some(v4_2)));
+        assert!(!is_version_compatible(v4_0, Some(v4_10), None));
+        // The same reference is fine once the crate requires a newer version
+        assert!(is_version_compatible(v4_10, Some(v4_10), None));
+        assert!(is_version_compatible(v4_10, Some(v4_10), Some(v4_2)));
+    }
+
+    #[test]
+    fn test_bare_types() {
+        let find =
+            |name: &str| (name == "GtkWidget").then(|| "[`Widget`][crate::Widget]".to_owned());
+        assert_eq!(
+            bare_types("A GtkWidget is shown.", find),
+            "A [`Widget`][crate::Widget] is shown."
+        );
+        // The words that aren't the exact C name of a type are left alone
+        let input = "A Widget or a GtkWidgets is shown";
+        assert_eq!(bare_types(input, find), input);
+        let input = "[`GtkWidget`][crate::Widget], `GtkWidget`, #GtkWidget and Gtk.GtkWidget";
+        assert_eq!(bare_types(input, find), input);
+        let input = "See [GtkWidget](https://docs.gtk.org/gtk4/class.Widget.html#GtkWidget)";
+        assert_eq!(bare_types(input, find), input);
+    }
+
+    #[test]
+    fn test_macro_constant() {
+        assert!(is_macro_name("GTK_MAJOR_VERSION"));
+        assert!(is_macro_name("GDK_KEY_3270_ENTER"));
+        assert!(!is_macro_name("GtkWidget"));
+        assert!(!is_macro_name("_GTK_PRIVATE"));
+        assert!(!is_macro_name(""));
+
+        let constant = |name: &str, version| analysis::constants::Info {
+            name: name.to_owned(),
+            glib_name: format!("GTK_{}", name),
+            typ: TypeId::default(),
+            version,
+            deprecated_version: None,
+            cfg_condition: None,
+        };
+        let constants = vec![
+            constant("MAJOR_VERSION", None),
+            constant("PRIORITY_RESIZE", Some(Version(4, 10, 0))),
+        ];
+        let found = find_constant_info(&constants, "GTK_MAJOR_VERSION", |_| true);
+        assert_eq!(
+            found.map(gen_const_doc_link).as_deref(),
+            Some("[`MAJOR_VERSION`][crate::MAJOR_VERSION]")
+        );
+        let is_available = |version: Option<Version>| version.is_none();
+        assert!(find_constant_info(&constants, "GTK_PRIORITY_RESIZE", is_available).is_none());
+        assert!(find_constant_info(&constants, "GTK_MINOR_VERSION", |_| true).is_none());
+    }
+
+    #[test]
+    fn test_link_text() {
+        let link = || "[`Widget`][crate::Widget]".to_owned();
+        assert_eq!(
+            with_link_text(link(), "GtkWidget", LinkText::Rust),
+            "[`Widget`][crate::Widget]"
+        );
+        assert_eq!(
+            with_link_text(link(), "GtkWidget", LinkText::C),
+            "[`GtkWidget`][crate::Widget]"
+        );
+        assert_eq!(
+            with_link_text(
+                "[`WidgetExt::show()`][crate::prelude::WidgetExt::show()] (since 4.10)".to_owned(),
+                "gtk_widget_show()",
+                LinkText::C
+            ),
+            "[`gtk_widget_show()`][crate::prelude::WidgetExt::show()] (since 4.10)"
+        );
+        assert_eq!(
+            with_link_text(link() + "s", "GtkWidget", LinkText::C),
+            "[`GtkWidget`][crate::Widget]s"
+        );
+        assert_eq!(
+            with_link_text("[`None`]".to_owned(), "NULL", LinkText::C),
+            "[`None`]"
+        );
+        assert_eq!(
+            with_link_text("`GtkWidget`".to_owned(), "GtkWidget", LinkText::C),
+            "`GtkWidget`"
+        );
+    }
+
+    #[test]
+    fn test_availability_note() {
+        let v4_0 = Version(4, 0, 0);
+        let v4_2 = Version(4, 2, 0);
+        let v4_10 = Version(4, 10, 0);
+
+        assert_eq!(
+            availability_note(v4_0, Some(v4_10), None).as_deref(),
+            Some("(since 4.10)")
+        );
+        assert_eq!(
+            availability_note(v4_0, Some(v4_0), Some(v4_10)).as_deref(),
+            Some("(deprecated since 4.10)")
+        );
+        assert_eq!(
+            availability_note(v4_0, Some(v4_2), Some(v4_10)).as_deref(),
+            Some("(since 4.2, deprecated since 4.10)")
+        );
+        // Everything up to the minimum supported version is available
+        assert_eq!(availability_note(v4_2, Some(v4_2), None), None);
+        assert_eq!(availability_note(v4_0, None, None), None);
+    }
+
+    #[test]
+    fn test_unqualified_function_reference() {
+        // Only the references without a `GtkButton.` like qualifier are looked for in
+        // the methods of `in_type`, linked as `Self::get_label()` by the method name
+        let caps = FUNCTION.captures("Use get_label() instead").unwrap();
+        assert!(caps.get(2).is_none());
+        assert_eq!(&caps[3], "get_label");
+        let caps = FUNCTION
+            .captures("Use #GtkButton.get_label() instead")
+            .unwrap();
+        assert_eq!(caps.get(2).map(|m| m.as_str()), Some("GtkButton."));
+    }
+
+    #[test]
+    fn test_type_macro() {
+        assert_eq!(
+            parse_type_macro("GTK_IS_WIDGET"),
+            (true, "gtk_widget_get_type".to_string())
+        );
+        assert_eq!(
+            parse_type_macro("GTK_IS_TREE_VIEW"),
+            (true, "gtk_tree_view_get_type".to_string())
+        );
+        assert_eq!(
+            parse_type_macro("G_OBJECT"),
+            (false, "g_object_get_type".to_string())
+        );
+        assert_eq!(
+            gen_type_macro_doc_link("glib::prelude::Cast::is", "Widget"),
+            "[`is::<Widget>()`][glib::prelude::Cast::is()]"
+        );
+
+        let caps = MACRO.captures("check with GTK_IS_WIDGET() first").unwrap();
+        assert_eq!(&caps[2], "GTK_IS_WIDGET");
+        assert!(MACRO.captures("call gtk_widget_show()").is_none());
+    }
+
+    #[test]
+    fn test_replace_macros() {
+        let find = |name: &str| {
+            (name == "GTK_WIDGET")
+                .then(|| gen_type_macro_doc_link("glib::prelude::Cast::dynamic_cast", "Widget"))
+        };
+        assert_eq!(
+            replace_macros("Cast it with GTK_WIDGET().", find),
+            "Cast it with [`dynamic_cast::<Widget>()`][glib::prelude::Cast::dynamic_cast()]."
+        );
+        assert_eq!(
+            replace_macros(
+                "Like G_TYPE_CHECK_INSTANCE_CAST() or GDK_IS_X11_Display().",
+                find
+            ),
+            "Like `G_TYPE_CHECK_INSTANCE_CAST()` or `GDK_IS_X11_Display()`."
+        );
+        let input = "Like `G_TYPE_CHECK_INSTANCE_CAST()` does";
+        assert_eq!(replace_macros(input, find), input);
+        // The inline code of a resolved macro is replaced by the link
+        assert_eq!(
+            replace_macros("Cast it with `GTK_WIDGET()`.", find),
+            "Cast it with [`dynamic_cast::<Widget>()`][glib::prelude::Cast::dynamic_cast()]."
+        );
+        assert!(is_ignored_function(
+            "G_TYPE_CHECK_INSTANCE_CAST",
+            &DocConfig::default()
+        ));
+    }
+
+    #[test]
+    fn test_find_named_member() {
+        use crate::config::gobjects::GStatus;
+
+        let member = |name: &str, c_identifier: &str, status| Member {
+            name: name.to_owned(),
+            c_identifier: c_identifier.to_owned(),
+            value: "0".to_owned(),
+            doc: None,
+            doc_deprecated: None,
+            status,
+            version: None,
+            deprecated_version: None,
+        };
+        let members = [
+            member("default", "G_NORMALIZE_DEFAULT", GStatus::Generate),
+            member("nfd", "G_NORMALIZE_NFD", GStatus::Ignore),
+            member("default_compose", "G_NORMALIZE_NFC", GStatus::Generate),
+        ];
+        let name = |member: &str| {
+            find_named_member(&members, member).map(|m| nameutil::enum_member_name(&m.name))
+        };
+        assert_eq!(name("NFC").as_deref(), None);
+        assert_eq!(name("G_NORMALIZE_NFC").as_deref(), Some("DefaultCompose"));
+        assert_eq!(name("DEFAULT_COMPOSE").as_deref(), Some("DefaultCompose"));
+        assert_eq!(name("default").as_deref(), Some("Default"));
+        assert_eq!(name("NFD"), None);
+        assert_eq!(name("G_NORMALIZE_NFD"), None);
+    }
+
+    #[test]
+    fn test_wrap_lines() {
+        let input = "\
+This paragraph is long enough to be wrapped at the limit.
+```c
+int a_very_long_line_of_code_that_should_not_be_wrapped = 0;
+```
+See [`Widget::show()`][crate::Widget::show()] and `a code span with spaces` or [the docs](https://example.com/docs).
+- A list item that is long enough to wrap.
+> A quote that is long enough to be wrapped.
+| A table row that is long enough to stay | b |
+Short line";
+        assert_eq!(
+            wrap_lines(input, 30),
+            "\
+This paragraph is long enough
+to be wrapped at the limit.
+```c
+int a_very_long_line_of_code_that_should_not_be_wrapped = 0;
+```
+See
+[`Widget::show()`][crate::Widget::show()]
+and `a code span with spaces`
+or [the
+docs](https://example.com/docs).
+- A list item that is long
+  enough to wrap.
+> A quote that is long enough
+> to be wrapped.
+| A table row that is long enough to stay | b |
+Short line"
+        );
+        assert_eq!(
+            wrap_words("a `b c` ``d ` e`` [f](g h) i"),
+            ["a", "`b c`", "``d ` e``", "[f](g h)", "i"]
+        );
+    }
+
+    #[test]
+    fn test_c_idioms() {
+        assert_eq!(
+            c_idioms("a %NULL-terminated array of strings"),
+            "a NULL-terminated array of strings"
+        );
+        assert_eq!(
+            c_idioms("a %NULL-separated list, or %NULL"),
+            "a NULL-separated list, or %NULL"
+        );
+        assert_eq!(c_idioms("Returns %NULL."), "Returns %NULL.");
+        assert_eq!(c_idioms("%NULL-ish"), "%NULL-ish");
+        assert!(matches!(c_idioms("%NULL"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_gi_docgen_enabled() {
+        let skipped = vec!["Gtk".to_owned()];
+        assert!(gi_docgen_enabled(true, &[], "Gtk"));
+        assert!(gi_docgen_enabled(true, &skipped, "Gdk"));
+        assert!(!gi_docgen_enabled(true, &skipped, "Gtk"));
+        assert!(!gi_docgen_enabled(false, &[], "Gtk"));
+    }
+
+    struct MockResolver;
+
+    impl DocResolver for MockResolver {
+        fn resolve_type(&self, c_type: &str) -> Option<String> {
+            (c_type == "GtkWidget").then(|| "[`Widget`][crate::Widget]".to_owned())
+        }
+
+        fn resolve_function(&self, c_name: &str) -> Option<String> {
+            (c_name == "gtk_widget_show")
+                .then(|| "[`WidgetExt::show()`][crate::prelude::WidgetExt::show()]".to_owned())
+        }
+
+        fn resolve_constant(&self, c_identifier: &str) -> Option<String> {
+            (c_identifier == "GTK_ALIGN_START")
+                .then(|| "[`Align::Start`][crate::Align::Start]".to_owned())
+        }
+    }
+
+    #[test]
+    fn test_reformat_standalone_doc() {
+        assert_eq!(
+            reformat_standalone_doc(
+                "Shows a #GtkWidget with gtk_widget_show(), aligned to %GTK_ALIGN_START.",
+                &MockResolver
+            ),
+            "Shows a [`Widget`][crate::Widget] with \
+             [`WidgetExt::show()`][crate::prelude::WidgetExt::show()], aligned to \
+             [`Align::Start`][crate::Align::Start]."
+        );
+        assert_eq!(
+            reformat_standalone_doc(
+                "Returns %TRUE unless #GtkLabel:label or gtk_label_new() is %NULL, see \
+                 <literal>a &amp; b</literal>:\n|[<!-- language=\"C\" -->\n\
+                 gtk_widget_show (#GtkWidget);\n]|",
+                &MockResolver
+            ),
+            "Returns [`true`] unless `GtkLabel:label` or `gtk_label_new()` is [`None`], see \
+             `a & b`:\n\n\n**⚠️ The following code is in C ⚠️**\n\n```C\n\
+             gtk_widget_show (#GtkWidget);\n```"
+        );
+    }
+
+    /// The code fences are balanced, open with a known language and close on a line of
+    /// their own, and every generated link is complete
+    fn assert_doc_invariants(input: &str, output: &str) {
+        let mut open = false;
+        for line in output.lines().filter(|l| l.trim_start().starts_with("```")) {
+            let fence = line.trim();
+            if open {
+                assert_eq!(fence, "```", "closing fence of {:?}: {:?}", input, output);
+            } else {
+                assert!(
+                    ["```text", "```C", "```xml"].contains(&fence),
+                    "opening fence of {:?}: {:?}",
+                    input,
+                    output
+                );
+            }
+            open = !open;
+        }
+        assert!(!open, "unterminated block of {:?}: {:?}", input, output);
+        for (pos, _) in output.match_indices("[`") {
+            let rest = output[pos + 2..].split('\n').next().unwrap();
+            assert!(
+                rest.contains("`]"),
+                "half-open link of {:?}: {:?}",
+                input,
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn test_reformat_doc_invariants() {
+        const FRAGMENTS: &[&str] = &[
+            "|[",
+            "]|",
+            "\n]|",
+            "\n",
+            "\n\n",
+            " ",
+            "|[<!-- language=\"C\" -->",
+            "<!-- language=\"xml\" -->",
+            "#GtkWidget",
+            "#GtkLabel:label",
+            "%GTK_ALIGN_START",
+            "%NULL",
+            "@widget",
+            "gtk_widget_show()",
+            "foo()",
+            "#",
+            "%",
+            "@",
+            "%d",
+            "`code`",
+            "*emphasis*",
+            "&amp;",
+            "&#35;",
+            "<literal>x</literal>",
+            "word",
+            "[class@Gtk.Widget]",
+            "GtkWidget",
+        ];
+        // Regressions: text after the closing fence, code on the line of the opening
+        // one, nested and unterminated blocks
+        let seeds = [
+            "|[\nfoo ();\n]|bar",
+            "|[foo ();\n]|",
+            "|[<!-- language=\"C\" -->foo ();\n]|",
+            "|[\n|[\nfoo\n]|\n]|",
+            "|[\n]||[\n]|",
+            "|[",
+            "]|",
+        ];
+        for input in seeds {
+            assert_doc_invariants(input, &reformat_standalone_doc(input, &MockResolver));
+        }
+        assert_eq!(
+            reformat_standalone_doc("|[foo ();\n]|", &MockResolver),
+            "```text\nfoo ();\n```"
+        );
+        assert_eq!(
+            reformat_standalone_doc("|[\nfoo ();\n]|bar", &MockResolver),
+            "```text\nfoo ();\n```\nbar"
+        );
+
+        // Random docs made of the fragments, from a fixed xorshift sequence
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..500 {
+            let len = 1 + next() % 40;
+            let input = (0..len)
+                .map(|_| FRAGMENTS[(next() % FRAGMENTS.len() as u64) as usize])
+                .collect::<String>();
+            assert_doc_invariants(&input, &reformat_standalone_doc(&input, &MockResolver));
+        }
+
+        // Only terminating is expected of these
+        for input in [
+            "`".repeat(10_000),
+            "|[".repeat(2_000),
+            "]|\n".repeat(2_000),
+            "#%@".repeat(3_000),
+            "<!--".repeat(2_000),
+        ] {
+            reformat_standalone_doc(&input, &MockResolver);
+        }
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\nc\n");
+        assert!(matches!(
+            normalize_line_endings("a\nb"),
+            Cow::Borrowed("a\nb")
+        ));
+        assert_eq!(
+            reformat_standalone_doc(
+                "Shows a #GtkWidget:\r\n|[<!-- language=\"C\" -->\r\ngtk_widget_show (widget);\r\n]|\r\nDone.\r\n",
+                &MockResolver
+            ),
+            "Shows a [`Widget`][crate::Widget]:\n\n\n**⚠️ The following code is in C ⚠️**\n\n```C\n\
+             gtk_widget_show (widget);\n```\nDone.\n"
+        );
+    }
+
+    #[test]
+    fn test_blank_and_code_only_docs() {
+        assert_eq!(reformat_standalone_doc("", &MockResolver), "");
+        assert_eq!(reformat_standalone_doc(" \n\t\n ", &MockResolver), "");
+        assert_eq!(
+            reformat_standalone_doc("|[\ngtk_widget_show (widget);\n]|", &MockResolver),
+            "```text\ngtk_widget_show (widget);\n```"
+        );
+        assert_eq!(
+            reformat_standalone_doc(
+                "|[<!-- language=\"C\" -->\ngtk_widget_show (widget);\n]|",
+                &MockResolver
+            ),
+            "**⚠️ The following code is in C ⚠️**\n\n```C\ngtk_widget_show (widget);\n```"
+        );
+        let prose = |s: &str| s.to_owned();
+        let code = |s: &str| s.to_owned();
+        assert_eq!(
+            language_blocks("\n|[\nfoo ();\n]|\n", &[], false, prose, code),
+            ("```text\nfoo ();\n```\n".to_owned(), true)
+        );
+    }
+
+    #[test]
+    fn test_first_paragraph() {
+        // From the gtk4 `GtkBuilderScope` docs
+        let doc = "`GtkBuilderScope` is an interface to provide language binding support
+to `GtkBuilder`.
+
+The goal of `GtkBuilderScope` is to look up programming-language-specific
+values for strings that are given in a `GtkBuilder` UI file.";
+        assert_eq!(
+            first_paragraph(doc),
+            "`GtkBuilderScope` is an interface to provide language binding support
+to `GtkBuilder`."
+        );
+        let doc = "Creates a new button, see [class@Gtk.
+Button] and the [label
+property](property.Button.label.html):
+```c
+button = gtk_button_new ();
+```
+More details.";
+        assert_eq!(
+            first_paragraph(doc),
+            "Creates a new button, see [class@Gtk.
+Button] and the [label
+property](property.Button.label.html):"
+        );
+        assert_eq!(
+            first_paragraph("\nAn example: |[<!-- language=\"C\" -->\nfoo ();\n]|"),
+            "An example:"
+        );
+        assert_eq!(
+            first_paragraph("<para>The summary.</para>\n<para>The details.</para>"),
+            "<para>The summary.</para>"
+        );
+        assert_eq!(first_paragraph("A single line"), "A single line");
+    }
+
+    #[test]
+    fn test_tables() {
+        // Like the flags tables of the DocBook era gtk3 docs
+        let doc = r#"The flags:
+<informaltable>
+  <tgroup cols="3">
+    <colspec align="left"/>
+    <colspec align="center"/>
+    <colspec/>
+    <thead>
+      <row>
+        <entry>Flag</entry>
+        <entry>Value</entry>
+        <entry>Meaning</entry>
+      </row>
+    </thead>
+    <tbody>
+      <row>
+        <entry>%GTK_DIALOG_MODAL</entry>
+        <entry>1 &lt;&lt; 0</entry>
+        <entry><para>Make the constructed dialog modal, see
+        gtk_window_set_modal()</para></entry>
+      </row>
+      <row>
+        <entry>%GTK_DIALOG_DESTROY_WITH_PARENT</entry>
+        <entry>1 &lt;&lt; 1</entry>
+        <entry>Destroy the dialog when its parent is destroyed</entry>
+      </row>
+      <row>
+        <entry>%GTK_DIALOG_USE_HEADER_BAR</entry>
+        <entry>1 &lt;&lt; 2</entry>
+        <entry>Use a header bar | title</entry>
+      </row>
+    </tbody>
+  </tgroup>
+</informaltable>
+Combine them with `|`."#;
+        assert_eq!(
+            tables(doc),
+            "The flags:
+
+| Flag | Value | Meaning |
+| :--- | :---: | --- |
+| %GTK_DIALOG_MODAL | 1 &lt;&lt; 0 | Make the constructed dialog modal, see gtk_window_set_modal() |
+| %GTK_DIALOG_DESTROY_WITH_PARENT | 1 &lt;&lt; 1 | Destroy the dialog when its parent is destroyed |
+| %GTK_DIALOG_USE_HEADER_BAR | 1 &lt;&lt; 2 | Use a header bar \\| title |
+
+Combine them with `|`."
+        );
+        assert_eq!(
+            tables(
+                "<table>\n<tr><th>Key</th><th align=\"right\">Width</th></tr>\n<tr><td>a</td><td>1</td></tr>\n</table>"
+            ),
+            "| Key | Width |\n| --- | ---: |\n| a | 1 |"
+        );
+        // Spanning cells can't be represented in markdown
+        assert_eq!(
+            tables(
+                "<table><title>Sizes</title><tr><td morerows=\"1\">Small</td><td>16</td></tr><tr><td>24 &amp; more</td></tr></table>"
+            ),
+            "**Sizes**\n\n|[\nSmall      16\n24 & more\n]|"
+        );
+        let plain = "No <tablet> here";
+        assert!(matches!(tables(plain), Cow::Borrowed(s) if s == plain));
+    }
+
+    #[test]
+    fn test_programlistings() {
+        // Like in the docs of the GIR files converted from DocBook
+        let doc = r#"<para>Connect to the signal:
+<programlisting language="C">
+g_signal_connect (button, "clicked",
+                  G_CALLBACK (on_clicked), NULL);
+</programlisting>
+</para>"#;
+        assert_eq!(
+            programlistings(doc),
+            r#"<para>Connect to the signal:
+|[<!-- language="C" -->
+g_signal_connect (button, "clicked",
+                  G_CALLBACK (on_clicked), NULL);
+]|
+</para>"#
+        );
+        let prose = |s: &str| s.to_owned();
+        let code = |s: &str| s.to_owned();
+        assert_eq!(
+            language_blocks(&programlistings(doc), &[], false, prose, code),
+            (
+                r#"<para>Connect to the signal:
+
+
+**⚠️ The following code is in C ⚠️**
+
+```C
+g_signal_connect (button, "clicked",
+                  G_CALLBACK (on_clicked), NULL);
+```
+</para>"#
+                    .to_owned(),
+                true
+            )
+        );
+
+        assert_eq!(
+            programlistings("<programlisting>\tindented\n\n  code</programlisting>"),
+            "|[\n\tindented\n\n  code\n]|"
+        );
+        assert_eq!(
+            programlistings(
+                "<programlisting language='xml'><![CDATA[\n<object class=\"GtkButton\"/>\n]]></programlisting>"
+            ),
+            "|[<!-- language=\"xml\" -->\n<object class=\"GtkButton\"/>\n]|"
+        );
+        assert_eq!(programlistings("No listing"), "No listing");
+    }
+
+    #[test]
+    fn test_unterminated_language_block() {
+        let prose = |s: &str| s.replace("#GtkWidget", "[`Widget`][crate::Widget]");
+        let code = |s: &str| s.to_owned();
+        assert_eq!(
+            language_blocks(
+                "A #GtkWidget:\n|[<!-- language=\"C\" -->\ngtk_widget_show (#GtkWidget);\n",
+                &[],
+                false,
+                prose,
+                code
+            ),
+            (
+                "A [`Widget`][crate::Widget]:\n\n\n**⚠️ The following code is in C ⚠️**\n\n```C\ngtk_widget_show (#GtkWidget);\n```"
+                    .to_owned(),
+                false
+            )
+        );
+        assert_eq!(
+            language_blocks("|[\nfoo ();\n]| and #GtkWidget", &[], false, prose, code),
+            (
+                "```text\nfoo ();\n```\n and [`Widget`][crate::Widget]".to_owned(),
+                true
+            )
+        );
+    }
+
+    #[test]
+    fn test_ignored_allowlists() {
+        let mut config = DocConfig::default();
+        assert!(is_ignored_function("g_free", &config));
+        assert!(!is_ignored_function("g_clear_pointer", &config));
+        let (glib, gtk) = (Some("GLib"), Some("Gtk"));
+        assert!(is_ignored_type("guint", None, &config));
+        assert!(!is_ignored_type("GtkPrivateThing", gtk, &config));
+
+        config.ignored_functions = vec!["g_clear_pointer".to_owned()];
+        config.ignored_types = vec!["GtkPrivateThing".to_owned()];
+        assert!(is_ignored_function("g_clear_pointer", &config));
+        assert!(is_ignored_type("GtkPrivateThing", gtk, &config));
+        assert!(is_ignored_type("guint", None, &config));
+
+        // A removed default is linked again, the other defaults are kept
+        config.ignored_types = vec!["GVariant".to_owned(), "!GList".to_owned()];
+        assert!(is_ignored_type("GVariant", glib, &config));
+        assert!(!is_ignored_type("GList", glib, &config));
+        assert!(is_ignored_type("gunicode", None, &config));
+
+        // The entries qualified by a namespace only apply to its types
+        config.ignored_types = vec!["GLib.GVariant".to_owned(), "!Gtk.GList".to_owned()];
+        assert!(is_ignored_type("GVariant", glib, &config));
+        assert!(!is_ignored_type("GVariant", gtk, &config));
+        assert!(is_ignored_type("GList", glib, &config));
+
+        let gst = Some("Gst");
+        assert!(!is_ignored_constant("GST_PAD_LINK_OK", gst, &config));
+        config.ignored_constants = vec!["GST_PAD_*".to_owned(), "GST_FLOW_OK".to_owned()];
+        assert!(is_ignored_constant("GST_PAD_LINK_OK", gst, &config));
+        assert!(is_ignored_constant("GST_FLOW_OK", None, &config));
+        assert!(!is_ignored_constant("GST_FLOW_ERROR", gst, &config));
+        assert!(!is_ignored_constant("GST_PA", gst, &config));
+
+        // The entries qualified by a namespace only apply to its constants
+        config.ignored_constants = vec!["Gst.GST_PAD_*".to_owned()];
+        assert!(is_ignored_constant("GST_PAD_LINK_OK", gst, &config));
+        assert!(!is_ignored_constant(
+            "GST_PAD_LINK_OK",
+            Some("GstVideo"),
+            &config
+        ));
+        assert!(!is_ignored_constant("GST_PAD_LINK_OK", None, &config));
+    }
+
+    /// An `Env` of `library` with the `doc` config, it needs a `GLib` namespace. Nothing
+    /// is analyzed, the tests add the analyzed types they look up.
+    pub(crate) fn test_env(library: Library, doc: DocConfig) -> Env {
+        use crate::config::{Config, WorkMode};
+        use std::{cell::RefCell, path::PathBuf};
+
+        let namespaces = namespaces::run(&library);
+        let symbols = analysis::symbols::run(&library, &namespaces);
+        let class_hierarchy = analysis::class_hierarchy::run(&library);
+        let config = Config {
+            work_mode: WorkMode::Doc,
+            config_file: PathBuf::new(),
+            girs_dirs: Vec::new(),
+            girs_version: Vec::new(),
+            library_name: library.namespace(MAIN_NAMESPACE).name.clone(),
+            library_version: "1.0".to_owned(),
+            target_path: PathBuf::new(),
+            auto_path: PathBuf::new(),
+            doc_target_path: PathBuf::new(),
+            external_libraries: Vec::new(),
+            objects: Default::default(),
+            min_cfg_version: Version::default(),
+            use_gi_docgen: false,
+            make_backup: false,
+            generate_safety_asserts: false,
+            deprecate_by_min_version: false,
+            show_statistics: false,
+            concurrency: crate::library::Concurrency::None,
+            single_version_file: None,
+            generate_display_trait: false,
+            trust_return_value_nullability: false,
+            docs_rs_features: Vec::new(),
+            disable_format: true,
+            split_build_rs: false,
+            extra_versions: Vec::new(),
+            lib_version_overrides: Default::default(),
+            feature_dependencies: Default::default(),
+            dox_feature_dependencies: Vec::new(),
+            doc,
+        };
+        Env {
+            library,
+            config,
+            namespaces,
+            symbols: RefCell::new(symbols),
+            class_hierarchy,
+            analysis: Default::default(),
+            doc_unresolved: Default::default(),
+            doc_logged: Default::default(),
+            doc_cache: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_ignored_type_links() {
+        fn find(type_: &str, ignored_types: &[&str]) -> Option<String> {
+            let mut library = Library::new("Gtk");
+            library.namespace_mut(MAIN_NAMESPACE).identifier_prefixes = vec!["Gtk".to_owned()];
+            let glib = library.add_namespace("GLib");
+            library.namespace_mut(glib).identifier_prefixes = vec!["G".to_owned()];
+            let doc = DocConfig {
+                ignored_types: ignored_types.iter().map(|&t| t.to_owned()).collect(),
+                type_links: [
+                    ("GList", "glib::List"),
+                    ("GtkPrivateThing", "gtk::PrivateThing"),
+                ]
+                .iter()
+                .map(|&(c_type, path)| (c_type.to_owned(), path.to_owned()))
+                .collect(),
+                ..Default::default()
+            };
+            let env = test_env(library, doc);
+            let symbols = env.symbols.borrow();
+            let ctx = DocContext::new(&env, &symbols);
+            find_type(type_, &ctx, None)
+        }
+        assert_eq!(
+            find("GtkPrivateThing", &[]),
+            Some("[`gtk::PrivateThing`][crate::gtk::PrivateThing]".to_owned())
+        );
+        assert_eq!(find("GList", &[]), None);
+
+        // An added entry isn't linked anymore, a removed default is linked again
+        assert_eq!(find("GtkPrivateThing", &["GtkPrivateThing"]), None);
+        assert!(find("GList", &["!GList"]).is_some());
+
+        // Only the entries of the namespace of the type apply to it
+        assert_eq!(find("GtkPrivateThing", &["Gtk.GtkPrivateThing"]), None);
+        assert!(find("GtkPrivateThing", &["GLib.GtkPrivateThing"]).is_some());
+        assert!(find("GList", &["!GLib.GList"]).is_some());
+        assert_eq!(find("GList", &["!Gtk.GList"]), None);
+    }
+
+    #[test]
+    fn test_ignored_constant_fallback() {
+        let mut library = Library::new("Gst");
+        library.namespace_mut(MAIN_NAMESPACE).symbol_prefixes = vec!["gst".to_owned()];
+        library.add_namespace("GLib");
+        let doc = DocConfig {
+            ignored_constants: vec![
+                "Gst.GST_PAD_PROBE_*".to_owned(),
+                "Gtk.GST_FLOW_*".to_owned(),
+            ],
+            ..Default::default()
+        };
+        let env = test_env(library, doc);
+        assert_eq!(
+            c_name_namespace(&env.library, "GST_PAD_PROBE_OK"),
+            Some("Gst")
+        );
+        assert_eq!(c_name_namespace(&env.library, "GTK_ALIGN_START"), None);
+
+        let symbols = env.symbols.borrow();
+        let ctx = DocContext::new(&env, &symbols);
+        let misses = RefCell::default();
+        for constant in ["GST_PAD_PROBE_OK", "GST_FLOW_OK"] {
+            assert_eq!(
+                find_constant_or_variant_wrapper(constant, None, &ctx, None, &misses),
+                format!("`{}`", constant)
+            );
+        }
+        // Only the miss that isn't expected is returned
+        let misses = misses.into_inner();
+        assert_eq!(misses.len(), 1);
+        assert_eq!(misses[0].kind, RefKind::Constant);
+        assert_eq!(misses[0].symbol, "GST_FLOW_OK");
+        assert!(misses[0].record);
+    }
+
+    #[test]
+    fn test_reformat_doc_with_misses() {
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let env = test_env(library, DocConfig::default());
+        let symbols = env.symbols.borrow();
+        let ctx = DocContext::new(&env, &symbols);
+
+        // The miss is returned with the doc, the `Env` is left as it was
+        assert_eq!(
+            reformat_doc_with("See #GtkFrobnicator", &ctx, None),
+            FormattedDoc {
+                doc: "See `GtkFrobnicator`".to_owned(),
+                misses: vec![Miss {
+                    kind: RefKind::Type,
+                    symbol: "GtkFrobnicator".to_owned(),
+                    log: Some((Level::Info, "Type `#GtkFrobnicator` not found".to_owned())),
+                    record: true,
+                }],
+            }
+        );
+        assert_eq!(reformat_doc_with("  ", &ctx, None), FormattedDoc::default());
+    }
+
+    #[test]
+    fn test_unresolved_text() {
+        assert_eq!(
+            unresolved_text("gtk_widget_frob()", UnresolvedStyle::Backticks),
+            "`gtk_widget_frob()`"
+        );
+        assert_eq!(
+            unresolved_text("GtkFrobnicator", UnresolvedStyle::Plain),
+            "GtkFrobnicator"
+        );
+        assert_eq!(unresolved_text("GTK_FROB_MAX", UnresolvedStyle::Drop), "");
+        // The space left by a dropped reference is collapsed
+        let doc = format!(
+            "Call {} first",
+            unresolved_text("gtk_frob()", UnresolvedStyle::Drop)
+        );
+        assert_eq!(collapse_spaces(&doc), "Call first");
+    }
+
+    #[test]
+    fn test_adjacent_language_blocks() {
+        let prose = |s: &str| s.to_owned();
+        let code = |s: &str| s.to_owned();
+        assert_eq!(
+            language_blocks("|[\nfoo ();\n]||[\nbar ();\n]|", &[], false, prose, code),
+            (
+                "```text\nfoo ();\n```\n\n```text\nbar ();\n```".to_owned(),
+                true
+            )
+        );
+        assert_eq!(
+            language_blocks("|[\nfoo ();\n]|\n|[\nbar ();\n]|", &[], false, prose, code),
+            (
+                "```text\nfoo ();\n```\n\n```text\nbar ();\n```".to_owned(),
+                true
+            )
+        );
+        assert_eq!(
+            language_blocks(
+                "|[\nfoo ();\n]||[<!-- language=\"C\" -->\nbar ();\n]|\nDone",
+                &[],
+                false,
+                prose,
+                code
+            ),
+            (
+                "```text\nfoo ();\n```\n\n\n**⚠️ The following code is in C ⚠️**\n\n```C\nbar ();\n```\nDone"
+                    .to_owned(),
+                true
+            )
+        );
+    }
+
+    #[test]
+    fn test_orphan_language_markers() {
+        assert_eq!(
+            orphan_language_markers("Call it like:\n<!-- language=\"C\" -->\n\nfoo ();"),
+            "Call it like:\n\nfoo ();"
+        );
+        assert_eq!(
+            orphan_language_markers("Use foo() <!-- language=\"C\" --> to do it"),
+            "Use foo() to do it"
+        );
+        let prose = |s: &str| orphan_language_markers(s).into_owned();
+        let code = |s: &str| s.to_owned();
+        assert_eq!(
+            language_blocks(
+                "Like <!-- language=\"C\" -->:\n|[\n<!-- language=\"C\" -->\nfoo ();\n]|",
+                &[],
+                false,
+                prose,
+                code
+            ),
+            (
+                "Like:\n\n\n**⚠️ The following code is in C ⚠️**\n\n```C\nfoo ();\n```".to_owned(),
+                true
+            )
+        );
+        let plain = "An <!-- other --> comment";
+        assert!(matches!(orphan_language_markers(plain), Cow::Borrowed(s) if s == plain));
+    }
+
+    #[test]
+    fn test_get_language() {
+        let mut out = String::new();
+        assert_eq!(
+            get_language("<!-- language=\"C\" -->\nfoo ();\n]|", &[], false, &mut out),
+            "\nfoo ();\n]|"
+        );
+        assert_eq!(out, "\n\n**⚠️ The following code is in C ⚠️**\n\n```C");
+
+        let mut out = String::new();
+        assert_eq!(
+            get_language("<!-- -->\nfoo ();\n]|", &[], false, &mut out),
+            "\nfoo ();\n]|"
+        );
+        assert_eq!(out, "\n```text");
+
+        let mut out = String::new();
+        assert_eq!(
+            get_language("<!-- language=\"\" -->\nfoo ();\n]|", &[], false, &mut out),
+            "\nfoo ();\n]|"
+        );
+        assert_eq!(out, "\n```text");
+
+        let mut out = String::new();
+        assert_eq!(
+            get_language(
+                "\nfoo ();\n]| <!-- language=\"C\" -->",
+                &[],
+                false,
+                &mut out
+            ),
+            "\nfoo ();\n]| <!-- language=\"C\" -->"
+        );
+        assert_eq!(out, "\n```text");
+
+        // A marker on the line after the `|[`
+        let mut out = String::new();
+        assert_eq!(
+            get_language(
+                "\n  <!-- language = 'C' -->\nfoo ();\n]|",
+                &[],
+                false,
+                &mut out
+            ),
+            "\nfoo ();\n]|"
+        );
+        assert_eq!(out, "\n\n**⚠️ The following code is in C ⚠️**\n\n```C");
+
+        let setup = ["use gtk::prelude::*;".to_owned(), String::new()];
+        let mut out = String::new();
+        assert_eq!(
+            get_language(
+                "<!-- language=\"rust\" -->\nfoo();\n]|",
+                &setup,
+                false,
+                &mut out
+            ),
+            "\nfoo();\n]|"
+        );
+        assert_eq!(out, "\n\n```rust\n# use gtk::prelude::*;\n#");
+
+        let mut out = String::new();
+        get_language(
+            "<!-- language=\"C\" -->\nfoo ();\n]|",
+            &setup,
+            false,
+            &mut out,
+        );
+        assert_eq!(out, "\n\n**⚠️ The following code is in C ⚠️**\n\n```C");
+
+        let mut out = String::new();
+        get_language(
+            "<!-- language=\"plain\" -->\nfoo ();\n]|",
+            &[],
+            true,
+            &mut out,
+        );
+        assert_eq!(out, "\n\n```text");
+
+        let mut out = String::new();
+        get_language("<!-- language=\"C\" -->\nfoo ();\n]|", &[], true, &mut out);
+        assert_eq!(
+            out,
+            "\n\n**⚠️ The following code is in C ⚠️**\n\n```C,ignore"
+        );
+
+        let mut out = String::new();
+        get_language(
+            "<!-- language=\"rust\" -->\nfoo();\n]|",
+            &[],
+            true,
+            &mut out,
+        );
+        assert_eq!(out, "\n\n```rust");
+    }
+
+    #[test]
+    fn test_autolink_urls() {
+        assert_eq!(
+            autolink_urls("See https://gtk.org/docs/. for more"),
+            "See <https://gtk.org/docs/>. for more"
+        );
+        assert_eq!(
+            autolink_urls("[GTK](https://gtk.org), <https://gtk.org> and `https://gtk.org`"),
+            "[GTK](https://gtk.org), <https://gtk.org> and `https://gtk.org`"
+        );
+        assert_eq!(
+            format_segments(
+                "Visit https://gtk.org\n```c\nload (\"https://gtk.org\");\n```\n",
+                &[],
+                |prose| autolink_urls(prose).into_owned(),
+                |code| code.to_owned(),
+            ),
+            "Visit <https://gtk.org>\n```c\nload (\"https://gtk.org\");\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_emphasis() {
+        assert_eq!(
+            emphasis("Do <emphasis>not</emphasis> call it twice"),
+            "Do *not* call it twice"
+        );
+        assert_eq!(
+            emphasis(
+                r#"It is <emphasis role="strong">not</emphasis> <emphasis role="bold">safe</emphasis>"#
+            ),
+            "It is **not** **safe**"
+        );
+        assert_eq!(
+            emphasis(r#"<emphasis role="italic">maybe</emphasis>"#),
+            "*maybe*"
+        );
+        // Emphasis with nested tags is left to the generic tags handling
+        let nested = "<emphasis>a <literal>b</literal></emphasis>";
+        assert_eq!(emphasis(nested), nested);
+    }
+
+    #[test]
+    fn test_ulinks() {
+        assert_eq!(
+            ulinks(
+                r#"See the <ulink url="https://www.freedesktop.org/wiki/">freedesktop wiki</ulink>."#
+            ),
+            "See the [freedesktop wiki](<https://www.freedesktop.org/wiki/>)."
+        );
+        // The attributes can come in any order and be single quoted
+        assert_eq!(
+            ulinks("<ulink type='http' url='https://gtk.org/#docs'>the\n  GTK site</ulink>"),
+            "[the GTK site](<https://gtk.org/#docs>)"
+        );
+        assert_eq!(
+            ulinks(r#"Read <ulink url="https://gtk.org"/> first"#),
+            "Read <https://gtk.org> first"
+        );
+        assert_eq!(
+            ulinks(r#"<ulink url="https://gtk.org"></ulink>"#),
+            "<https://gtk.org>"
+        );
+        let no_url = "<ulink>text</ulink>";
+        assert_eq!(ulinks(no_url), no_url);
+    }
+
+    #[test]
+    fn test_linkends() {
+        let resolve = |linkend: &str| match linkend {
+            "GtkWidget" => Some("[`Widget`][crate::Widget]".to_owned()),
+            "gtk-widget-show" => {
+                Some("[`WidgetExt::show()`][crate::prelude::WidgetExt::show()]".to_owned())
+            }
+            _ => None,
+        };
+        assert_eq!(
+            linkends(r#"A <link linkend="GtkWidget">widget</link>"#, resolve),
+            "A [widget][crate::Widget]"
+        );
+        assert_eq!(
+            linkends(r#"Call <link linkend='gtk-widget-show'/> then"#, resolve),
+            "Call [`WidgetExt::show()`][crate::prelude::WidgetExt::show()] then"
+        );
+        assert_eq!(
+            linkends(
+                r#"See <link endterm="x" linkend="gtk-migrating">the migration guide</link>"#,
+                resolve
+            ),
+            "See the migration guide"
+        );
+        assert_eq!(
+            linkends(r#"<link linkend="gtk-migrating"></link>"#, resolve),
+            "`gtk-migrating`"
+        );
+    }
+
+    #[test]
+    fn test_docbook() {
+        // From the height-for-width section of the gtk3 `GtkWidget` docs
+        assert_eq!(
+            docbook(
+                "Height-for-width geometry management is implemented by way of five virtual methods:
+
+<itemizedlist>
+<listitem>
+<para>#GtkWidgetClass.get_request_mode()</para>
+</listitem>
+<listitem>
+<para>#GtkWidgetClass.get_preferred_width()</para>
+</listitem>
+</itemizedlist>
+
+There are some important things to keep in mind when implementing height-for-width"
+            ),
+            "Height-for-width geometry management is implemented by way of five virtual methods:
+
+- #GtkWidgetClass.get_request_mode()
+- #GtkWidgetClass.get_preferred_width()
+
+There are some important things to keep in mind when implementing height-for-width"
+        );
+        // From the gtk3 `GtkContainer` docs
+        assert_eq!(
+            docbook(
+                "<para>The <literal>GtkContainer</literal> is the base class.</para>
+<para>Its children are <emphasis>not</emphasis> reordered.</para>"
+            ),
+            "The `GtkContainer` is the base class.
+
+Its children are <emphasis>not</emphasis> reordered."
+        );
+        assert_eq!(
+            docbook("Use <code>gtk_init()</code>\n"),
+            "Use `gtk_init()`\n"
+        );
+        let plain = "No <tag> to translate";
+        assert!(matches!(docbook(plain), Cow::Borrowed(s) if s == plain));
+    }
+
+    #[test]
+    fn test_key_combos() {
+        assert_eq!(
+            key_combos(
+                "Press <keycombo><keycap>Ctrl</keycap><keycap>C</keycap></keycombo> to copy"
+            ),
+            "Press `Ctrl`+`C` to copy"
+        );
+        assert_eq!(
+            key_combos(
+                "<keycombo>\n  <keycap>Ctrl</keycap>\n  <keycap>Shift</keycap>\n  <keycap>I</keycap>\n</keycombo> or <keycap>F12</keycap>"
+            ),
+            "`Ctrl`+`Shift`+`I` or `F12`"
+        );
+        assert_eq!(key_combos("No <b>keys</b>"), "No <b>keys</b>");
+    }
+
+    #[test]
+    fn test_inline_file_paths() {
+        assert_eq!(
+            inline_file_paths("Reads /etc/foo.conf, then ~/.config or (/usr/share/foo/)."),
+            "Reads `/etc/foo.conf`, then ~/.config or (`/usr/share/foo/`)."
+        );
+        assert_eq!(
+            inline_file_paths("/dev/null is\nalso \"/tmp/a-b\""),
+            "`/dev/null` is\nalso \"`/tmp/a-b`\""
+        );
+        for input in [
+            "See https://gtk.org/docs/foo.html",
+            "The `/etc/foo.conf` file",
+            "[the file](/etc/foo.conf)",
+            "Use a width/height ratio or the / operator",
+            "<file:///tmp/foo.txt>",
+        ] {
+            assert_eq!(inline_file_paths(input), input);
+        }
+    }
+
+    #[test]
+    fn test_doctest_setup() {
+        let setup = ["use gtk::prelude::*;".to_owned(), String::new()];
+        assert_eq!(
+            format_segments(
+                "Like:\n```rust,no_run\nlet b = Button::new();\n```\nor:\n```text\ngtk_button_new ();\n```",
+                &setup,
+                str::to_owned,
+                str::to_owned
+            ),
+            "Like:\n```rust,no_run\n# use gtk::prelude::*;\n#\nlet b = Button::new();\n```\nor:\n```text\ngtk_button_new ();\n```"
+        );
+    }
+
+    #[test]
+    fn test_inline_code() {
+        assert_eq!(inline_code("gtk_init()"), "`gtk_init()`");
+        assert_eq!(inline_code("a`b"), "``a`b``");
+        assert_eq!(inline_code("a``b`c"), "```a``b`c```");
+        assert_eq!(inline_code("`"), "`` ` ``");
+        assert_eq!(inline_code("`quoted`"), "`` `quoted` ``");
+    }
+
+    #[test]
+    fn test_separate_code_spans() {
+        assert_eq!(separate_code_spans("`A``B`"), "`A` `B`");
+        assert_eq!(
+            separate_code_spans("`A``B``C` and `D`"),
+            "`A` `B` `C` and `D`"
+        );
+        assert_eq!(
+            separate_code_spans("The `` `quoted` `` and ```a``b``` spans"),
+            "The `` `quoted` `` and ```a``b``` spans"
+        );
+        assert_eq!(separate_code_spans("Empty `` string"), "Empty `` string");
+    }
+
+    #[test]
+    fn test_embedded_backticks() {
+        let input = "The <literal>`</literal> key, <keycap>`</keycap> and <literal>a``b</literal><literal>c</literal>.\n```text\n`A``B`\n```\n`A``B`";
+        assert_eq!(
+            format_segments(
+                input,
+                &[],
+                |prose| separate_code_spans(&key_combos(&docbook(prose))).into_owned(),
+                str::to_owned
+            ),
+            "The `` ` `` key, `` ` `` and ```a``b``` `c`.\n```text\n`A``B`\n```\n`A` `B`"
+        );
+    }
+
+    #[test]
+    fn test_collapse_spaces() {
+        let input = "Fills the  struct, like:\n\n```c\nGtkBorder border = {\n  .left   = 1,  // left\n  .right  = 2,  // right\n};\n```\nwith `a  =  b`  aligned.";
+        assert_eq!(
+            format_segments(
+                input,
+                &[],
+                |prose| collapse_spaces(prose).into_owned(),
+                str::to_owned
+            ),
+            "Fills the struct, like:\n\n**⚠️ The following code is in c ⚠️**\n\n```c\nGtkBorder border = {\n  .left   = 1,  // left\n  .right  = 2,  // right\n};\n```\nwith `a  =  b` aligned."
+        );
+    }
+
+    #[test]
+    fn test_demote_lone_heading() {
+        assert_eq!(demote_lone_heading("# GtkButton"), "GtkButton");
+        assert_eq!(demote_lone_heading("\n## Buttons \n"), "Buttons");
+        for input in [
+            "# GtkButton\n\nA widget emitting a signal when clicked.",
+            "A widget emitting a signal when clicked.",
+            "#GtkButton",
+            "# ",
+        ] {
+            assert_eq!(demote_lone_heading(input), input);
+        }
+    }
+
+    #[test]
+    fn test_trim_trailing_whitespace() {
+        let out = trim_trailing_whitespace(
+            "Shows the [`Widget`][crate::Widget]. \nSee `show()`\t\n  \n```rust  \nlet a = 1;  \n```\nEnd.  ",
+        );
+        assert_eq!(
+            out,
+            "Shows the [`Widget`][crate::Widget].\nSee `show()`\n\n```rust\nlet a = 1;  \n```\nEnd."
+        );
+        let mut prose = out.split("```").step_by(2).flat_map(str::lines);
+        assert!(prose.clone().count() > 0);
+        assert!(prose.all(|line| line == line.trim_end()));
+    }
+
+    #[test]
+    fn test_join_wrapped_symbols() {
+        let is_type = |name: &str| ["GtkWidget", "GtkLabel"].contains(&name);
+        assert_eq!(
+            join_wrapped_symbols("Adds a #Gtk\nWidget to the box.", is_type),
+            "Adds a #GtkWidget to the box."
+        );
+        assert_eq!(
+            join_wrapped_symbols("A #Gtk\nLabel or (#Gtk\nWidget)", is_type),
+            "A #GtkLabel or (#GtkWidget)"
+        );
+        // Unknown names, paragraph breaks and indented lines are kept
+        for input in [
+            "Adds a #Gtk\nButton to the box.",
+            "Adds a #GtkWidget\nTo the box.",
+            "Adds a #Gtk\n\nWidget to the box.",
+            "Adds a #Gtk\n Widget to the box.",
+            "Adds a foo#Gtk\nWidget to the box.",
+        ] {
+            assert_eq!(join_wrapped_symbols(input, is_type), input);
+        }
+    }
+
+    #[test]
+    fn test_see_also_lists() {
+        assert_eq!(
+            see_also_lists(
+                "Shows the widget.\n\nSee [`show()`][Self::show()] and [`hide()`][Self::hide()] for details.\n\nOther paragraph."
+            ),
+            "Shows the widget.\n\nSee also:\n\n- [`show()`][Self::show()]\n- [`hide()`][Self::hide()]\n\nOther paragraph."
+        );
+        assert_eq!(
+            see_also_lists("See [`A`][crate::A], [`B`][crate::B], and [`C`][crate::C]."),
+            "See also:\n\n- [`A`][crate::A]\n- [`B`][crate::B]\n- [`C`][crate::C]"
+        );
+        // Sentences with other words or unresolved references are kept
+        for input in [
+            "See [`show()`][Self::show()] and `gtk_widget_hide()` for details.",
+            "See [`show()`][Self::show()] for details.",
+            "See [`A`][crate::A] and [`B`][crate::B].\nSame paragraph.",
+            "Also see [`A`][crate::A] and [`B`][crate::B].",
+        ] {
+            assert_eq!(see_also_lists(input), input);
+        }
+    }
+
+    #[test]
+    fn test_autolinks() {
+        let replace = |s: &str| s.replace('#', "!").replace('@', "?");
+        assert_eq!(
+            replace_outside_links("see <https://example.com/a#b> for #Foo", replace),
+            "see <https://example.com/a#b> for !Foo"
+        );
+        assert_eq!(
+            replace_outside_links("mail <gtk-list@gnome.org> about @self", replace),
+            "mail <gtk-list@gnome.org> about ?self"
+        );
+        assert_eq!(
+            replace_outside_links("<xi:include> #Foo", replace),
+            "<xi:include> !Foo"
+        );
+    }
+
+    #[test]
+    fn test_field_reference() {
+        let field = |name: &str, private| Field {
+            name: name.to_owned(),
+            private,
+            ..Default::default()
+        };
+        let fields = vec![
+            field("line", false),
+            field("type", false),
+            field("dummy1", true),
+        ];
+        let find_getter = |name: &str| {
+            ["line", "type_"]
+                .contains(&name)
+                .then(|| format!("[`{0}()`][crate::TextIter::{0}()]", name))
+        };
+        assert_eq!(
+            field_reference(&fields, "line", find_getter).as_deref(),
+            Some("[`line()`][crate::TextIter::line()]")
+        );
+        assert_eq!(
+            field_reference(&fields, "type", find_getter).as_deref(),
+            Some("[`type_()`][crate::TextIter::type_()]")
+        );
+        assert_eq!(
+            field_reference(&fields, "line", |_| None).as_deref(),
+            Some("`line`")
+        );
+        assert_eq!(field_reference(&fields, "dummy1", find_getter), None);
+        assert_eq!(field_reference(&fields, "offset", find_getter), None);
+    }
+
+    #[test]
+    fn test_existing_links() {
+        let replace = |s: &str| s.replace("#GtkWidget", "[`Widget`][crate::Widget]");
+        assert_eq!(
+            replace_outside_links(
+                "See https://docs.gtk.org/gtk4/class.Widget.html#GtkWidget for #GtkWidget",
+                replace
+            ),
+            "See https://docs.gtk.org/gtk4/class.Widget.html#GtkWidget for [`Widget`][crate::Widget]"
+        );
+        assert_eq!(
+            replace_outside_links(
+                "The [#GtkWidget docs](https://docs.gtk.org/gtk4/class.Widget.html) of #GtkWidget",
+                replace
+            ),
+            "The [#GtkWidget docs](https://docs.gtk.org/gtk4/class.Widget.html) of [`Widget`][crate::Widget]"
+        );
+        assert_eq!(
+            replace_outside_links(
+                "A [guide][1] about #GtkWidget\n\n[1]: https://gtk.org/#GtkWidget",
+                replace
+            ),
+            "A [guide][1] about [`Widget`][crate::Widget]\n\n[1]: https://gtk.org/#GtkWidget"
+        );
+        // The gi-docgen links aren't markdown links yet
+        assert_eq!(
+            replace_outside_links("[class@Gtk.Widget] or #GtkWidget", |s| s.replace('#', "!")),
+            "[class@Gtk.Widget] or !GtkWidget"
+        );
+    }
+
+    #[test]
+    fn test_numeric_entities_not_replaced() {
+        let replace = |s: &str| s.replace('#', "!");
+        assert_eq!(
+            replace_outside("&#35;Foo and &#x23;Foo but #Foo", &NUMERIC_ENTITY, replace),
+            "&#35;Foo and &#x23;Foo but !Foo"
+        );
+    }
+
+    #[test]
+    fn test_escape_markdown() {
+        for (input, output) in [
+            ("the *nix way", "the \\*nix way"),
+            (
+                "a char *pointer and *another",
+                "a char \\*pointer and \\*another",
+            ),
+            ("the file_name_t type", "the file_name_t type"),
+            (
+                "the _private and __init__ names",
+                "the \\_private and \\_\\_init\\_\\_ names",
+            ),
+            ("an [optional] value", "an \\[optional\\] value"),
+            ("a stray ` backtick", "a stray \\` backtick"),
+            // Generated by gir
+            ("Do *not* call it **twice**", "Do *not* call it **twice**"),
+            (
+                "See [`Widget`][crate::Widget] and [`true`]",
+                "See [`Widget`][crate::Widget] and [`true`]",
+            ),
+            (
+                "the [GTK site](https://gtk.org/a_b)",
+                "the [GTK site](https://gtk.org/a_b)",
+            ),
+            ("the `file_*` functions", "the `file_*` functions"),
+            ("<https://gtk.org/a_b>", "<https://gtk.org/a_b>"),
+            ("already \\_escaped", "already \\_escaped"),
+            ("Items:\n* first_\n- second", "Items:\n* first\\_\n- second"),
+        ] {
+            assert_eq!(escape_markdown(input), output);
+        }
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        for (input, output) in [
+            ("&num;GtkWidget", "#GtkWidget"),
+            ("100&percnt;", "100%"),
+            ("&commat;self", "@self"),
+            ("A &amp; B", "A & B"),
+            ("a &lt; b", "a < b"),
+            ("a &gt; b", "a > b"),
+            ("a&nbsp;b", "a\u{a0}b"),
+            ("&quot;quoted&quot;", "\"quoted\""),
+            ("it&apos;s", "it's"),
+            ("1&ndash;2", "1–2"),
+            ("A &mdash; B", "A — B"),
+            ("Wait&hellip;", "Wait…"),
+            ("&copy; GNOME", "© GNOME"),
+            ("&#35;GtkWidget", "#GtkWidget"),
+            ("&#x25;NULL", "%NULL"),
+            ("&#X40;self", "@self"),
+        ] {
+            assert_eq!(decode_entities(input), output);
+        }
+        // Would be taken for a tag or another entity
+        assert_eq!(decode_entities("&lt;child&gt;"), "&lt;child>");
+        assert_eq!(decode_entities("&lt;/child&gt;"), "&lt;/child>");
+        assert_eq!(decode_entities("&amp;lt;"), "&amp;lt;");
+        // Unknown or invalid
+        assert_eq!(decode_entities("&foo; &#xD800;"), "&foo; &#xD800;");
+        assert_eq!(decode_entities("A & B;"), "A & B;");
+    }
+
+    #[test]
+    fn test_nested_type() {
+        use crate::library::{Record, Union};
+
+        let mut library = Library::new("Gtk");
+        let nested_record = Type::record(
+            &mut library,
+            Record {
+                name: "Event_u1_s1".to_owned(),
+                c_type: "GtkEvent_u1_s1".to_owned(),
+                ..Default::default()
+            },
+            MAIN_NAMESPACE,
+        );
+        let nested_union = Type::union(
+            &mut library,
+            Union {
+                name: "Event_u1".to_owned(),
+                c_type: Some("GtkEvent_u1".to_owned()),
+                fields: vec![Field {
+                    name: "s1".to_owned(),
+                    typ: nested_record,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            MAIN_NAMESPACE,
+        );
+        let other = library.add_type(
+            MAIN_NAMESPACE,
+            "Other",
+            Type::Record(Record {
+                name: "Other".to_owned(),
+                c_type: "GtkOther".to_owned(),
+                ..Default::default()
+            }),
+        );
+        let fields = vec![
+            Field {
+                name: "u1".to_owned(),
+                typ: nested_union,
+                ..Default::default()
+            },
+            Field {
+                name: "other".to_owned(),
+                typ: other,
+                ..Default::default()
+            },
+        ];
+
+        // Top level types are found on their own
+        assert_eq!(
+            nested_c_types(&library, &fields),
+            ["GtkEvent_u1", "GtkEvent_u1_s1"]
+        );
+    }
+
+    #[test]
+    fn test_deprecated_member_doc() {
+        let link = "[`Align::Baseline`][crate::Align::Baseline]".to_owned();
+        assert_eq!(
+            gen_deprecated_member_doc(
+                "GTK_ALIGN_BASELINE",
+                link.clone(),
+                DeprecatedMemberLink::Variant
+            ),
+            link
+        );
+        assert_eq!(
+            gen_deprecated_member_doc("GTK_ALIGN_BASELINE", link, DeprecatedMemberLink::Note),
+            "`GTK_ALIGN_BASELINE` (deprecated)"
+        );
+    }
+
+    #[test]
+    fn test_flags_combination_prefix() {
+        let members = ["GTK_FOO_BAR_A", "GTK_FOO_BAR_B", "GTK_FOO_BAZ"];
+        assert_eq!(
+            flags_combination_prefix("GTK_FOO_DEFAULT", &members),
+            Some("GTK_FOO_")
+        );
+        assert_eq!(flags_combination_prefix("GTK_FOO_", &members), None);
+        assert_eq!(flags_combination_prefix("GDK_FOO_DEFAULT", &members), None);
+        assert_eq!(
+            flags_combination_prefix("GTK_FOO_BAR_ALL", &members[..2]),
+            Some("GTK_FOO_BAR_")
+        );
+        assert_eq!(flags_combination_prefix("GTK_FOO_ALL", &[]), None);
+    }
+
+    #[test]
+    fn test_combined_flags_constant() {
+        use crate::{
+            config::gobjects::GStatus,
+            library::{Bitfield, Constant, Stability},
+        };
+
+        let member = |name: &str| Member {
+            name: name.to_lowercase(),
+            c_identifier: format!("GTK_FOO_{}", name),
+            value: "1".to_owned(),
+            doc: None,
+            doc_deprecated: None,
+            status: GStatus::default(),
+            version: None,
+            deprecated_version: None,
+        };
+        let bitfield = |name: &str, members| {
+            Type::Bitfield(Bitfield {
+                name: name.to_owned(),
+                c_type: format!("Gtk{}", name),
+                symbol_prefix: None,
+                members,
+                functions: Vec::new(),
+                version: None,
+                deprecated_version: None,
+                stability: Stability::default(),
+                doc: None,
+                doc_deprecated: None,
+                glib_get_type: None,
+            })
+        };
+
+        let mut library = Library::new("Gtk");
+        let foo = library.add_type(
+            MAIN_NAMESPACE,
+            "FooFlags",
+            bitfield("FooFlags", vec![member("A"), member("B")]),
+        );
+        let other = library.add_type(
+            MAIN_NAMESPACE,
+            "OtherFlags",
+            bitfield("OtherFlags", vec![member("BAR_A"), member("BAR_B")]),
+        );
+        let flags = [foo, other];
+
+        assert_eq!(
+            find_combined_flags(&library, &flags, "GTK_FOO_DEFAULT"),
+            Some(foo)
+        );
+        assert_eq!(
+            find_combined_flags(&library, &flags, "GTK_FOO_BAR_ALL"),
+            Some(other)
+        );
+        assert_eq!(find_combined_flags(&library, &flags, "GTK_BAR_ALL"), None);
+
+        library.add_constant(
+            MAIN_NAMESPACE,
+            Constant {
+                name: "OTHER_DEFAULT".to_owned(),
+                c_identifier: "GTK_OTHER_DEFAULT".to_owned(),
+                typ: other,
+                c_type: "GtkOtherFlags".to_owned(),
+                value: "3".to_owned(),
+                version: None,
+                deprecated_version: None,
+                doc: None,
+                doc_deprecated: None,
+            },
+        );
+        assert_eq!(
+            find_combined_flags(&library, &flags, "GTK_OTHER_DEFAULT"),
+            Some(other)
+        );
+        assert_eq!(
+            find_combined_flags(&library, &flags[..1], "GTK_OTHER_DEFAULT"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_static_type_doc_link() {
+        assert_eq!(
+            gen_static_type_doc_link("Widget"),
+            "[`Widget::static_type`][crate::Widget::static_type]"
+        );
+        assert_eq!(
+            gen_static_type_doc_link("gdk::Paintable"),
+            "[`gdk::Paintable::static_type`][crate::gdk::Paintable::static_type]"
+        );
+    }
+
+    #[test]
+    fn test_error_domain() {
+        use crate::library::{Enumeration, Stability};
+
+        let enumeration = |name: &str, error_domain: Option<&str>| {
+            Type::Enumeration(Enumeration {
+                name: name.to_owned(),
+                c_type: format!("Gtk{}", name),
+                symbol_prefix: None,
+                members: Vec::new(),
+                functions: Vec::new(),
+                version: None,
+                deprecated_version: None,
+                stability: Stability::default(),
+                doc: None,
+                doc_deprecated: None,
+                error_domain: error_domain.map(|q| ErrorDomain::Quark(q.to_owned())),
+                glib_get_type: None,
+            })
+        };
+
+        let mut library = Library::new("Gtk");
+        let align = library.add_type(MAIN_NAMESPACE, "Align", enumeration("Align", None));
+        let builder_error = library.add_type(
+            MAIN_NAMESPACE,
+            "BuilderError",
+            enumeration("BuilderError", Some("gtk-builder-error-quark")),
+        );
+
+        let type_ids = [align, builder_error];
+        assert_eq!(
+            find_error_domain(&library, type_ids, "gtk_builder_error_quark"),
+            Some(builder_error)
+        );
+        assert_eq!(
+            find_error_domain(&library, type_ids, "gtk_css_parser_error_quark"),
+            None
+        );
+        assert_eq!(
+            find_error_domain(&library, type_ids, "gtk_builder_error_get_type"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_c_type_index() {
+        let types: Vec<(String, TypeId)> = (0..2000)
+            .map(|id| (format!("GtkType{}", id % 1500), TypeId { ns_id: 1, id }))
+            .collect();
+        let index = c_type_index(types.iter().map(|(c_type, tid)| (c_type.as_str(), *tid)));
+        let linear = |c_type: &str| types.iter().find(|(t, _)| t == c_type).map(|&(_, tid)| tid);
+
+        let doc = (0..3000)
+            .map(|id| format!("#GtkType{}", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+        for reference in doc.split(' ') {
+            let c_type = &reference[1..];
+            assert_eq!(index.get(c_type).copied(), linear(c_type), "{}", c_type);
+        }
+        assert_eq!(index.len(), 1500);
+        // The first type of a C type is kept, like the linear lookup
+        assert_eq!(index["GtkType10"], TypeId { ns_id: 1, id: 10 });
+    }
+
+    #[test]
+    fn test_type_index_links() {
+        use crate::{config::gobjects::GObject, library::Class};
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let type_ids = (0..300)
+            .map(|id| {
+                let name = format!("Type{}", id);
+                let class = Class {
+                    name: name.clone(),
+                    c_type: format!("Gtk{}", name),
+                    ..Default::default()
+                };
+                library.add_type(MAIN_NAMESPACE, &name, Type::Class(class))
+            })
+            .collect::<Vec<_>>();
+        let mut env = test_env(library, DocConfig::default());
+        for (id, &type_id) in type_ids.iter().enumerate() {
+            let info = object_info(type_id, &format!("GtkType{}", id), false, &[]);
+            // The ignored types are left out of the index
+            let status = if id == 7 {
+                GStatus::Ignore
+            } else {
+                GStatus::Generate
+            };
+            let object = GObject {
+                name: info.full_name.clone(),
+                status,
+                ..Default::default()
+            };
+            env.config.objects.insert(info.full_name.clone(), object);
+            env.analysis.objects.insert(info.full_name.clone(), info);
+        }
+        // The scan of the analyzed objects the index replaces
+        let linear = |c_type: &str| {
+            env.analysis
+                .objects
+                .values()
+                .find(|o| o.c_type == c_type)
+                .filter(|o| !env.config.objects[&o.full_name].status.ignored())
+                .map_or_else(
+                    || format!("`{}`", c_type),
+                    |o| format!("[`{0}`][crate::{0}]", o.name),
+                )
+        };
+
+        let c_types = (0..400)
+            .map(|id| format!("GtkType{}", id))
+            .collect::<Vec<_>>();
+        let doc = c_types
+            .iter()
+            .map(|c_type| format!("#{}", c_type))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let expected = c_types
+            .iter()
+            .map(|c_type| linear(c_type))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(reformat_doc(&doc, &env, None), expected);
+        assert!(expected.starts_with("[`Type0`][crate::Type0] "));
+        assert!(expected.contains(" `GtkType7` "));
+        assert!(expected.ends_with(" `GtkType399`"));
+    }
+
+    #[test]
+    fn test_ascii_only() {
+        assert_eq!(
+            ascii_only(
+                "The “default” widget’s size — see Café…\n\n```\nlet s = \"é\";\n```\nA → B"
+            ),
+            "The \"default\" widget's size - see Cafe...\n\n```\nlet s = \"é\";\n```\nA &#x2192; B"
+        );
+        assert_eq!(ascii_only("Plain text"), "Plain text");
+    }
+
+    #[test]
+    fn test_interface_signal_owner() {
+        use crate::analysis::{general::StatusedTypeId, object, signals};
+
+        let signal = |name: &str| signals::Info {
+            connect_name: format!("connect_{}", name),
+            signal_name: name.to_owned(),
+            action_emit_name: None,
+            trampoline: Err(Vec::new()),
+            version: None,
+            deprecated_version: None,
+            doc_hidden: false,
+            is_detailed: false,
+        };
+        let editable_id = TypeId { ns_id: 1, id: 1 };
+        let entry_id = TypeId { ns_id: 1, id: 2 };
+        let mut objects = BTreeMap::new();
+        objects.insert(
+            "Gtk.Editable".to_owned(),
+            object::Info {
+                base: crate::analysis::info_base::InfoBase {
+                    type_id: editable_id,
+                    ..Default::default()
+                },
+                is_interface: true,
+                signals: vec![signal("changed")],
+                ..Default::default()
+            },
+        );
+        let entry = object::Info {
+            base: crate::analysis::info_base::InfoBase {
+                type_id: entry_id,
+                ..Default::default()
+            },
+            supertypes: vec![StatusedTypeId {
+                type_id: editable_id,
+                name: "Editable".to_owned(),
+                status: crate::config::gobjects::GStatus::Generate,
+            }],
+            signals: vec![signal("activate")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            signal_owner(&entry, "changed", &objects).type_id,
+            editable_id
+        );
+        assert_eq!(signal_owner(&entry, "activate", &objects).type_id, entry_id);
+        assert_eq!(signal_owner(&entry, "unknown", &objects).type_id, entry_id);
+    }
+
+    #[test]
+    fn test_inherited_method_owner() {
+        use crate::analysis::{general::StatusedTypeId, info_base::InfoBase, object};
+
+        let object =
+            |id: u32, name: &str, is_interface: bool, supertypes: &[(u32, &str)]| object::Info {
+                base: InfoBase {
+                    type_id: TypeId { ns_id: 1, id },
+                    name: name.to_owned(),
+                    ..Default::default()
+                },
+                is_interface,
+                supertypes: supertypes
+                    .iter()
+                    .map(|(id, name)| StatusedTypeId {
+                        type_id: TypeId { ns_id: 1, id: *id },
+                        name: (*name).to_owned(),
+                        status: crate::config::gobjects::GStatus::Generate,
+                    })
+                    .collect(),
+                ..Default::default()
+            };
+        let objects: BTreeMap<_, _> = [
+            object(1, "Widget", false, &[(3, "Buildable")]),
+            object(3, "Buildable", true, &[]),
+        ]
+        .into_iter()
+        .map(|o| (o.name.clone(), o))
+        .collect();
+        let button = object(2, "Button", false, &[(1, "Widget"), (3, "Buildable")]);
+        // The methods each type declares
+        let methods = |o: &object::Info| match &*o.name {
+            "Button" => &["clicked"][..],
+            "Widget" => &["show", "get_name"][..],
+            "Buildable" => &["get_buildable_id", "get_name"][..],
+            _ => &[][..],
+        };
+        let owner = |name: &str| {
+            inherited_owner(&button, &objects, |o| methods(o).contains(&name)).map(|o| &*o.name)
+        };
+
+        assert_eq!(owner("clicked"), Some("Button"));
+        // `#GtkButton.show()` links to the parent class method
+        assert_eq!(owner("show"), Some("Widget"));
+        // and `#GtkButton.get_buildable_id()` to the interface one
+        assert_eq!(owner("get_buildable_id"), Some("Buildable"));
+        // The closest one wins
+        assert_eq!(owner("get_name"), Some("Widget"));
+        assert_eq!(owner("unknown"), None);
+    }
+
+    #[test]
+    fn test_parse_member_ref() {
+        assert_eq!(parse_member_ref("::clicked"), MemberRef::Signal("clicked"));
+        assert_eq!(
+            parse_member_ref("::notify-event"),
+            MemberRef::Signal("notify-event")
+        );
+        assert_eq!(parse_member_ref(":label"), MemberRef::Property("label"));
+        assert_eq!(
+            parse_member_ref(":use-underline"),
+            MemberRef::Property("use-underline")
+        );
+        assert_eq!(
+            parse_member_ref(".set_label"),
+            MemberRef::Method("set_label")
+        );
+    }
+
+    #[test]
+    fn test_is_generated_status() {
+        // The ignored types aren't linked, nor the ones of the main namespace left out of
+        // the config
+        assert!(!is_generated_status(Some(GStatus::Ignore), true));
+        assert!(!is_generated_status(None, true));
+        assert!(!is_generated_status(Some(GStatus::Ignore), false));
+        assert!(is_generated_status(Some(GStatus::Generate), true));
+        assert!(is_generated_status(Some(GStatus::Manual), true));
+        // The other namespaces are generated by their own crate
+        assert!(is_generated_status(None, false));
+    }
+
+    #[test]
+    fn test_item_version_links() {
+        use crate::{config::gobjects::GObject, library::Class};
+
+        // The doc of a global function, so without a type
+        let format_doc = |min_cfg_version: Version, item_version: Option<Version>| {
+            let mut library = Library::new("Gtk");
+            library.add_namespace("GLib");
+            let infos = [("Button", Version(4, 10, 0)), ("Widget", Version(4, 12, 0))]
+                .iter()
+                .map(|&(name, version)| {
+                    let c_type = format!("Gtk{}", name);
+                    let class = Class {
+                        name: name.to_owned(),
+                        c_type: c_type.clone(),
+                        version: Some(version),
+                        ..Default::default()
+                    };
+                    let type_id = library.add_type(MAIN_NAMESPACE, name, Type::Class(class));
+                    object_info(type_id, &c_type, false, &[])
+                })
+                .collect::<Vec<_>>();
+            let doc = DocConfig {
+                version_aware_links: true,
+                ..Default::default()
+            };
+            let mut env = test_env(library, doc);
+            env.config.min_cfg_version = min_cfg_version;
+            for info in infos {
+                let object = GObject {
+                    name: info.full_name.clone(),
+                    status: GStatus::Generate,
+                    ..Default::default()
+                };
+                env.config.objects.insert(info.full_name.clone(), object);
+                env.analysis.objects.insert(info.full_name.clone(), info);
+            }
+            let symbols = env.symbols.borrow();
+            let ctx = DocContext::new(&env, &symbols);
+            let _item = ctx.enter_item(item_version);
+            reformat_doc_with("See #GtkButton and #GtkWidget.", &ctx, None).doc
+        };
+
+        let v4_0 = Version(4, 0, 0);
+        let v4_10 = Version(4, 10, 0);
+        let v4_12 = Version(4, 12, 0);
+        // Only the types that aren't newer than the function are linked
+        assert_eq!(
+            format_doc(v4_0, Some(v4_10)),
+            "See [`Button`][crate::Button] and `GtkWidget`."
+        );
+        assert_eq!(
+            format_doc(v4_12, Some(v4_10)),
+            "See [`Button`][crate::Button] and [`Widget`][crate::Widget]."
+        );
+        assert_eq!(format_doc(v4_0, None), "See `GtkButton` and `GtkWidget`.");
+    }
+
+    #[test]
+    fn test_ungenerated_type_logged_once() {
+        use crate::library::Class;
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let widget = library.add_type(
+            MAIN_NAMESPACE,
+            "Widget",
+            Type::Class(Class {
+                name: "Widget".to_owned(),
+                c_type: "GtkWidget".to_owned(),
+                ..Default::default()
+            }),
+        );
+        let env = test_env(library, DocConfig::default());
+        let symbols = env.symbols.borrow();
+        let ctx = DocContext::new(&env, &symbols);
+
+        // Left out of the config, so only its first reference is logged
+        assert!(!is_generated_type(&ctx, widget));
+        assert!(!is_generated_type(&ctx, widget));
+        assert_eq!(*ctx.logged_ungenerated.borrow(), HashSet::from([widget]));
+    }
+
+    #[test]
+    fn test_vfunc_owner() {
+        use crate::analysis::{info_base::InfoBase, object};
+
+        let object = |name: &str, class_struct: &str, is_interface: bool| object::Info {
+            base: InfoBase {
+                name: name.to_owned(),
+                ..Default::default()
+            },
+            c_class_type: Some(class_struct.to_owned()),
+            is_interface,
+            generate_trait: true,
+            ..Default::default()
+        };
+        let objects: BTreeMap<_, _> = [
+            object("Widget", "GtkWidgetClass", false),
+            object("Editable", "GtkEditableInterface", true),
+        ]
+        .into_iter()
+        .map(|o| (o.name.clone(), o))
+        .collect();
+
+        // `#GtkEditableInterface.get_text` links to `EditableExt::text()`
+        assert_eq!(parse_member_ref(".get_text"), MemberRef::Method("get_text"));
+        let editable = vfunc_owner(&objects, "GtkEditableInterface").unwrap();
+        assert_eq!(editable.name, "Editable");
+        assert!(gi_docgen::provides_vfunc_methods(editable, &editable.name));
+        // `#GtkWidgetClass.snapshot` has no generated method
+        let widget = vfunc_owner(&objects, "GtkWidgetClass").unwrap();
+        assert_eq!(widget.name, "Widget");
+        assert!(!gi_docgen::provides_vfunc_methods(widget, &widget.name));
+        assert_eq!(
+            gen_vfunc_doc_link(&widget.name, "snapshot"),
+            "`vfunc::Widget::snapshot`"
+        );
+        assert!(vfunc_owner(&objects, "GtkWidget").is_none());
+    }
+
+    #[test]
+    fn test_generated_signal() {
+        use crate::analysis::{object, signals};
+
+        let button = object::Info {
+            signals: vec![signals::Info {
+                connect_name: "connect_clicked".to_owned(),
+                signal_name: "clicked".to_owned(),
+                action_emit_name: None,
+                trampoline: Err(Vec::new()),
+                version: None,
+                deprecated_version: None,
+                doc_hidden: false,
+                is_detailed: false,
+            }],
+            ..Default::default()
+        };
+        // Its trampoline couldn't be generated, so neither was the `connect_` method
+        assert!(generated_signal(&button, "clicked").is_none());
+        assert!(generated_signal(&button, "unknown").is_none());
+
+        assert_eq!(
+            gen_accessor_doc_link("prelude::ButtonExt", "ButtonExt", "connect_clicked", false),
+            "[`ButtonExt::connect_clicked()`][crate::prelude::ButtonExt::connect_clicked()]"
+        );
+        assert_eq!(
+            gen_accessor_doc_link("Button", "Button", "connect_clicked", true),
+            "[`connect_clicked()`][Self::connect_clicked()]"
+        );
+    }
+
+    #[test]
+    fn test_namespace_prefixes() {
+        let prefixes = ["Gtk", "Gdk", "Adw", "cairo"];
+        for name in ["GtkWidget", "GdkPixbuf", "AdwLeaflet", "cairo_t"] {
+            assert!(has_namespace_prefix(name, &prefixes), "{}", name);
+        }
+        for name in [
+            "Gtk",
+            "Gtkwidget",
+            "GstElement",
+            "Widget",
+            "cairo",
+            "cairo_",
+            "Adwaita",
+        ] {
+            assert!(!has_namespace_prefix(name, &prefixes), "{}", name);
+        }
+
+        let mut library = Library::new("Adw");
+        library.namespace_mut(MAIN_NAMESPACE).identifier_prefixes = vec!["Adw".to_owned()];
+        assert_eq!(namespace_prefixes(&library), ["Adw"]);
+    }
+
+    #[test]
+    fn test_gdk_gtk_trailing_dot() {
+        let replace = |input: &str, link: bool| {
+            GDK_GTK
+                .replace_all(input, |caps: &Captures<'_>| {
+                    replace_gdk_gtk(caps, |type_| {
+                        (link && type_ == "GtkWidget")
+                            .then(|| "[`Widget`][crate::Widget]".to_owned())
+                    })
+                })
+                .into_owned()
+        };
+        assert_eq!(
+            replace("Adds a `GtkWidget.` to the box", true),
+            "Adds a [`Widget`][crate::Widget]. to the box"
+        );
+        assert_eq!(
+            replace("Adds a `GtkWidget`", true),
+            "Adds a [`Widget`][crate::Widget]"
+        );
+        assert_eq!(
+            replace("Adds a `GtkWidget.` to the box", false),
+            "Adds a `GtkWidget`. to the box"
+        );
+        assert_eq!(replace("Uses `GtkLabel.`", true), "Uses `GtkLabel`.");
+        assert_eq!(
+            replace("A `GtkWidget,` a `GtkLabel;` or a `GtkWidget:`", true),
+            "A [`Widget`][crate::Widget], a `GtkLabel`; or a [`Widget`][crate::Widget]:"
+        );
+    }
+
+    #[test]
+    fn test_trailing_punctuation() {
+        // The sentence punctuation following a reference is never part of it
+        for punctuation in [".", ",", ";"] {
+            for reference in [
+                "#GtkWidget",
+                "%TRUE",
+                "@widget",
+                "#GtkWidget:label",
+                "#GtkWidget::destroy",
+            ] {
+                let input = format!("Uses {}{} then", reference, punctuation);
+                assert_eq!(&SYMBOL.captures(&input).unwrap()[0], reference);
+            }
+            for reference in ["%GTK_ALIGN_START", "%GtkAlign.START"] {
+                let input = format!("Uses {}{} then", reference, punctuation);
+                assert_eq!(&GI_DOCGEN_SYMBOL.captures(&input).unwrap()[0], reference);
+            }
+            for reference in [
+                "gtk_widget_show()",
+                "#GtkWidget.show()",
+                "GtkWidget::show()",
+            ] {
+                let input = format!("Uses {}{} then", reference, punctuation);
+                assert_eq!(&FUNCTION.captures(&input).unwrap()[0], reference);
+            }
+            let input = format!("Uses `GtkWidget{}` then", punctuation);
+            assert_eq!(&GDK_GTK.captures(&input).unwrap()[3], punctuation);
+        }
+    }
+
+    #[test]
+    fn test_c_literals() {
+        let is_literal = |input: &str| {
+            let start = input.rfind(|c| c == '%' || c == '#').unwrap();
+            let name_len = input[start + 1..]
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(input.len() - start - 1);
+            is_c_literal_at(input, start, &input[start + 1..start + 1 + name_len])
+        };
+        assert!(is_literal("Formats it with %d and more"));
+        assert!(is_literal("Like \"%s: %s\""));
+        assert!(is_literal("In hexadecimal %02x"));
+        assert!(is_literal("A literal %%d"));
+        assert!(is_literal("Uses 100%CPU"));
+        assert!(is_literal("#ifdef GTK_DISABLE_DEPRECATED"));
+        assert!(is_literal("Wrap it in #ifdef like"));
+        assert!(is_literal("With a #define"));
+        // References
+        assert!(!is_literal("Returns %TRUE"));
+        assert!(!is_literal("Returns %GTK_ALIGN_START"));
+        assert!(!is_literal("A #GtkWidget"));
+        assert!(!is_literal("The #GtkWidget:visible property"));
+        // `100%` alone isn't even a `SYMBOL` match
+        assert!(SYMBOL.find("Up to 100% of the width").is_none());
+        assert!(SYMBOL.find("Up to 100%.").is_none());
+    }
+
+    #[test]
+    fn test_group_examples() {
+        let input = "Shows a button.
+
+For example:
+
+```rust
+let button = Button::new();
+button.show();
+```
+
+Then `x` is set, as in:
+
+```text
+x = 1
+```
+
+More prose.
+```c
+gtk_widget_show (button);
+gtk_widget_hide (button);
+```
+";
+        // The blocks of several lines move, the one-liner stays in the prose
+        assert_eq!(
+            group_examples(input, "Examples"),
+            "Shows a button.
+
+Then `x` is set, as in:
+
+```text
+x = 1
+```
+
+More prose.
+
+# Examples
+
+For example:
+
+```rust
+let button = Button::new();
+button.show();
+```
+
+```c
+gtk_widget_show (button);
+gtk_widget_hide (button);
+```
+"
+        );
+        // Without such blocks or with an existing section the doc is kept
+        let inline = "Like:\n\n```rust\nlet a = 1;\n```\n";
+        assert_eq!(group_examples(inline, "Examples"), inline);
+        let grouped = "Text\n\n# Examples\n\n```rust\nlet a = 1;\nlet b = 2;\n```";
+        assert_eq!(group_examples(grouped, "Examples"), grouped);
+        assert_eq!(
+            group_examples("```rust\nlet a = 1;\nlet b = 2;\n```", "Exemples"),
+            "# Exemples\n\n```rust\nlet a = 1;\nlet b = 2;\n```"
+        );
+    }
+
+    #[test]
+    fn test_strip_type_decorators() {
+        assert_eq!(
+            strip_type_decorators("Takes a #GtkWidget* and more"),
+            "Takes a #GtkWidget and more"
+        );
+        assert_eq!(
+            strip_type_decorators("Fills a #GtkWidget **, or #GtkWidget**."),
+            "Fills a #GtkWidget, or #GtkWidget."
+        );
+        assert_eq!(
+            strip_type_decorators("Returns a #guint8[] of #guint8[16]"),
+            "Returns a #guint8 of #guint8"
+        );
+        assert_eq!(strip_type_decorators("#GtkWidget*"), "#GtkWidget");
+        // Emphasis
+        assert_eq!(
+            strip_type_decorators("A *#GtkWidget* or #GtkWidget *not* a pointer"),
+            "A *#GtkWidget* or #GtkWidget *not* a pointer"
+        );
+        assert_eq!(strip_type_decorators("a*b #c"), "a*b #c");
+    }
+
+    #[test]
+    fn test_builder_doc_link() {
+        // `#GtkButtonBuilder` when `GtkButton` has builder properties
+        assert_eq!(
+            gen_builder_doc_link("Button"),
+            "[`ButtonBuilder`][crate::builders::ButtonBuilder]"
+        );
+    }
+
+    #[test]
+    fn test_crate_path() {
+        assert_eq!(crate_path("Button", false), "crate::Button");
+        // In a module of the crate
+        assert_eq!(
+            crate_path("prelude::WidgetExt", false),
+            "crate::prelude::WidgetExt"
+        );
+        // In the crate of another namespace
+        assert_eq!(crate_path("gdk::Rectangle", false), "crate::gdk::Rectangle");
+        // The gobject types re-exported by glib are at its root
+        assert_eq!(crate_path("glib::Object", false), "crate::glib::Object");
+        assert_eq!(crate_path("glib::Object", true), "crate::Object");
+        assert_eq!(
+            crate_path("glib::prelude::ObjectExt", true),
+            "crate::prelude::ObjectExt"
+        );
+        assert_eq!(crate_relative_path("gio::File", true), "gio::File");
+    }
+
+    #[test]
+    fn test_symbol_links() {
+        // Like `G_LOG_DOMAIN = "glib::LOG_DOMAIN"` in `[doc.symbol_links]`
+        assert_eq!(
+            gen_core_type_doc_link("glib::LOG_DOMAIN", false),
+            "[`glib::LOG_DOMAIN`][crate::glib::LOG_DOMAIN]"
+        );
+        assert_eq!(
+            gen_core_type_doc_link("glib::LOG_DOMAIN", true),
+            "[`LOG_DOMAIN`][crate::LOG_DOMAIN]"
+        );
+    }
+
+    #[test]
+    fn test_link_overrides() {
+        let link_overrides = [
+            (
+                "g_foo_bar".to_owned(),
+                DocLinkOverride {
+                    rust_link: "Foo::bar".to_owned(),
+                    force: false,
+                },
+            ),
+            (
+                "gtk_widget_show".to_owned(),
+                DocLinkOverride {
+                    rust_link: "prelude::WidgetExtManual::show".to_owned(),
+                    force: true,
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+        // Like `find_type` and the function references, with `found` as the automatic link
+        let link = |name: &str, found: Option<&'static str>| {
+            link_override(name, &link_overrides, true)
+                .or(found)
+                .or_else(|| link_override(name, &link_overrides, false))
+                .map(|path| gen_core_function_doc_link(path, false))
+        };
+        // Fills a gap
+        assert_eq!(
+            link("g_foo_bar", None).as_deref(),
+            Some("[`Foo::bar()`][crate::Foo::bar()]")
+        );
+        assert_eq!(
+            link("g_foo_bar", Some("Foo::found")).as_deref(),
+            Some("[`Foo::found()`][crate::Foo::found()]")
+        );
+        // Replaces the automatic link
+        assert_eq!(
+            link("gtk_widget_show", Some("Widget::show")).as_deref(),
+            Some("[`WidgetExtManual::show()`][crate::prelude::WidgetExtManual::show()]")
+        );
+        assert_eq!(link("g_object_ref", None), None);
+    }
+
+    #[test]
+    fn test_signal_functions() {
+        let function_links = [(
+            "g_signal_emit".to_owned(),
+            "glib::prelude::ObjectExtManual::emit".to_owned(),
+        )]
+        .into_iter()
+        .collect();
+        let link = |name: &str, is_glib_crate: bool| {
+            function_link(name, &function_links)
+                .map(|path| gen_core_function_doc_link(path, is_glib_crate))
+        };
+        assert_eq!(
+            link("g_signal_emit_by_name", false).as_deref(),
+            Some("[`ObjectExt::emit_by_name()`][crate::glib::prelude::ObjectExt::emit_by_name()]")
+        );
+        assert_eq!(
+            link("g_signal_handler_disconnect", true).as_deref(),
+            Some("[`ObjectExt::disconnect()`][crate::prelude::ObjectExt::disconnect()]")
+        );
+        // The configured links take precedence
+        assert_eq!(
+            link("g_signal_emit", false).as_deref(),
+            Some("[`ObjectExtManual::emit()`][crate::glib::prelude::ObjectExtManual::emit()]")
+        );
+        assert_eq!(link("g_object_ref", false), None);
+    }
+
+    #[test]
+    fn test_removed_note() {
+        let removed_items = [(
+            "gtk_widget_set_double_buffered".to_owned(),
+            Version(4, 0, 0),
+        )]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            with_removed_note(
+                "`gtk_widget_set_double_buffered()`",
+                "gtk_widget_set_double_buffered",
+                &removed_items
+            )
+            .as_deref(),
+            Some("`gtk_widget_set_double_buffered()` (removed in 4.0)")
+        );
+        assert_eq!(
+            with_removed_note("`gtk_widget_show()`", "gtk_widget_show", &removed_items),
+            None
+        );
+    }
+
+    #[test]
+    fn test_union() {
+        use crate::library::Union;
+
+        let mut library = Library::new("Gdk");
+        library.add_type(
+            MAIN_NAMESPACE,
+            "Event",
+            Type::Union(Union {
+                name: "Event".to_owned(),
+                c_type: Some("GdkEvent".to_owned()),
+                ..Default::default()
+            }),
+        );
+        Type::union(
+            &mut library,
+            Union {
+                name: "Event_u1".to_owned(),
+                c_type: Some("GdkEvent_u1".to_owned()),
+                ..Default::default()
+            },
+            MAIN_NAMESPACE,
+        );
+
+        // Nested unions link to their parent instead
+        assert_eq!(unions(&library), HashSet::from(["GdkEvent"]));
+        assert_eq!(
+            gen_sys_type_doc_link("ffi", "GdkEvent", false),
+            "[`ffi::GdkEvent`][crate::ffi::GdkEvent]"
+        );
+    }
+
+    #[test]
+    fn test_callback_pointers() {
+        let find = |name: &str| {
+            (name == "GtkTickCallback").then(|| gen_sys_type_doc_link("ffi", name, false))
+        };
+        assert_eq!(
+            replace_callback_pointers("Takes a #GtkTickCallback* or #GtkTickCallback *.", find),
+            "Takes a [`ffi::GtkTickCallback`][crate::ffi::GtkTickCallback] or \
+             [`ffi::GtkTickCallback`][crate::ffi::GtkTickCallback]."
+        );
+        // Other pointers are left to the other passes
+        assert_eq!(
+            replace_callback_pointers("A #GtkWidget* or a #GtkTickCallback", find),
+            "A #GtkWidget* or a #GtkTickCallback"
+        );
+    }
+
+    #[test]
+    fn test_alias_target() {
+        use crate::library::{Alias, Record};
+
+        let mut library = Library::new("Gtk");
+        let border = library.add_type(
+            MAIN_NAMESPACE,
+            "Border",
+            Type::Record(Record {
+                name: "Border".to_owned(),
+                c_type: "GtkBorder".to_owned(),
+                ..Default::default()
+            }),
+        );
+        library.add_type(
+            MAIN_NAMESPACE,
+            "Padding",
+            Type::Alias(Alias {
+                name: "Padding".to_owned(),
+                c_identifier: "GtkPadding".to_owned(),
+                typ: border,
+                target_c_type: "GtkBorder".to_owned(),
+                doc: None,
+                doc_deprecated: None,
+            }),
+        );
+
+        assert_eq!(
+            alias_targets(&library),
+            HashMap::from([("GtkPadding", border)])
+        );
+    }
+
+    #[test]
+    fn test_glib_type_name() {
+        use crate::library::Class;
+
+        let mut library = Library::new("Gtk");
+        let expression = library.add_type(
+            MAIN_NAMESPACE,
+            "Expression",
+            Type::Class(Class {
+                name: "Expression".to_owned(),
+                c_type: "GtkExpression".to_owned(),
+                glib_type_name: Some("GtkExpressionType".to_owned()),
+                is_fundamental: true,
+                ..Default::default()
+            }),
+        );
+        let widget = library.add_type(
+            MAIN_NAMESPACE,
+            "Widget",
+            Type::Class(Class {
+                name: "Widget".to_owned(),
+                c_type: "GtkWidget".to_owned(),
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(
+            glib_type_names(&library, [widget, expression]),
+            HashMap::from([("GtkExpressionType", expression)])
+        );
+        assert!(glib_type_names(&library, [widget]).is_empty());
+    }
+
+    #[test]
+    fn test_external_type() {
+        use crate::{
+            analysis::{namespaces, symbols},
+            library::Class,
+        };
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let ns_id = library.add_namespace("GdkPixbuf");
+        let pixbuf = library.add_type(
+            ns_id,
+            "Pixbuf",
+            Type::Class(Class {
+                name: "Pixbuf".to_owned(),
+                c_type: "GdkPixbuf".to_owned(),
+                ..Default::default()
+            }),
+        );
+        let widget = library.add_type(
+            MAIN_NAMESPACE,
+            "Widget",
+            Type::Class(Class {
+                name: "Widget".to_owned(),
+                c_type: "GtkWidget".to_owned(),
+                ..Default::default()
+            }),
+        );
+
+        // Types of the main namespace are found through the analysis
+        assert_eq!(
+            external_types(&library),
+            HashMap::from([("GdkPixbuf", pixbuf)])
+        );
+
+        let symbols = symbols::run(&library, &namespaces::run(&library));
+        assert_eq!(
+            symbols.by_tid(pixbuf).unwrap().full_rust_name(),
+            "gdk_pixbuf::Pixbuf"
+        );
+        assert_eq!(symbols.by_tid(widget).unwrap().full_rust_name(), "Widget");
+    }
+
+    #[test]
+    fn test_is_dependency() {
+        let mut library = Library::new("Gtk");
+        let glib = library.add_namespace("GLib");
+        let gobject = library.add_namespace("GObject");
+        let gdk = library.add_namespace("Gdk");
+        let harfbuzz = library.add_namespace("HarfBuzz");
+        let namespaces = namespaces::run(&library);
+        let external_libraries = [ExternalLibrary {
+            namespace: "Gdk".to_owned(),
+            crate_name: "gdk".to_owned(),
+            min_version: None,
+        }];
+
+        assert!(is_dependency(&namespaces, &external_libraries, glib));
+        assert!(is_dependency(&namespaces, &external_libraries, gobject));
+        assert!(is_dependency(&namespaces, &external_libraries, gdk));
+        // Only included by the GIR files, so not a crate the docs can link to
+        assert!(!is_dependency(&namespaces, &external_libraries, harfbuzz));
+        assert!(!is_dependency(&namespaces, &[], gdk));
+    }
+
+    #[test]
+    fn test_core_types() {
+        assert_eq!(
+            glib_core_type_path("GMainContext"),
+            Some("glib::MainContext")
+        );
+        assert_eq!(glib_core_type_path("GBytes"), Some("glib::Bytes"));
+        assert_eq!(glib_core_type_path("GtkSnapshot"), None);
+
+        assert_eq!(
+            gen_core_type_doc_link("glib::MainContext", false),
+            "[`glib::MainContext`][crate::glib::MainContext]"
+        );
+        assert_eq!(
+            gen_core_type_doc_link("glib::MainContext", true),
+            "[`MainContext`][crate::MainContext]"
+        );
+    }
+
+    #[test]
+    fn test_c_type_name() {
+        assert_eq!(c_type_name("GtkWidget*"), "GtkWidget");
+        assert_eq!(c_type_name("const GtkTextIter *"), "GtkTextIter");
+        assert_eq!(c_type_name("GtkStateFlags"), "GtkStateFlags");
+        assert_eq!(c_type_name("const volatile GtkWidget * *"), "GtkWidget");
+    }
+
+    #[test]
+    fn test_qualified_types() {
+        let find =
+            |name: &str| (name == "GtkWidget").then(|| "[`Widget`][crate::Widget]".to_owned());
+        assert_eq!(
+            replace_qualified_types("Takes a `const GtkWidget *` and a #const GtkWidget*.", find),
+            "Takes a [`Widget`][crate::Widget] and a [`Widget`][crate::Widget]."
+        );
+        assert_eq!(
+            replace_qualified_types("A `volatile GtkWidget` value", find),
+            "A [`Widget`][crate::Widget] value"
+        );
+        // The unknown types and the unqualified ones are left to the other passes
+        let unknown = "A `const GtkFoo *` and a `GtkWidget *`";
+        assert_eq!(replace_qualified_types(unknown, find), unknown);
+    }
+
+    #[test]
+    fn test_interface_trait_doc_link() {
+        assert_eq!(
+            gen_interface_trait_doc_link("OrientableExt"),
+            "[`OrientableExt`][trait@crate::prelude::OrientableExt]"
+        );
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq!(
+            expand_tabs_to("if (x)\n\tfoo ();\n\t\tbar ();", 4),
+            "if (x)\n    foo ();\n        bar ();"
+        );
+        assert_eq!(expand_tabs_to("ab\tc", 4), "ab  c");
+        assert_eq!(expand_tabs_to("no tabs", 2), "no tabs");
+    }
+
+    #[test]
+    fn test_plural_type() {
+        assert_eq!(singular_type_name("GtkWidgets"), Some("GtkWidget"));
+        assert_eq!(singular_type_name("GtkWidget"), None);
+        assert_eq!(singular_type_name("GtkAddress"), None);
+        assert_eq!(
+            gen_plural_doc_link("[`Widget`][crate::Widget]"),
+            "[`Widget`][crate::Widget]s"
+        );
+    }
+
+    #[test]
+    fn test_plural_type_link() {
+        use crate::{config::gobjects::GObject, library::Class};
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let type_id = library.add_type(
+            MAIN_NAMESPACE,
+            "Widget",
+            Type::Class(Class {
+                name: "Widget".to_owned(),
+                c_type: "GtkWidget".to_owned(),
+                ..Default::default()
+            }),
+        );
+        let mut env = test_env(library, DocConfig::default());
+        let object = GObject {
+            name: "Gtk.Widget".to_owned(),
+            status: GStatus::Generate,
+            ..Default::default()
+        };
+        env.config.objects.insert("Gtk.Widget".to_owned(), object);
+        env.analysis.objects.insert(
+            "Gtk.Widget".to_owned(),
+            object_info(type_id, "GtkWidget", false, &[]),
+        );
+
+        // The `#` references to an unknown type are tried in the singular
+        assert_eq!(
+            reformat_doc("The #GtkWidgets of a #GtkWidget", &env, None),
+            "The [`Widget`][crate::Widget]s of a [`Widget`][crate::Widget]"
+        );
+        assert_eq!(
+            reformat_doc("The #GtkFrobnicators", &env, None),
+            "The `GtkFrobnicators`"
+        );
+    }
+
+    #[test]
+    fn test_property_accessor_name() {
+        assert_eq!(property_accessor_name("label", false), "label");
+        assert_eq!(property_accessor_name("label", true), "set_label");
+        assert_eq!(
+            property_accessor_name("use-underline", true),
+            "set_use_underline"
+        );
+        assert_eq!(
+            gen_accessor_doc_link(
+                "prelude::ButtonExt",
+                "ButtonExt",
+                &property_accessor_name("label", true),
+                false
+            ),
+            "[`ButtonExt::set_label()`][crate::prelude::ButtonExt::set_label()]"
+        );
+        assert_eq!(
+            gen_accessor_doc_link("Button", "Button", "set_label", true),
+            "[`set_label()`][Self::set_label()]"
+        );
+        // Like `gtk_widget_get_visible()`, generated as `is_visible()`
+        assert!(is_c_property_accessor("get_visible", "visible", false));
+        assert!(is_c_property_accessor("is_visible", "visible", false));
+        assert!(is_c_property_accessor(
+            "get_use_underline",
+            "use-underline",
+            false
+        ));
+        assert!(is_c_property_accessor("set_visible", "visible", true));
+        assert!(!is_c_property_accessor("set_visible", "visible", false));
+        assert!(!is_c_property_accessor("get_visible", "visible", true));
+        assert!(!is_c_property_accessor(
+            "get_visible_child",
+            "visible",
+            false
+        ));
+    }
+
+    /// The analysis of a Gtk object or interface with the `(property, getter)` ones
+    pub(crate) fn object_info(
+        type_id: TypeId,
+        c_type: &str,
+        is_interface: bool,
+        properties: &[(&str, &str)],
+    ) -> analysis::object::Info {
+        use crate::{
+            analysis::{bounds::Bounds, properties::Property, ref_mode::RefMode},
+            library::Nullable,
+        };
+
+        let name = c_type.trim_start_matches("Gtk").to_owned();
+        analysis::object::Info {
+            base: analysis::info_base::InfoBase {
+                full_name: format!("Gtk.{}", name),
+                type_id,
+                name: name.clone(),
+                ..Default::default()
+            },
+            c_type: c_type.to_owned(),
+            is_interface,
+            generate_trait: true,
+            trait_name: format!("{}Ext", name),
+            properties: properties
+                .iter()
+                .map(|&(property, getter)| Property {
+                    name: property.to_owned(),
+                    var_name: getter.to_owned(),
+                    typ: TypeId::default(),
+                    is_get: true,
+                    func_name: getter.to_owned(),
+                    func_name_alias: None,
+                    nullable: Nullable(false),
+                    get_out_ref_mode: RefMode::None,
+                    set_in_ref_mode: RefMode::None,
+                    bounds: Bounds::default(),
+                    set_bound: None,
+                    version: None,
+                    deprecated_version: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_renamed_property_link() {
+        use crate::library::Class;
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let type_id = library.add_type(
+            MAIN_NAMESPACE,
+            "Widget",
+            Type::Class(Class {
+                name: "Widget".to_owned(),
+                c_type: "GtkWidget".to_owned(),
+                ..Default::default()
+            }),
+        );
+        let mut env = test_env(library, DocConfig::default());
+        env.analysis.objects.insert(
+            "Gtk.Widget".to_owned(),
+            object_info(type_id, "GtkWidget", false, &[("visible", "is_visible")]),
+        );
+        let symbols = env.symbols.borrow();
+        let ctx = DocContext::new(&env, &symbols);
+
+        // The getter of the `visible` property is named `is_visible()`
+        assert_eq!(
+            find_member("GtkWidget", ":visible", &ctx, None),
+            Some("[`WidgetExt::is_visible()`][crate::prelude::WidgetExt::is_visible()]".to_owned())
+        );
+        assert_eq!(
+            replace_c_types(
+                "Whether #GtkWidget:visible is set",
+                &ctx,
+                None,
+                &RefCell::default()
+            ),
+            "Whether [`WidgetExt::is_visible()`][crate::prelude::WidgetExt::is_visible()] is set"
+        );
+    }
+
+    #[test]
+    fn test_type_macro_links() {
+        use crate::library::Class;
+
+        let class = |name: &str| {
+            Type::Class(Class {
+                name: name.to_owned(),
+                c_type: format!("Gtk{}", name),
+                ..Default::default()
+            })
+        };
+        let env_with = |doc: DocConfig| {
+            let mut library = Library::new("Gtk");
+            library.add_namespace("GLib");
+            let widget = library.add_type(MAIN_NAMESPACE, "Widget", class("Widget"));
+            let mut env = test_env(library, doc);
+            // Added after the symbols are collected, so without one
+            let label = env
+                .library
+                .add_type(MAIN_NAMESPACE, "Label", class("Label"));
+            for (type_id, c_type) in [(widget, "GtkWidget"), (label, "GtkLabel")] {
+                let mut info = object_info(type_id, c_type, false, &[]);
+                info.get_type = format!("gtk_{}_get_type", info.name.to_lowercase());
+                env.analysis.objects.insert(info.full_name.clone(), info);
+            }
+            env
+        };
+        // The doc and how many references it left unresolved
+        let replace = |env: &Env, input: &str| {
+            let symbols = env.symbols.borrow();
+            let ctx = DocContext::new(env, &symbols);
+            let misses = RefCell::default();
+            let doc = replace_c_types(input, &ctx, None, &misses);
+            (doc, misses.into_inner().len())
+        };
+
+        let env = env_with(DocConfig::default());
+        assert_eq!(
+            replace(&env, "Check with GTK_IS_WIDGET() first"),
+            (
+                "Check with [`is::<Widget>()`][glib::prelude::Cast::is()] first".to_owned(),
+                0
+            )
+        );
+        assert_eq!(
+            replace(&env, "Cast with GTK_WIDGET()"),
+            (
+                "Cast with [`dynamic_cast::<Widget>()`][glib::prelude::Cast::dynamic_cast()]"
+                    .to_owned(),
+                0
+            )
+        );
+        // Without a symbol for the object the macro isn't linked
+        assert_eq!(
+            replace(&env, "Check with GTK_IS_LABEL()"),
+            ("Check with `GTK_IS_LABEL()`".to_owned(), 1)
+        );
+
+        // An empty path backticks the macros of the objects without a warning
+        let env = env_with(DocConfig {
+            type_check_macro_link: Some(String::new()),
+            ..Default::default()
+        });
+        assert_eq!(
+            replace(&env, "Check with GTK_IS_WIDGET()"),
+            ("Check with `GTK_IS_WIDGET()`".to_owned(), 0)
+        );
+    }
+
+    #[test]
+    fn test_property_setter_link() {
+        use crate::{
+            analysis::{bounds::Bounds, properties::Property, ref_mode::RefMode},
+            library::{Class, Nullable},
+        };
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let type_id = library.add_type(
+            MAIN_NAMESPACE,
+            "Button",
+            Type::Class(Class {
+                name: "Button".to_owned(),
+                c_type: "GtkButton".to_owned(),
+                ..Default::default()
+            }),
+        );
+        let doc = DocConfig {
+            property_link_target: PropertyLinkTarget::Setter,
+            ..Default::default()
+        };
+        let mut env = test_env(library, doc);
+        let mut info = object_info(type_id, "GtkButton", false, &[("label", "label")]);
+        info.properties.push(Property {
+            name: "label".to_owned(),
+            var_name: "label".to_owned(),
+            typ: TypeId::default(),
+            is_get: false,
+            func_name: "set_label".to_owned(),
+            func_name_alias: None,
+            nullable: Nullable(false),
+            get_out_ref_mode: RefMode::None,
+            set_in_ref_mode: RefMode::None,
+            bounds: Bounds::default(),
+            set_bound: None,
+            version: None,
+            deprecated_version: None,
+        });
+        env.analysis.objects.insert("Gtk.Button".to_owned(), info);
+        let symbols = env.symbols.borrow();
+        let ctx = DocContext::new(&env, &symbols);
+
+        // `doc.property_link_target = "setter"` links the properties to their setter
+        assert_eq!(
+            replace_c_types("Sets #GtkButton:label", &ctx, None, &RefCell::default()),
+            "Sets [`ButtonExt::set_label()`][crate::prelude::ButtonExt::set_label()]"
+        );
+    }
+
+    #[test]
+    fn test_interface_property_link() {
+        use crate::library::Interface;
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let type_id = library.add_type(
+            MAIN_NAMESPACE,
+            "Orientable",
+            Type::Interface(Interface {
+                name: "Orientable".to_owned(),
+                c_type: "GtkOrientable".to_owned(),
+                ..Default::default()
+            }),
+        );
+        let mut env = test_env(library, DocConfig::default());
+        env.analysis.objects.insert(
+            "Gtk.Orientable".to_owned(),
+            object_info(
+                type_id,
+                "GtkOrientable",
+                true,
+                &[("orientation", "orientation")],
+            ),
+        );
+        let symbols = env.symbols.borrow();
+        let ctx = DocContext::new(&env, &symbols);
+
+        // The properties of interfaces are linked to the getter of their trait
+        let link = "[property@Gtk.Orientable:orientation]"
+            .parse::<gi_docgen::GiDocgen>()
+            .unwrap();
+        assert_eq!(
+            link.rust_link(&ctx, None),
+            "[`OrientableExt::orientation()`][crate::prelude::OrientableExt::orientation()]"
+        );
+    }
+
+    #[test]
+    fn test_interface_struct_link() {
+        use crate::library::Interface;
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let mut infos = Vec::new();
+        for (name, generate_trait) in [("Orientable", true), ("Buildable", false)] {
+            let c_type = format!("Gtk{}", name);
+            let type_id = library.add_type(
+                MAIN_NAMESPACE,
+                name,
+                Type::Interface(Interface {
+                    name: name.to_owned(),
+                    c_type: c_type.clone(),
+                    c_class_type: Some(format!("{}Iface", c_type)),
+                    ..Default::default()
+                }),
+            );
+            let mut info = object_info(type_id, &c_type, true, &[]);
+            info.c_class_type = Some(format!("{}Iface", c_type));
+            info.generate_trait = generate_trait;
+            infos.push(info);
+        }
+        let mut env = test_env(library, DocConfig::default());
+        for info in infos {
+            env.analysis.objects.insert(info.full_name.clone(), info);
+        }
+        let symbols = env.symbols.borrow();
+        let ctx = DocContext::new(&env, &symbols);
+
+        // The interface structs link to the trait of their interface
+        assert_eq!(
+            find_type("GtkOrientableIface", &ctx, None),
+            Some("[`OrientableExt`][trait@crate::prelude::OrientableExt]".to_owned())
+        );
+        // or to the interface itself without one
+        assert_eq!(
+            find_type("GtkBuildableIface", &ctx, None),
+            Some("[`Buildable`][crate::Buildable]".to_owned())
+        );
     }
 }
@@ -1,21 +1,29 @@
 use crate::{
-    analysis::object::LocationInObject,
+    analysis::{
+        functions,
+        object::{self, LocationInObject},
+    },
     codegen::doc::format::{
         gen_alias_doc_link, gen_callback_doc_link, gen_const_doc_link, gen_object_fn_doc_link,
-        gen_property_doc_link, gen_signal_doc_link, gen_symbol_doc_link, gen_vfunc_doc_link,
+        gen_property_doc_link, gen_signal_doc_link, gen_symbol_doc_link, gen_type_fn_doc_link,
+        gen_vfunc_doc_link, is_available_in, signal_owner, DocContext,
     },
-    library::{TypeId, MAIN_NAMESPACE},
+    library::{FunctionKind, TypeId, MAIN_NAMESPACE},
     nameutil::mangle_keywords,
-    Env,
 };
+use log::info;
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 use std::{
+    borrow::Cow,
     fmt::{self, Display, Formatter},
     str::FromStr,
 };
 
-use super::format::find_method_or_function;
+use super::format::{
+    find_c_identifier, find_method_or_function, find_property_accessor, find_signal_connect,
+    find_vfunc_method, inline_code,
+};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum GiDocgenError {
@@ -92,25 +100,66 @@ fn namespace_type_method_from_details(
     }
 }
 
-static GI_DOCGEN_SYMBOLS: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"\[(callback|id|alias|class|const|ctor|enum|error|flags|func|iface|method|property|signal|struct|vfunc)[@](\w+\b)([:.]+[\w-]+\b)?([:.]+[\w-]+\b)?\]?").unwrap()
-});
+static GI_DOCGEN_SYMBOLS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[([a-z]+)[@](\w+\b)([:.]+[\w-]+\b)?([:.]+[\w-]+\b)?\]?").unwrap());
 
+/// Link the gi-docgen references and transform the text between them with `raw`, the
+/// later passes it runs never see the rendered links
 pub(crate) fn replace_c_types(
     entry: &str,
-    env: &Env,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
+    raw: impl Fn(&str) -> String,
 ) -> String {
-    GI_DOCGEN_SYMBOLS
-        .replace_all(entry, |caps: &Captures<'_>| {
-            if let Ok(gi_type) = GiDocgen::from_str(&caps[0]) {
-                gi_type.rust_link(env, in_type)
-            } else {
-                // otherwise fallback to the original string
-                caps[0].to_string()
+    replace_links(
+        entry,
+        |link| match GiDocgen::from_str(link) {
+            Ok(gi_type) => gi_type.rust_link(env, in_type),
+            Err(err) => {
+                if let GiDocgenError::InvalidLinkType(_) = err {
+                    info!("{} in `{}`", err, link);
+                }
+                // otherwise fallback to the referenced item as inline code
+                unresolved_link(link)
             }
-        })
-        .to_string()
+        },
+        raw,
+    )
+}
+
+/// Replace the gi-docgen links with `link` and the text between them with `raw`
+fn replace_links(
+    entry: &str,
+    link: impl Fn(&str) -> String,
+    raw: impl Fn(&str) -> String,
+) -> String {
+    let mut out = String::with_capacity(entry.len());
+    let mut last = 0;
+    for m in GI_DOCGEN_SYMBOLS.find_iter(entry) {
+        out.push_str(&raw(&entry[last..m.start()]));
+        out.push_str(&link(m.as_str()));
+        last = m.end();
+    }
+    out.push_str(&raw(&entry[last..]));
+    out
+}
+
+/// The gi-docgen links as inline code, for the docs that skip the gi-docgen pass. The
+/// ones of an unknown kind, like a bracketed e-mail address, are kept as they are.
+pub(crate) fn links_as_code(entry: &str) -> Cow<'_, str> {
+    GI_DOCGEN_SYMBOLS.replace_all(entry, |caps: &Captures<'_>| {
+        match GiDocgen::from_str(&caps[0]) {
+            Err(GiDocgenError::InvalidLinkType(_)) => caps[0].to_owned(),
+            _ => unresolved_link(&caps[0]),
+        }
+    })
+}
+
+/// The `Gtk.Widget` item of a `[kind@Gtk.Widget]` link that can't be parsed
+fn unresolved_link(item_link: &str) -> String {
+    let item_link = item_link.trim_start_matches('[').trim_end_matches(']');
+    let details = item_link.split_once('@').map_or(item_link, |(_, d)| d);
+    inline_code(details)
 }
 
 /// A representation of the various ways to link items using GI-docgen
@@ -186,6 +235,11 @@ pub enum GiDocgen {
         type_: String,
         name: String,
     },
+    // Any kind of type
+    Type {
+        namespace: Option<String>,
+        type_: String,
+    },
 }
 
 fn ns_type_to_doc(namespace: &Option<String>, type_: &str) -> String {
@@ -200,7 +254,7 @@ fn find_method_or_function_by_name(
     type_: Option<&str>,
     namespace: Option<&str>,
     name: &str,
-    env: &Env,
+    env: &DocContext<'_>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
     is_class_method: bool,
 ) -> Option<String> {
@@ -238,7 +292,42 @@ fn find_method_or_function_by_name(
     )
 }
 
-fn is_same_namespace(env: &Env, namespace: Option<&str>, type_id: TypeId) -> bool {
+/// Constructors are associated functions of objects or records, linked under the name
+/// gir generates for them, e.g. `with_label()` for `new_with_label`
+fn find_constructor(
+    type_: &str,
+    namespace: Option<&str>,
+    name: &str,
+    env: &DocContext<'_>,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) -> Option<String> {
+    let search_fn = |f: &functions::Info| {
+        f.kind == FunctionKind::Constructor
+            && f.name == mangle_keywords(name)
+            && is_available_in(env, in_type, f.version)
+    };
+    if let Some((obj_info, fn_info)) = env.analysis.find_object_by_function(
+        env.library,
+        |o| o.name == type_ && is_same_namespace(env, namespace, o.type_id),
+        search_fn,
+    ) {
+        Some(gen_object_fn_doc_link(
+            obj_info, fn_info, env, in_type, type_,
+        ))
+    } else {
+        env.analysis
+            .find_record_by_function(
+                env.library,
+                |r| r.name == type_ && is_same_namespace(env, namespace, r.type_id),
+                search_fn,
+            )
+            .map(|(record_info, fn_info)| {
+                gen_type_fn_doc_link(record_info.type_id, fn_info, env, in_type)
+            })
+    }
+}
+
+fn is_same_namespace(env: &DocContext<'_>, namespace: Option<&str>, type_id: TypeId) -> bool {
     namespace
         .as_ref()
         .map_or(MAIN_NAMESPACE == type_id.ns_id, |n| {
@@ -246,13 +335,19 @@ fn is_same_namespace(env: &Env, namespace: Option<&str>, type_id: TypeId) -> boo
         })
 }
 
+/// The vfuncs of interfaces are called by the method of the same name gir provides
+/// in their `Ext` trait, which the vfunc references link to
+pub(super) fn provides_vfunc_methods(obj_info: &object::Info, type_: &str) -> bool {
+    obj_info.is_interface && obj_info.generate_trait && obj_info.name == type_
+}
+
 impl GiDocgen {
     pub fn rust_link(
         &self,
-        env: &Env,
+        env: &DocContext<'_>,
         in_type: Option<(&TypeId, Option<LocationInObject>)>,
     ) -> String {
-        let symbols = env.symbols.borrow();
+        let symbols = env.symbols;
         match self {
             GiDocgen::Enum { type_, namespace } | GiDocgen::Error { type_, namespace } => env
                 .analysis
@@ -307,9 +402,12 @@ impl GiDocgen {
                 })
                 .map_or_else(
                     || gen_property_doc_link(&ns_type_to_doc(namespace, type_), name),
+                    // Interfaces are analyzed as objects too, their accessors are on the trait
                     |info| {
-                        let sym = symbols.by_tid(info.type_id).unwrap();
-                        gen_property_doc_link(&sym.full_rust_name(), name)
+                        find_property_accessor(info, name, env, in_type).unwrap_or_else(|| {
+                            let sym = symbols.by_tid(info.type_id).unwrap();
+                            gen_property_doc_link(&sym.full_rust_name(), name)
+                        })
                     },
                 ),
             GiDocgen::Signal {
@@ -325,15 +423,20 @@ impl GiDocgen {
                 })
                 .map_or_else(
                     || gen_signal_doc_link(&ns_type_to_doc(namespace, type_), name),
+                    // The signal can be declared on an interface implemented by the type
                     |info| {
-                        let sym = symbols.by_tid(info.type_id).unwrap();
-                        gen_signal_doc_link(&sym.full_rust_name(), name)
+                        let owner = signal_owner(info, name, &env.analysis.objects);
+                        find_signal_connect(owner, name, env, in_type).unwrap_or_else(|| {
+                            let sym = symbols.by_tid(owner.type_id).unwrap();
+                            gen_signal_doc_link(&sym.full_rust_name(), name)
+                        })
                     },
                 ),
-            GiDocgen::Id(c_name) => symbols.by_c_name(c_name).map_or_else(
-                || format!("`{}`", c_name),
-                |sym| format!("[`{n}`][crate::{n}]", n = sym.full_rust_name()),
-            ),
+            // Only the generated items are linked, not any symbol of the library
+            GiDocgen::Id(c_name) => find_c_identifier(c_name, env, in_type).unwrap_or_else(|| {
+                info!("No generated item found for `{}`", c_name);
+                format!("`{}`", c_name)
+            }),
             GiDocgen::Struct { namespace, type_ } => env
                 .analysis
                 .records
@@ -349,19 +452,8 @@ impl GiDocgen {
                 namespace,
                 type_,
                 name,
-            } => env
-                .analysis
-                .find_object_by_function(
-                    env,
-                    |o| &o.name == type_ && is_same_namespace(env, namespace.as_deref(), o.type_id),
-                    |f| f.name == mangle_keywords(name),
-                )
-                .map_or_else(
-                    || format!("`{}::{}()`", ns_type_to_doc(namespace, type_), name),
-                    |(obj_info, fn_info)| {
-                        gen_object_fn_doc_link(obj_info, fn_info, env, in_type, type_)
-                    },
-                ),
+            } => find_constructor(type_, namespace.as_deref(), name, env, in_type)
+                .unwrap_or_else(|| format!("`{}::{}()`", ns_type_to_doc(namespace, type_), name)),
             GiDocgen::Func {
                 namespace,
                 type_,
@@ -403,7 +495,51 @@ impl GiDocgen {
                 namespace,
                 type_,
                 name,
-            } => gen_vfunc_doc_link(&ns_type_to_doc(namespace, type_), name),
+            } => find_vfunc_method(name, env, in_type, |o| {
+                provides_vfunc_methods(o, type_)
+                    && is_same_namespace(env, namespace.as_deref(), o.type_id)
+            })
+            .unwrap_or_else(|| {
+                let type_ = ns_type_to_doc(namespace, type_);
+                info!("No method generated for the vfunc `{}.{}`", type_, name);
+                gen_vfunc_doc_link(&type_, name)
+            }),
+            GiDocgen::Type { namespace, type_ } => {
+                let is_type = |name: &str, type_id: TypeId| {
+                    name == type_ && is_same_namespace(env, namespace.as_deref(), type_id)
+                };
+                let analysis = env.analysis;
+                analysis
+                    .objects
+                    .values()
+                    .find(|o| is_type(&o.name, o.type_id))
+                    .map(|o| o.type_id)
+                    .or_else(|| {
+                        analysis
+                            .records
+                            .values()
+                            .find(|r| is_type(&r.name, r.type_id))
+                            .map(|r| r.type_id)
+                    })
+                    .or_else(|| {
+                        analysis
+                            .enumerations
+                            .iter()
+                            .find(|e| is_type(&e.name, e.type_id))
+                            .map(|e| e.type_id)
+                    })
+                    .or_else(|| {
+                        analysis
+                            .flags
+                            .iter()
+                            .find(|f| is_type(&f.name, f.type_id))
+                            .map(|f| f.type_id)
+                    })
+                    .map_or_else(
+                        || format!("`{}`", ns_type_to_doc(namespace, type_)),
+                        |type_id| gen_symbol_doc_link(type_id, env),
+                    )
+            }
         }
     }
 }
@@ -515,6 +651,10 @@ impl FromStr for GiDocgen {
                         name,
                     })
                 }
+                "type" => {
+                    let (namespace, type_) = namespace_type_from_details(link_details, "type")?;
+                    Ok(GiDocgen::Type { namespace, type_ })
+                }
                 "id" => Ok(GiDocgen::Id(link_details.to_string())),
                 e => Err(GiDocgenError::InvalidLinkType(e.to_string())),
             }
@@ -655,6 +795,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_link_type() {
+        assert_eq!(
+            GiDocgen::from_str("[type@Gtk.Widget]"),
+            Ok(GiDocgen::Type {
+                namespace: Some("Gtk".to_string()),
+                type_: "Widget".to_string()
+            })
+        );
+        assert_eq!(
+            GiDocgen::from_str("[type@Gtk.]"),
+            Err(GiDocgenError::BrokenLinkType("type".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_unresolved_link() {
+        assert_eq!(
+            GiDocgen::from_str("[annotation@Gtk.Widget]"),
+            Err(GiDocgenError::InvalidLinkType("annotation".to_string()))
+        );
+        assert_eq!(unresolved_link("[annotation@Gtk.Widget]"), "`Gtk.Widget`");
+        assert_eq!(unresolved_link("[signal@Gtk.Widget:]"), "`Gtk.Widget:`");
+        assert_eq!(
+            GI_DOCGEN_SYMBOLS
+                .captures("See [annotation@Gtk.Widget] too")
+                .map(|caps| caps[0].to_owned())
+                .as_deref(),
+            Some("[annotation@Gtk.Widget]")
+        );
+    }
+
+    #[test]
+    fn test_replace_links() {
+        // The constant of the rendered link isn't linked again, unlike the one of the text
+        let link = |_: &str| "[`%GTK_ALIGN_END`][crate::Align::End]".to_owned();
+        let raw = |text: &str| text.replace("%GTK_ALIGN_END", "[`Align::End`][crate::Align::End]");
+        assert_eq!(
+            replace_links("Use [enum@Gtk.Align] or %GTK_ALIGN_END.", link, raw),
+            "Use [`%GTK_ALIGN_END`][crate::Align::End] or [`Align::End`][crate::Align::End]."
+        );
+        assert_eq!(replace_links("", link, raw), "");
+        let len = |link: &str| link.len().to_string();
+        let raw = |text: &str| format!("<{}>", text);
+        assert_eq!(
+            replace_links("[enum@Gtk.Align][enum@Gtk.Align]", len, raw),
+            "<>16<>16<>"
+        );
+    }
+
+    #[test]
+    fn test_links_as_code() {
+        assert_eq!(
+            links_as_code("Add it to a [class@Gtk.Widget] with @widget."),
+            "Add it to a `Gtk.Widget` with @widget."
+        );
+        assert_eq!(
+            links_as_code("Emits [signal@Gtk.Widget::destroy]"),
+            "Emits `Gtk.Widget::destroy`"
+        );
+        let input = "Write to [maintainer@example.org]";
+        assert_eq!(links_as_code(input), input);
+    }
+
     #[test]
     fn test_link_property() {
         assert_eq!(
@@ -704,6 +908,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_provides_vfunc_methods() {
+        let object = |name: &str, is_interface: bool, generate_trait: bool| object::Info {
+            base: crate::analysis::info_base::InfoBase {
+                name: name.to_owned(),
+                ..Default::default()
+            },
+            is_interface,
+            generate_trait,
+            ..Default::default()
+        };
+        // `[vfunc@Gtk.Editable.get_text]` links to `EditableExt::text()`
+        assert!(provides_vfunc_methods(
+            &object("Editable", true, true),
+            "Editable"
+        ));
+        assert!(!provides_vfunc_methods(
+            &object("Editable", true, false),
+            "Editable"
+        ));
+        assert!(!provides_vfunc_methods(
+            &object("Editable", true, true),
+            "Entry"
+        ));
+        // The vfuncs of classes are overridden through the subclassing traits instead
+        assert!(!provides_vfunc_methods(
+            &object("Widget", false, true),
+            "Widget"
+        ));
+    }
+
     #[test]
     fn test_link_vfunc() {
         assert_eq!(
@@ -723,6 +958,22 @@ mod tests {
                 name: "snapshot".to_string(),
             })
         );
+
+        assert_eq!(
+            GiDocgen::from_str("[vfunc@Gtk.Editable.get_text]"),
+            Ok(GiDocgen::VFunc {
+                namespace: Some("Gtk".to_string()),
+                type_: "Editable".to_string(),
+                name: "get_text".to_string(),
+            })
+        );
+        assert_eq!(
+            gen_vfunc_doc_link(
+                &ns_type_to_doc(&Some("Gtk".to_string()), "Widget"),
+                "snapshot"
+            ),
+            "`vfunc::Gtk::Widget::snapshot`"
+        );
     }
 
     #[test]
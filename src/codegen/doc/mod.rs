@@ -1,7 +1,10 @@
-use self::format::reformat_doc;
+use self::format::{reformat_doc, reformat_doc_in, DocContext};
 use crate::{
-    analysis::{self, namespaces::MAIN, object::LocationInObject},
-    config::gobjects::GObject,
+    analysis::{self, namespaces::MAIN, object::LocationInObject, rust_type::RustType},
+    config::{
+        doc::{DocConfig, DocExample, DocItemKind, ImplementsStyle},
+        gobjects::GObject,
+    },
     env::Env,
     file_saver::save_to_file,
     library::{self, Type as LType, *},
@@ -9,7 +12,7 @@ use crate::{
     traits::*,
     version::Version,
 };
-use log::{error, info};
+use log::{error, info, warn};
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 use std::{
@@ -19,8 +22,18 @@ use std::{
 };
 use stripper_lib::{write_file_name, write_item_doc, Type as SType, TypeStruct};
 
+mod c_symbols;
+mod cache;
 mod format;
 mod gi_docgen;
+mod since;
+mod unresolved;
+
+pub use self::{
+    cache::DocCache,
+    format::{reformat_standalone_doc, DocResolver},
+    unresolved::{check_strict, LoggedRefs, UnresolvedRefs},
+};
 
 // A list of C parameters that are not used directly by the Rust bindings
 const IGNORED_C_FN_PARAMS: [&str; 6] = [
@@ -63,10 +76,13 @@ trait FunctionLikeType {
     fn parameters(&self) -> &[Parameter];
     fn version(&self) -> &Option<Version>;
     fn deprecated_version(&self) -> &Option<Version>;
+    fn stability(&self) -> Stability;
+    fn is_signal(&self) -> bool;
+    fn is_constructor(&self) -> bool;
 }
 
 macro_rules! impl_function_like_type {
-    ($ty:ident) => {
+    ($ty:ident, $is_signal:expr, $is_constructor:expr) => {
         impl FunctionLikeType for $ty {
             fn doc(&self) -> &Option<String> {
                 &self.doc
@@ -86,6 +102,15 @@ macro_rules! impl_function_like_type {
             fn deprecated_version(&self) -> &Option<Version> {
                 &self.deprecated_version
             }
+            fn stability(&self) -> Stability {
+                self.stability
+            }
+            fn is_signal(&self) -> bool {
+                $is_signal
+            }
+            fn is_constructor(&self) -> bool {
+                ($is_constructor)(self)
+            }
         }
     };
 }
@@ -97,26 +122,248 @@ impl_to_stripper_type!(Class, Struct);
 impl_to_stripper_type!(Function, Fn);
 impl_to_stripper_type!(Signal, Fn, false);
 
-impl_function_like_type!(Function);
-impl_function_like_type!(Signal);
+impl_function_like_type!(Function, false, |f: &Function| f.kind
+    == FunctionKind::Constructor);
+impl_function_like_type!(Signal, true, |_: &Signal| false);
 
 pub fn generate(env: &Env) {
     info!("Generating documentation {:?}", env.config.doc_target_path);
+    cache::load(env);
+    // The doc is generated once for the doc file and the sidecar files
+    let mut doc = Vec::new();
+    generate_doc(&mut doc, env).unwrap_or_else(|why| {
+        panic!(
+            "couldn't write to {:?}: {:?}",
+            env.config.doc_target_path, why
+        )
+    });
     save_to_file(&env.config.doc_target_path, env.config.make_backup, |w| {
-        generate_doc(w, env)
+        w.write_all(&doc)
     });
+    if env.config.doc.c_symbols_index {
+        let path = env.config.doc_target_path.with_file_name("c_symbols.md");
+        info!("Generating C symbols index {:?}", path);
+        save_to_file(path, env.config.make_backup, |w| {
+            c_symbols::generate(w, env)
+        });
+    }
+    if env.config.doc.since_index {
+        let path = env.config.doc_target_path.with_file_name("since.md");
+        info!("Generating since index {:?}", path);
+        save_to_file(path, env.config.make_backup, |w| since::generate(w, env));
+    }
+    if let Some(ref dir) = env.config.doc.sidecar_dir {
+        info!("Generating documentation sidecar files in {:?}", dir);
+        let doc = String::from_utf8_lossy(&doc);
+        for (name, content) in item_docs(&doc) {
+            save_to_file(
+                dir.join(format!("{}.md", name)),
+                env.config.make_backup,
+                |w| w.write_all(content.as_bytes()),
+            );
+        }
+    }
+    cache::save(env);
+    unresolved::generate(env);
+    unresolved::log_summary(env);
+}
+
+/// Splits a generated doc file into the docs of each item, by its path like
+/// `ButtonExt.set_label`, see `doc.sidecar_dir`
+fn item_docs(doc: &str) -> Vec<(String, String)> {
+    let mut items: Vec<(String, String)> = Vec::new();
+    let mut in_item = false;
+    for line in doc.lines() {
+        if let Some(names) = item_header_names(line) {
+            // The `<!-- file * -->` headers aren't items
+            in_item = !names.contains(&"*");
+            if in_item {
+                items.push((names.join("."), String::new()));
+            }
+        } else if let Some((_, content)) = items.last_mut().filter(|_| in_item) {
+            content.push_str(line);
+            content.push('\n');
+        }
+    }
+    items
+}
+
+/// Returns the documentation of a single item exactly as it would be written by
+/// [`generate`], or `None` if there's no such item. The item is given by its path,
+/// optionally prefixed by the namespace, like `Gtk.Button`, `Gtk.Button.new` or
+/// `Gtk.Button.clicked` for a signal. The references the docs leave unresolved are
+/// logged but not kept in `env`.
+pub fn preview(env: &Env, item: &str) -> Result<Option<String>> {
+    let mut doc = Vec::new();
+    unresolved::without_recording(env, || generate_doc(&mut doc, env))?;
+    let doc = String::from_utf8_lossy(&doc);
+
+    let path: Vec<&str> = item.split('.').collect();
+    let path = match path.split_first() {
+        Some((ns, rest)) if *ns == env.library.namespace(MAIN_NAMESPACE).name => rest,
+        _ => &path[..],
+    };
+    Ok(find_item_doc(&doc, path))
+}
+
+/// Looks for the section of `path` in a generated doc file, where each item starts
+/// with a `<!-- impl Type::fn name -->` like header.
+fn find_item_doc(doc: &str, path: &[&str]) -> Option<String> {
+    let mut found: Option<String> = None;
+    for line in doc.lines() {
+        if let Some(names) = item_header_names(line) {
+            if found.is_some() {
+                break;
+            }
+            if is_item_path(&names, path) {
+                found = Some(String::new());
+            }
+        } else if let Some(ref mut content) = found {
+            content.push_str(line);
+            content.push('\n');
+        }
+    }
+    found
+}
+
+/// The names of the item and its parents in a `<!-- trait ButtonExt::fn set_label -->`
+/// like header
+fn item_header_names(line: &str) -> Option<Vec<&str>> {
+    let header = line.strip_prefix("<!-- ")?.strip_suffix(" -->")?;
+    Some(
+        header
+            .split("::")
+            .map(|s| s.rsplit(' ').next().unwrap_or(s))
+            .collect(),
+    )
+}
+
+/// Adds the configured examples at the end of the docs of their item, see `doc.examples`
+fn insert_examples(doc: &str, config: &DocConfig) -> String {
+    let mut out = String::with_capacity(doc.len());
+    let mut pending: Option<&DocExample> = None;
+    for line in doc.lines() {
+        if let Some(names) = item_header_names(line) {
+            if let Some(example) = pending.take() {
+                out.push_str(&gen_example_block(example, config));
+            }
+            pending = config
+                .examples
+                .iter()
+                .find(|(path, _)| is_item_path(&names, &path.split('.').collect::<Vec<_>>()))
+                .map(|(_, example)| example);
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    if let Some(example) = pending {
+        out.push_str(&gen_example_block(example, config));
+    }
+    out
+}
+
+fn gen_example_block(example: &DocExample, config: &DocConfig) -> String {
+    format!(
+        "\n# {}\n\n```rust{}\n{}{}\n```\n",
+        config.section_titles.examples,
+        if example.no_run { ",no_run" } else { "" },
+        config
+            .doctest_setup()
+            .iter()
+            .map(|line| format!("{}\n", format::hidden_line(line)))
+            .collect::<String>(),
+        example.code.trim_end()
+    )
+}
+
+fn is_item_path(names: &[&str], path: &[&str]) -> bool {
+    if names.len() != path.len() {
+        return false;
+    }
+    let is_type = |name: &str, type_: &str| {
+        name == type_
+            || name
+                .strip_prefix(type_)
+                .map_or(false, |s| ["Ext", "ExtManual", "Builder"].contains(&s))
+    };
+    match (names.split_last(), path.split_last()) {
+        (Some((name, parents)), Some((item, item_parents))) => {
+            parents
+                .iter()
+                .zip(item_parents)
+                .all(|(name, type_)| is_type(*name, *type_))
+                && (is_type(*name, *item)
+                    || *name == format!("connect_{}", nameutil::signal_to_snake(item)))
+        }
+        _ => false,
+    }
+}
+
+/// The doc comment put on the `pub use` re-export of a type, see `doc.reexport_docs`
+pub fn reexport_doc(env: &Env, type_id: TypeId) -> Option<String> {
+    if !env.config.doc.reexport_docs {
+        return None;
+    }
+    let doc = match env.library.type_(type_id) {
+        LType::Class(cl) => cl.doc.as_ref(),
+        LType::Interface(iface) => iface.doc.as_ref(),
+        LType::Record(record) => record.doc.as_ref(),
+        LType::Enumeration(enum_) => enum_.doc.as_ref(),
+        LType::Bitfield(flags) => flags.doc.as_ref(),
+        _ => None,
+    }?;
+    // Links are resolved without `in_type` as `Self` wouldn't work in a `mod.rs`
+    let summary = reformat_doc(&summary(doc)?, env, None);
+    Some(to_doc_comment(&summary))
+}
+
+/// The first sentence of the first paragraph of `doc`, on a single line
+fn summary(doc: &str) -> Option<String> {
+    let paragraph = doc.trim().split("\n\n").next()?;
+    let sentence = paragraph
+        .find(". ")
+        .or_else(|| paragraph.find(".\n"))
+        .map_or(paragraph, |pos| &paragraph[..=pos]);
+    let summary = sentence.split_whitespace().collect::<Vec<_>>().join(" ");
+    if summary.is_empty() {
+        None
+    } else {
+        Some(summary)
+    }
+}
+
+fn to_doc_comment(doc: &str) -> String {
+    doc.lines()
+        .map(|line| format!("/// {}", line).trim_end().to_owned())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
-#[allow(clippy::type_complexity)]
 fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
+    if env.config.doc.examples.is_empty() {
+        return generate_items_doc(w, env);
+    }
+    let mut doc = Vec::new();
+    generate_items_doc(&mut doc, env)?;
+    let doc = String::from_utf8_lossy(&doc);
+    w.write_all(insert_examples(&doc, &env.config.doc).as_bytes())
+}
+
+#[allow(clippy::type_complexity)]
+fn generate_items_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
     write_file_name(w, None)?;
-    let mut generators: Vec<(&str, Box<dyn Fn(&mut dyn Write, &Env) -> Result<()>>)> = Vec::new();
+    let symbols = env.symbols.borrow();
+    let ctx = &DocContext::new(env, &symbols);
+    let mut generators: Vec<(
+        &str,
+        Box<dyn Fn(&mut dyn Write, &DocContext<'_>) -> Result<()>>,
+    )> = Vec::new();
 
     for info in env.analysis.objects.values() {
         if info.type_id.ns_id == MAIN && !env.is_totally_deprecated(None, info.deprecated_version) {
             generators.push((
                 &info.name,
-                Box::new(move |w, e| create_object_doc(w, e, info)),
+                Box::new(move |w, ctx| create_object_doc(w, env, ctx, info)),
             ));
         }
     }
@@ -125,7 +372,7 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
         if info.type_id.ns_id == MAIN && !env.is_totally_deprecated(None, info.deprecated_version) {
             generators.push((
                 &info.name,
-                Box::new(move |w, e| create_record_doc(w, e, info)),
+                Box::new(move |w, ctx| create_record_doc(w, env, ctx, info)),
             ));
         }
     }
@@ -141,7 +388,7 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
             {
                 generators.push((
                     &enum_.name[..],
-                    Box::new(move |w, e| create_enum_doc(w, e, enum_, tid)),
+                    Box::new(move |w, ctx| create_enum_doc(w, env, ctx, enum_, tid)),
                 ));
             }
         } else if let LType::Bitfield(bitfield) = type_ {
@@ -154,7 +401,7 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
             {
                 generators.push((
                     &bitfield.name[..],
-                    Box::new(move |w, e| create_bitfield_doc(w, e, bitfield, tid)),
+                    Box::new(move |w, ctx| create_bitfield_doc(w, env, ctx, bitfield, tid)),
                 ));
             }
         }
@@ -195,7 +442,7 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
                     .unwrap_or_default();
 
                 let should_be_documented = f_info
-                    .map(|f| f.should_docs_be_generated(env))
+                    .map(|f| f.should_docs_be_generated(&env.library))
                     .unwrap_or(false);
                 if !should_be_documented {
                     continue;
@@ -204,10 +451,12 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
                 create_fn_doc(
                     w,
                     env,
+                    ctx,
                     function,
                     parent,
                     fn_new_name,
                     doc_ignored_parameters,
+                    analyzed_instance_parameter(f_info),
                     None,
                 )?;
             }
@@ -222,9 +471,14 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
             SType::Const
         };
         let ty_id = TypeStruct::new(ty, &constant.name);
+        let _item = ctx.enter_item(constant.version);
         write_item_doc(w, &ty_id, |w| {
             if let Some(ref doc) = constant.doc {
-                writeln!(w, "{}", reformat_doc(doc, env, Some((&constant.typ, None))))?;
+                writeln!(
+                    w,
+                    "{}",
+                    reformat_doc_in(doc, env, ctx, Some((&constant.typ, None)))
+                )?;
             }
             Ok(())
         })?;
@@ -232,13 +486,18 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
 
     generators.sort_by_key(|&(name, _)| name);
     for (_, f) in generators {
-        f(w, env)?;
+        f(w, ctx)?;
     }
 
     Ok(())
 }
 
-fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info) -> Result<()> {
+fn create_object_doc(
+    w: &mut dyn Write,
+    env: &Env,
+    ctx: &DocContext<'_>,
+    info: &analysis::object::Info,
+) -> Result<()> {
     let ty = TypeStruct::new(SType::Struct, &info.name);
     let ty_ext = TypeStruct::new(SType::Trait, &info.trait_name);
     let has_trait = info.generate_trait;
@@ -249,6 +508,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
     let properties: &[Property];
     let is_abstract;
     let has_builder;
+    let stability;
 
     let obj = env
         .config
@@ -265,6 +525,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
             properties = &cl.properties;
             is_abstract = env.library.type_(info.type_id).is_abstract();
             has_builder = obj.generate_builder;
+            stability = cl.stability;
         }
         Type::Interface(iface) => {
             doc = iface.doc.as_ref();
@@ -274,20 +535,26 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
             properties = &iface.properties;
             is_abstract = false;
             has_builder = false;
+            stability = iface.stability;
         }
         _ => unreachable!(),
     }
 
     let manual_traits = get_type_manual_traits_for_implements(env, info);
+    warn_missing_doc(&env.config.doc, DocItemKind::Type, doc.is_some(), || {
+        info.full_name.clone()
+    });
 
     write_item_doc(w, &ty, |w| {
+        write_stability_banner(w, env, stability)?;
         if let Some(doc) = doc_deprecated {
             writeln!(
                 w,
                 "{}",
-                reformat_doc(
+                reformat_doc_in(
                     doc,
                     env,
+                    ctx,
                     Some((&info.type_id, Some(LocationInObject::Impl)))
                 )
             )?;
@@ -296,9 +563,10 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
             writeln!(
                 w,
                 "{}",
-                reformat_doc(
-                    doc,
+                reformat_doc_in(
+                    &strip_doc_fields(doc),
                     env,
+                    ctx,
                     Some((&info.type_id, Some(LocationInObject::Impl)))
                 )
             )?;
@@ -325,8 +593,24 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
         implements.extend(manual_traits);
 
         if !implements.is_empty() {
-            writeln!(w, "\n# Implements\n")?;
-            writeln!(w, "{}", &implements.join(", "))?;
+            let supertypes = env
+                .class_hierarchy
+                .supertypes(info.type_id)
+                .iter()
+                .filter(|&tid| !env.type_status(&tid.full_name(&env.library)).ignored())
+                .map(|&tid| format::gen_symbol_doc_link(tid, ctx))
+                .collect::<Vec<_>>();
+            writeln!(w, "\n# {}\n", env.config.doc.section_titles.implements)?;
+            writeln!(
+                w,
+                "{}",
+                implements_doc(
+                    env.config.doc.implements_style,
+                    &implements,
+                    &supertypes,
+                    &nameutil::use_glib_type(env, "prelude"),
+                )
+            )?;
         }
         Ok(())
     })?;
@@ -347,6 +631,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
             }
         }
         for property in builder_properties.iter() {
+            let _item = ctx.enter_item(property.version);
             let ty = TypeStruct {
                 ty: SType::Fn,
                 name: nameutil::signal_to_snake(&property.name),
@@ -358,9 +643,10 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
                     writeln!(
                         w,
                         "{}",
-                        reformat_doc(
-                            &fix_param_names(doc, &None),
+                        reformat_doc_in(
+                            &fix_param_names(doc, &None, None),
                             env,
+                            ctx,
                             Some((&info.type_id, Some(LocationInObject::Builder)))
                         )
                     )?;
@@ -369,9 +655,10 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
                     writeln!(
                         w,
                         "{}",
-                        reformat_doc(
-                            &fix_param_names(doc, &None),
+                        reformat_doc_in(
+                            &fix_param_names(doc, &None, None),
                             env,
+                            ctx,
                             Some((&info.type_id, Some(LocationInObject::Builder)))
                         )
                     )?;
@@ -383,7 +670,11 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
 
     if has_trait {
         write_item_doc(w, &ty_ext, |w| {
-            writeln!(w, "Trait containing all [`struct@{}`] methods.", ty.name)?;
+            writeln!(
+                w,
+                "{}",
+                trait_doc(env.config.doc.trait_doc_template.as_deref(), &ty.name)
+            )?;
 
             let mut implementors = std::iter::once(info.type_id)
                 .chain(env.class_hierarchy.subtypes(info.type_id))
@@ -397,7 +688,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
                 .collect::<Vec<_>>();
             implementors.sort();
 
-            writeln!(w, "\n# Implementors\n")?;
+            writeln!(w, "\n# {}\n", env.config.doc.section_titles.implementors)?;
             writeln!(w, "{}", implementors.join(", "))?;
             Ok(())
         })?;
@@ -452,7 +743,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
         if let Some(c_identifier) = &function.c_identifier {
             let f_info = info.functions.iter().find(|f| &f.glib_name == c_identifier);
             let should_be_documented = f_info
-                .map(|f| f.should_docs_be_generated(env))
+                .map(|f| f.should_docs_be_generated(&env.library))
                 .unwrap_or(false);
 
             if !should_be_documented {
@@ -467,10 +758,12 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
             create_fn_doc(
                 w,
                 env,
+                ctx,
                 function,
                 Some(Box::new(ty)),
                 fn_new_name,
                 doc_ignored_parameters,
+                analyzed_instance_parameter(f_info),
                 Some((&info.type_id, object_location)),
             )?;
         }
@@ -492,10 +785,12 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
         create_fn_doc(
             w,
             env,
+            ctx,
             signal,
             Some(Box::new(ty)),
             None,
             HashSet::new(),
+            None,
             Some((&info.type_id, object_location)),
         )?;
     }
@@ -529,6 +824,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
         create_property_doc(
             w,
             env,
+            ctx,
             property,
             Some(Box::new(ty)),
             (&info.type_id, object_location),
@@ -540,21 +836,46 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
     Ok(())
 }
 
-fn create_record_doc(w: &mut dyn Write, env: &Env, info: &analysis::record::Info) -> Result<()> {
+fn create_record_doc(
+    w: &mut dyn Write,
+    env: &Env,
+    ctx: &DocContext<'_>,
+    info: &analysis::record::Info,
+) -> Result<()> {
     let record: &Record = env.library.type_(info.type_id).to_ref_as();
     let ty = record.to_stripper_type();
+    warn_missing_doc(
+        &env.config.doc,
+        DocItemKind::Type,
+        record.doc.is_some(),
+        || info.full_name.clone(),
+    );
 
     write_item_doc(w, &ty, |w| {
+        write_stability_banner(w, env, record.stability)?;
         if let Some(ref doc) = record.doc {
-            writeln!(w, "{}", reformat_doc(doc, env, Some((&info.type_id, None))))?;
+            let doc = strip_doc_fields(doc);
+            writeln!(
+                w,
+                "{}",
+                reformat_doc_in(&doc, env, ctx, Some((&info.type_id, None)))
+            )?;
         }
         if let Some(ver) = info.deprecated_version {
-            writeln!(w, "\n# Deprecated since {}\n", ver)?;
+            writeln!(
+                w,
+                "\n# {}\n",
+                env.config.doc.section_titles.deprecated_since(ver)
+            )?;
         } else if record.doc_deprecated.is_some() {
-            writeln!(w, "\n# Deprecated\n")?;
+            writeln!(w, "\n# {}\n", env.config.doc.section_titles.deprecated)?;
         }
         if let Some(ref doc) = record.doc_deprecated {
-            writeln!(w, "{}", reformat_doc(doc, env, Some((&info.type_id, None))))?;
+            writeln!(
+                w,
+                "{}",
+                reformat_doc_in(doc, env, ctx, Some((&info.type_id, None)))
+            )?;
         }
         Ok(())
     })?;
@@ -567,7 +888,7 @@ fn create_record_doc(w: &mut dyn Write, env: &Env, info: &analysis::record::Info
         if let Some(c_identifier) = &function.c_identifier {
             let f_info = info.functions.iter().find(|f| &f.glib_name == c_identifier);
             let should_be_documented = f_info
-                .map(|f| f.should_docs_be_generated(env))
+                .map(|f| f.should_docs_be_generated(&env.library))
                 .unwrap_or(false);
             if !should_be_documented {
                 continue;
@@ -577,10 +898,12 @@ fn create_record_doc(w: &mut dyn Write, env: &Env, info: &analysis::record::Info
             create_fn_doc(
                 w,
                 env,
+                ctx,
                 function,
                 Some(Box::new(ty.clone())),
                 fn_new_name,
                 HashSet::new(),
+                analyzed_instance_parameter(f_info),
                 Some((&info.type_id, None)),
             )?;
         }
@@ -588,26 +911,54 @@ fn create_record_doc(w: &mut dyn Write, env: &Env, info: &analysis::record::Info
     Ok(())
 }
 
-fn create_enum_doc(w: &mut dyn Write, env: &Env, enum_: &Enumeration, tid: TypeId) -> Result<()> {
+fn create_enum_doc(
+    w: &mut dyn Write,
+    env: &Env,
+    ctx: &DocContext<'_>,
+    enum_: &Enumeration,
+    tid: TypeId,
+) -> Result<()> {
     let ty = enum_.to_stripper_type();
+    warn_missing_doc(
+        &env.config.doc,
+        DocItemKind::Type,
+        enum_.doc.is_some(),
+        || tid.full_name(&env.library),
+    );
 
     write_item_doc(w, &ty, |w| {
+        write_stability_banner(w, env, enum_.stability)?;
         if let Some(ref doc) = enum_.doc {
-            writeln!(w, "{}", reformat_doc(doc, env, Some((&tid, None))))?;
+            writeln!(
+                w,
+                "{}",
+                reformat_doc_in(&strip_doc_fields(doc), env, ctx, Some((&tid, None)))
+            )?;
         }
         if let Some(ver) = enum_.deprecated_version {
-            writeln!(w, "\n# Deprecated since {}\n", ver)?;
+            writeln!(
+                w,
+                "\n# {}\n",
+                env.config.doc.section_titles.deprecated_since(ver)
+            )?;
         } else if enum_.doc_deprecated.is_some() {
-            writeln!(w, "\n# Deprecated\n")?;
+            writeln!(w, "\n# {}\n", env.config.doc.section_titles.deprecated)?;
         }
         if let Some(ref doc) = enum_.doc_deprecated {
-            writeln!(w, "{}", reformat_doc(doc, env, Some((&tid, None))))?;
+            writeln!(w, "{}", reformat_doc_in(doc, env, ctx, Some((&tid, None))))?;
         }
         Ok(())
     })?;
 
     for member in &enum_.members {
+        warn_missing_doc(
+            &env.config.doc,
+            DocItemKind::Member,
+            member.doc.is_some(),
+            || member.c_identifier.clone(),
+        );
         if member.doc.is_some() {
+            let _item = ctx.enter_item(member.version);
             let sub_ty = TypeStruct {
                 name: nameutil::enum_member_name(&member.name),
                 parent: Some(Box::new(ty.clone())),
@@ -616,10 +967,10 @@ fn create_enum_doc(w: &mut dyn Write, env: &Env, enum_: &Enumeration, tid: TypeI
             };
             write_item_doc(w, &sub_ty, |w| {
                 if let Some(ref doc) = member.doc {
-                    writeln!(w, "{}", reformat_doc(doc, env, Some((&tid, None))))?;
+                    writeln!(w, "{}", reformat_doc_in(doc, env, ctx, Some((&tid, None))))?;
                 }
                 if let Some(ref doc) = member.doc_deprecated {
-                    writeln!(w, "{}", reformat_doc(doc, env, Some((&tid, None))))?;
+                    writeln!(w, "{}", reformat_doc_in(doc, env, ctx, Some((&tid, None))))?;
                 }
                 Ok(())
             })?;
@@ -632,28 +983,51 @@ fn create_enum_doc(w: &mut dyn Write, env: &Env, enum_: &Enumeration, tid: TypeI
 fn create_bitfield_doc(
     w: &mut dyn Write,
     env: &Env,
+    ctx: &DocContext<'_>,
     bitfield: &Bitfield,
     tid: TypeId,
 ) -> Result<()> {
     let ty = bitfield.to_stripper_type();
+    warn_missing_doc(
+        &env.config.doc,
+        DocItemKind::Type,
+        bitfield.doc.is_some(),
+        || tid.full_name(&env.library),
+    );
 
     write_item_doc(w, &ty, |w| {
+        write_stability_banner(w, env, bitfield.stability)?;
         if let Some(ref doc) = bitfield.doc {
-            writeln!(w, "{}", reformat_doc(doc, env, Some((&tid, None))))?;
+            writeln!(
+                w,
+                "{}",
+                reformat_doc_in(&strip_doc_fields(doc), env, ctx, Some((&tid, None)))
+            )?;
         }
         if let Some(ver) = bitfield.deprecated_version {
-            writeln!(w, "\n# Deprecated since {}\n", ver)?;
+            writeln!(
+                w,
+                "\n# {}\n",
+                env.config.doc.section_titles.deprecated_since(ver)
+            )?;
         } else if bitfield.doc_deprecated.is_some() {
-            writeln!(w, "\n# Deprecated\n")?;
+            writeln!(w, "\n# {}\n", env.config.doc.section_titles.deprecated)?;
         }
         if let Some(ref doc) = bitfield.doc_deprecated {
-            writeln!(w, "{}", reformat_doc(doc, env, Some((&tid, None))))?;
+            writeln!(w, "{}", reformat_doc_in(doc, env, ctx, Some((&tid, None))))?;
         }
         Ok(())
     })?;
 
     for member in &bitfield.members {
+        warn_missing_doc(
+            &env.config.doc,
+            DocItemKind::Member,
+            member.doc.is_some(),
+            || member.c_identifier.clone(),
+        );
         if member.doc.is_some() {
+            let _item = ctx.enter_item(member.version);
             let sub_ty = TypeStruct {
                 name: nameutil::bitfield_member_name(&member.name),
                 parent: Some(Box::new(ty.clone())),
@@ -662,10 +1036,10 @@ fn create_bitfield_doc(
             };
             write_item_doc(w, &sub_ty, |w| {
                 if let Some(ref doc) = member.doc {
-                    writeln!(w, "{}", reformat_doc(doc, env, Some((&tid, None))))?;
+                    writeln!(w, "{}", reformat_doc_in(doc, env, ctx, Some((&tid, None))))?;
                 }
                 if let Some(ref doc) = member.doc_deprecated {
-                    writeln!(w, "{}", reformat_doc(doc, env, Some((&tid, None))))?;
+                    writeln!(w, "{}", reformat_doc_in(doc, env, ctx, Some((&tid, None))))?;
                 }
                 Ok(())
             })?;
@@ -675,26 +1049,263 @@ fn create_bitfield_doc(
     Ok(())
 }
 
+/// Warn about the items of the kinds of `doc.missing_docs` without any documentation,
+/// returns whether it did
+fn warn_missing_doc(
+    config: &DocConfig,
+    kind: DocItemKind,
+    has_doc: bool,
+    name: impl FnOnce() -> String,
+) -> bool {
+    let missing = !has_doc && config.missing_docs.contains(&kind);
+    if missing {
+        warn!("{} `{}` has no documentation", kind, name());
+    }
+    missing
+}
+
+fn write_stability_banner(w: &mut dyn Write, env: &Env, stability: Stability) -> Result<()> {
+    if let Some(banner) = stability_banner(&env.config.doc, stability) {
+        writeln!(w, "{}\n", banner)?;
+    }
+    Ok(())
+}
+
+/// The summary of the `Ext` trait of a type, see `doc.trait_doc_template`
+fn trait_doc(template: Option<&str>, type_name: &str) -> String {
+    match template {
+        Some(template) => {
+            template.replace("{type}", &format!("[`{0}`][struct@crate::{0}]", type_name))
+        }
+        None => format!("Trait containing all [`struct@{}`] methods.", type_name),
+    }
+}
+
+/// The content of the `# Implements` section, see `doc.implements_style`
+fn implements_doc(
+    style: ImplementsStyle,
+    implements: &[String],
+    supertypes: &[String],
+    glib_prelude: &str,
+) -> String {
+    let mut doc = implements.join(", ");
+    if style == ImplementsStyle::IsA && !supertypes.is_empty() {
+        doc.push_str(&format!(
+            "\n\nIt implements [`IsA`][{p}::IsA] for {}, so it can be used where they are \
+             expected and upcast to them with [`upcast()`][{p}::Cast::upcast()].",
+            supertypes.join(", "),
+            p = glib_prelude,
+        ));
+    }
+    doc
+}
+
+/// The banner put on top of the docs of non-stable items, see `doc.unstable_banner`
+fn stability_banner(config: &DocConfig, stability: Stability) -> Option<&str> {
+    match stability {
+        Stability::Stable => None,
+        Stability::Unstable | Stability::Private => config.unstable_banner.as_deref(),
+    }
+}
+
+/// The name of the C parameter the analysis of a method passes as `self`
+fn analyzed_instance_parameter(f_info: Option<&analysis::functions::Info>) -> Option<&str> {
+    f_info?
+        .parameters
+        .c_parameters
+        .iter()
+        .find(|p| p.instance_parameter)
+        .map(|p| p.name.as_str())
+}
+
+/// The parameter referred to as `@self`, the analysis can make one of a parameter the GIR
+/// doesn't mark as `instance-parameter`, like the `gpointer` of the functions of some records
+fn self_parameter<'a>(analyzed: Option<&'a str>, parameters: &'a [Parameter]) -> Option<&'a str> {
+    analyzed.or_else(|| {
+        parameters
+            .iter()
+            .find(|p| p.instance_parameter)
+            .map(|p| p.name.as_str())
+    })
+}
+
 static PARAM_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"@(\w+)\b").unwrap());
 
-fn fix_param_names<'a>(doc: &'a str, self_name: &Option<String>) -> Cow<'a, str> {
+/// The `@name` references are renamed like the parameters of the generated function,
+/// when `parameters` are given the other names are kept as they are.
+fn fix_param_names<'a>(
+    doc: &'a str,
+    self_name: &Option<String>,
+    parameters: Option<&[&str]>,
+) -> Cow<'a, str> {
     PARAM_NAME.replace_all(doc, |caps: &Captures<'_>| {
         if let Some(self_name) = self_name {
             if &caps[1] == self_name {
                 return "@self".into();
             }
         }
-        format!("@{}", nameutil::mangle_keywords(&caps[1]))
+        match parameters {
+            Some(parameters) if !parameters.contains(&&caps[1]) => {
+                info!("`@{}` is not a parameter", &caps[1]);
+                caps[0].to_owned()
+            }
+            _ => format!("@{}", nameutil::mangle_keywords(&caps[1])),
+        }
+    })
+}
+
+/// The `@n_items` references to the length parameters of the arrays, which are elided
+/// in Rust, are turned into references to the arrays, `None` being the returned one
+fn rephrase_array_lengths<'a>(doc: &'a str, lengths: &[(&str, Option<&str>)]) -> Cow<'a, str> {
+    if lengths.is_empty() {
+        return Cow::Borrowed(doc);
+    }
+    PARAM_NAME.replace_all(doc, |caps: &Captures<'_>| {
+        match lengths.iter().find(|(length, _)| *length == &caps[1]) {
+            Some((_, Some(array))) => format!("the length of @{}", array),
+            Some((_, None)) => "the length of the returned array".to_owned(),
+            None => caps[0].to_owned(),
+        }
+    })
+}
+
+/// The `@error` references of the throwing functions are turned into references to the
+/// error of the returned `Result`, the `GError` out parameter not existing in Rust
+fn rephrase_error<'a>(doc: &'a str, error_name: Option<&str>, error_type: &str) -> Cow<'a, str> {
+    let error_name = match error_name {
+        Some(error_name) => error_name,
+        None => return Cow::Borrowed(doc),
+    };
+    PARAM_NAME.replace_all(doc, |caps: &Captures<'_>| {
+        if &caps[1] == error_name {
+            format!("the [`{}`] of the returned `Result`", error_type)
+        } else {
+            caps[0].to_owned()
+        }
     })
 }
 
+static PROPERTY_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"@(\w+(?:-\w+)*)").unwrap());
+
+/// The `@label` references of constructor docs to the properties set at construction
+/// are turned into `#GtkLabel:label` ones, linked like the other property references
+fn link_construct_properties<'a>(
+    doc: &'a str,
+    parameters: &[&str],
+    c_type: &str,
+    construct_properties: &[&str],
+) -> Cow<'a, str> {
+    PROPERTY_NAME.replace_all(doc, |caps: &Captures<'_>| {
+        let name = caps[1].replace('_', "-");
+        if construct_properties.contains(&name.as_str()) && !parameters.contains(&&caps[1]) {
+            format!("#{}:{}", c_type, name)
+        } else {
+            caps[0].to_owned()
+        }
+    })
+}
+
+static RETURN_REF: Lazy<Regex> = Lazy::new(|| Regex::new(r"@returns?\b").unwrap());
+
+/// The `@return` references of signal docs are about the value returned by the handlers
+fn describe_handler_return<'a>(doc: &'a str, return_type: &str) -> Cow<'a, str> {
+    RETURN_REF.replace_all(doc, |_: &Captures<'_>| {
+        format!("the return value of the handler ({})", return_type)
+    })
+}
+
+static DOC_FIELD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(Returns|Since|Deprecated):(?:\s+(.*))?$").unwrap());
+
+/// The GTK-Doc `Returns:`, `Since:` and `Deprecated:` fields left in the body of the docs
+/// are removed from it, each starts a line and lasts until the end of its paragraph. The
+/// `Returns:` text is returned, the others are already generated from the GIR metadata.
+fn split_doc_fields(doc: &str) -> (Cow<'_, str>, Option<String>) {
+    let mut body = Vec::new();
+    let mut returns: Option<String> = None;
+    let mut in_field = None;
+    let mut in_code = false;
+    for line in doc.split('\n') {
+        let trimmed = line.trim();
+        if !in_code {
+            if let Some(caps) = DOC_FIELD.captures(line) {
+                let field = caps.get(1).unwrap().as_str();
+                if let (Some(text), "Returns") = (caps.get(2), field) {
+                    returns = Some(text.as_str().trim().to_owned());
+                }
+                in_field = Some(field);
+                continue;
+            }
+        }
+        if let Some(field) = in_field {
+            if trimmed.is_empty() {
+                in_field = None;
+            } else {
+                if field == "Returns" {
+                    let returns = returns.get_or_insert_with(String::new);
+                    if !returns.is_empty() {
+                        returns.push('\n');
+                    }
+                    returns.push_str(trimmed);
+                }
+                continue;
+            }
+        }
+        if in_code {
+            in_code = !(trimmed.contains("]|") || trimmed.starts_with("```"));
+        } else {
+            in_code =
+                trimmed.starts_with("```") || (trimmed.contains("|[") && !trimmed.contains("]|"));
+        }
+        body.push(line);
+    }
+    if body.len() == doc.split('\n').count() {
+        return (Cow::Borrowed(doc), None);
+    }
+    // Without the blank lines left around the removed fields
+    let mut out = Vec::<&str>::with_capacity(body.len());
+    for line in body {
+        let follows_text = matches!(out.last(), Some(l) if !l.trim().is_empty());
+        if follows_text || !line.trim().is_empty() {
+            out.push(line);
+        }
+    }
+    if matches!(out.last(), Some(l) if l.trim().is_empty()) {
+        out.pop();
+    }
+    (Cow::Owned(out.join("\n")), returns)
+}
+
+/// The docs of a type without their `Since:` and `Deprecated:` fields, see
+/// [`split_doc_fields`], the docs with a `Returns:` one that means nothing there are kept
+fn strip_doc_fields(doc: &str) -> Cow<'_, str> {
+    match split_doc_fields(doc) {
+        (body, None) => body,
+        (_, Some(_)) => Cow::Borrowed(doc),
+    }
+}
+
+/// The docs of the return value from the GIR, followed by the `Returns:` field of the
+/// body when it says something else
+fn merge_returns(ret_doc: Option<&str>, fields_returns: Option<String>) -> Option<String> {
+    match (ret_doc, fields_returns) {
+        (Some(doc), Some(field)) if !doc.split_whitespace().eq(field.split_whitespace()) => {
+            Some(format!("{}\n\n{}", doc, field))
+        }
+        (Some(doc), _) => Some(doc.to_owned()),
+        (None, field) => field,
+    }
+}
+
 fn create_fn_doc<T>(
     w: &mut dyn Write,
     env: &Env,
+    ctx: &DocContext<'_>,
     fn_: &T,
     parent: Option<Box<TypeStruct>>,
     name_override: Option<String>,
     doc_ignored_parameters: HashSet<String>,
+    instance_parameter: Option<&str>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
 ) -> Result<()>
 where
@@ -703,6 +1314,19 @@ where
     if env.is_totally_deprecated(None, *fn_.deprecated_version()) {
         return Ok(());
     }
+    let _item = ctx.enter_item(*fn_.version());
+    let kind = if fn_.is_signal() {
+        DocItemKind::Signal
+    } else {
+        DocItemKind::Function
+    };
+    warn_missing_doc(&env.config.doc, kind, fn_.doc().is_some(), || {
+        let name = fn_.to_stripper_type().name;
+        in_type.map_or_else(
+            || name.clone(),
+            |(tid, _)| format!("{}.{}", tid.full_name(&env.library), name),
+        )
+    });
     if fn_.doc().is_none()
         && fn_.doc_deprecated().is_none()
         && fn_.ret().doc.is_none()
@@ -716,31 +1340,98 @@ where
         st.name = name_override;
     }
     let ty = TypeStruct { parent, ..st };
-    let self_name: Option<String> = fn_
+    let self_name = self_parameter(instance_parameter, fn_.parameters()).map(str::to_owned);
+
+    let construct_properties = in_type
+        .filter(|_| fn_.is_constructor())
+        .and_then(|(tid, _)| match env.library.type_(*tid) {
+            Type::Class(cl) => Some((
+                cl.c_type.as_str(),
+                cl.properties
+                    .iter()
+                    .filter(|p| p.construct || p.construct_only)
+                    .map(|p| p.name.as_str())
+                    .collect::<Vec<_>>(),
+            )),
+            _ => None,
+        });
+    let parameter_names = fn_
         .parameters()
         .iter()
-        .find(|p| p.instance_parameter)
-        .map(|p| p.name.clone());
+        .map(|p| p.name.as_str())
+        .collect::<Vec<_>>();
+    let fix_names =
+        |doc: &str| fix_param_names(doc, &self_name, Some(&parameter_names)).into_owned();
+    let length_name = |array_length: Option<u32>| {
+        array_length
+            .and_then(|pos| fn_.parameters().get(pos as usize))
+            .map(|p| p.name.as_str())
+    };
+    let array_lengths = fn_
+        .parameters()
+        .iter()
+        .filter_map(|p| Some((length_name(p.array_length)?, Some(p.name.as_str()))))
+        .chain(length_name(fn_.ret().array_length).map(|length| (length, None)))
+        .collect::<Vec<_>>();
+    let error_name = fn_
+        .parameters()
+        .iter()
+        .find(|p| p.is_error)
+        .map(|p| p.name.as_str());
+    let error_type = nameutil::use_glib_type(env, "Error");
+    let rephrase = |doc: &str| {
+        let doc = rephrase_array_lengths(doc, &array_lengths);
+        fix_names(&rephrase_error(&doc, error_name, &error_type))
+    };
+    let handler_return_type = Some(fn_.ret())
+        .filter(|ret| fn_.is_signal() && ret.typ != TypeId::tid_none())
+        .map(|ret| {
+            let link = format::find_param_type(&ret.c_type, ctx, in_type);
+            if link.starts_with('[') {
+                link
+            } else {
+                RustType::try_new(env, ret.typ)
+                    .map_or(link, |rust_type| format!("`{}`", rust_type.as_str()))
+            }
+        });
+
+    let (doc, fields_returns) = match fn_.doc() {
+        Some(doc) => {
+            let (doc, returns) = split_doc_fields(doc);
+            (Some(doc).filter(|doc| !doc.trim().is_empty()), returns)
+        }
+        None => (None, None),
+    };
+    let returns_doc = merge_returns(fn_.ret().doc.as_deref(), fields_returns);
 
     write_item_doc(w, &ty, |w| {
-        if let Some(doc) = fn_.doc() {
+        write_stability_banner(w, env, fn_.stability())?;
+        if let Some(ref doc) = doc {
+            let doc = match construct_properties {
+                Some((c_type, ref properties)) => {
+                    link_construct_properties(doc, &parameter_names, c_type, properties)
+                }
+                None => Cow::Borrowed(&**doc),
+            };
+            let doc = match handler_return_type {
+                Some(ref return_type) => {
+                    Cow::Owned(describe_handler_return(&doc, return_type).into_owned())
+                }
+                None => doc,
+            };
+            writeln!(w, "{}", reformat_doc_in(&rephrase(&doc), env, ctx, in_type))?;
+        }
+        if let Some(ver) = fn_.deprecated_version() {
             writeln!(
                 w,
-                "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), env, in_type)
+                "\n# {}\n",
+                env.config.doc.section_titles.deprecated_since(ver)
             )?;
-        }
-        if let Some(ver) = fn_.deprecated_version() {
-            writeln!(w, "\n# Deprecated since {}\n", ver)?;
         } else if fn_.doc_deprecated().is_some() {
-            writeln!(w, "\n# Deprecated\n")?;
+            writeln!(w, "\n# {}\n", env.config.doc.section_titles.deprecated)?;
         }
         if let Some(doc) = fn_.doc_deprecated() {
-            writeln!(
-                w,
-                "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), env, in_type)
-            )?;
+            writeln!(w, "{}", reformat_doc_in(&rephrase(doc), env, ctx, in_type))?;
         }
 
         // A list of parameter positions to filter out
@@ -761,7 +1452,7 @@ where
             .filter_map(|(indice, param)| {
                 (!indices_to_ignore.contains(&(indice as u32))).then(|| param)
             })
-            .filter(|param| !param.instance_parameter)
+            .filter(|param| !param.instance_parameter && self_name.as_ref() != Some(&param.name))
             .collect();
 
         let in_parameters = no_array_length_params.iter().filter(|param| {
@@ -775,17 +1466,44 @@ where
             !ignore
         });
 
-        for parameter in in_parameters {
-            if parameter.name.is_empty() {
-                continue;
+        let parameter_rows = || {
+            in_parameters
+                .clone()
+                .filter(|param| !param.name.is_empty())
+                .map(|param| {
+                    (
+                        nameutil::mangle_keywords(&param.name[..]).into_owned(),
+                        format::find_param_type(&param.c_type, ctx, in_type),
+                        param.doc.as_ref().map_or_else(String::new, |doc| {
+                            reformat_doc_in(&fix_names(doc), env, ctx, in_type)
+                        }),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if fn_.is_signal() && env.config.doc.signal_parameter_tables {
+            let rows = parameter_rows();
+            if rows.len() > 1 {
+                writeln!(w, "\n{}", gen_parameter_table(&rows))?;
             }
-            if let Some(ref doc) = parameter.doc {
-                writeln!(w, "## `{}`", nameutil::mangle_keywords(&parameter.name[..]))?;
-                writeln!(
-                    w,
-                    "{}",
-                    reformat_doc(&fix_param_names(doc, &self_name), env, in_type)
-                )?;
+        }
+
+        if env.config.doc.argument_lists {
+            let rows = parameter_rows();
+            if !rows.is_empty() {
+                writeln!(w, "\n# {}\n", env.config.doc.section_titles.arguments)?;
+                writeln!(w, "{}", gen_argument_list(&rows))?;
+            }
+        } else {
+            for parameter in in_parameters {
+                if parameter.name.is_empty() {
+                    continue;
+                }
+                if let Some(ref doc) = parameter.doc {
+                    writeln!(w, "## `{}`", nameutil::mangle_keywords(&parameter.name[..]))?;
+                    writeln!(w, "{}", reformat_doc_in(&fix_names(doc), env, ctx, in_type))?;
+                }
             }
         }
 
@@ -798,16 +1516,12 @@ where
             })
             .collect();
 
-        if fn_.ret().doc.is_some() || !out_parameters.is_empty() {
-            writeln!(w, "\n# Returns\n")?;
+        if returns_doc.is_some() || !out_parameters.is_empty() {
+            writeln!(w, "\n# {}\n", env.config.doc.section_titles.returns)?;
         }
         // document function's return
-        if let Some(ref doc) = fn_.ret().doc {
-            writeln!(
-                w,
-                "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), env, in_type)
-            )?;
+        if let Some(doc) = returns_doc.as_deref() {
+            writeln!(w, "{}", reformat_doc_in(&rephrase(doc), env, ctx, in_type))?;
         }
         // document OUT parameters as part of the function's Return
         for parameter in out_parameters {
@@ -817,20 +1531,53 @@ where
                     "\n## `{}`",
                     nameutil::mangle_keywords(&parameter.name[..])
                 )?;
-                writeln!(
-                    w,
-                    "{}",
-                    reformat_doc(&fix_param_names(doc, &self_name), env, in_type),
-                )?;
+                writeln!(w, "{}", reformat_doc_in(&fix_names(doc), env, ctx, in_type))?;
             }
         }
         Ok(())
     })
 }
 
+/// A markdown table of the `(name, type, description)` of each parameter
+fn gen_parameter_table(rows: &[(String, String, String)]) -> String {
+    let cell = |s: &str| {
+        s.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace('|', "\\|")
+    };
+    let mut table = String::from("| Parameter | Type | Description |\n|---|---|---|");
+    for (name, type_, doc) in rows {
+        table.push_str(&format!(
+            "\n| `{}` | {} | {} |",
+            name,
+            cell(type_),
+            cell(doc)
+        ));
+    }
+    table
+}
+
+/// A markdown list of the `(name, type, description)` of each parameter, the
+/// description lines indented to stay in their item
+fn gen_argument_list(rows: &[(String, String, String)]) -> String {
+    rows.iter()
+        .map(|(name, type_, doc)| {
+            let mut item = format!("- `{}` ({})", name, type_);
+            if !doc.is_empty() {
+                item.push_str(": ");
+                item.push_str(&doc.trim().replace('\n', "\n  ").replace("\n  \n", "\n\n"));
+            }
+            item
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn create_property_doc(
     w: &mut dyn Write,
     env: &Env,
+    ctx: &DocContext<'_>,
     property: &Property,
     parent: Option<Box<TypeStruct>>,
     in_type: (&TypeId, Option<LocationInObject>),
@@ -841,6 +1588,13 @@ fn create_property_doc(
     if env.is_totally_deprecated(Some(in_type.0.ns_id), property.deprecated_version) {
         return Ok(());
     }
+    let _item = ctx.enter_item(property.version);
+    warn_missing_doc(
+        &env.config.doc,
+        DocItemKind::Property,
+        property.doc.is_some(),
+        || format!("{}:{}", obj_info.full_name, property.name),
+    );
     if property.doc.is_none()
         && property.doc_deprecated.is_none()
         && (property.readable || property.writable)
@@ -879,19 +1633,23 @@ fn create_property_doc(
                 writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &None), env, Some(in_type))
+                    reformat_doc_in(&fix_param_names(doc, &None, None), env, ctx, Some(in_type))
                 )?;
             }
             if let Some(ver) = property.deprecated_version {
-                writeln!(w, "\n# Deprecated since {}\n", ver)?;
+                writeln!(
+                    w,
+                    "\n# {}\n",
+                    env.config.doc.section_titles.deprecated_since(ver)
+                )?;
             } else if property.doc_deprecated.is_some() {
-                writeln!(w, "\n# Deprecated\n")?;
+                writeln!(w, "\n# {}\n", env.config.doc.section_titles.deprecated)?;
             }
             if let Some(ref doc) = property.doc_deprecated {
                 writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &None), env, Some(in_type))
+                    reformat_doc_in(&fix_param_names(doc, &None, None), env, ctx, Some(in_type))
                 )?;
             }
             Ok(())
@@ -945,3 +1703,554 @@ pub fn get_type_manual_traits_for_implements(
         .map(|name| format!("[`{0}`][trait@crate::prelude::{0}]", name))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "<!-- file * -->
+<!-- struct Button -->
+A button.
+<!-- impl Button::fn new -->
+Creates a new button.
+<!-- trait ButtonExt::fn connect_clicked -->
+Emitted when the button has been activated.
+
+# Implements
+<!-- trait ButtonExt::fn set_label -->
+Sets the label.
+";
+
+    #[test]
+    fn test_find_item_doc() {
+        assert_eq!(find_item_doc(DOC, &["Button"]).unwrap(), "A button.\n");
+        assert_eq!(
+            find_item_doc(DOC, &["Button", "new"]).unwrap(),
+            "Creates a new button.\n"
+        );
+        assert_eq!(
+            find_item_doc(DOC, &["Button", "clicked"]).unwrap(),
+            "Emitted when the button has been activated.\n\n# Implements\n"
+        );
+        assert_eq!(
+            find_item_doc(DOC, &["Button", "set_label"]).unwrap(),
+            "Sets the label.\n"
+        );
+        assert_eq!(find_item_doc(DOC, &["Button", "free"]), None);
+        assert_eq!(find_item_doc(DOC, &["Label"]), None);
+    }
+
+    #[test]
+    fn test_preview() {
+        use super::{
+            format::tests::{object_info, test_env},
+            unresolved::RefKind,
+        };
+        use crate::config::gobjects::GStatus;
+
+        let mut library = Library::new("Gtk");
+        library.add_namespace("GLib");
+        let clicked = Signal {
+            name: "clicked".to_owned(),
+            parameters: Vec::new(),
+            ret: Parameter {
+                name: String::new(),
+                typ: TypeId::tid_none(),
+                c_type: "void".to_owned(),
+                instance_parameter: false,
+                direction: ParameterDirection::Return,
+                transfer: Transfer::None,
+                caller_allocates: false,
+                nullable: Nullable(false),
+                allow_none: false,
+                array_length: None,
+                is_error: false,
+                doc: None,
+                scope: ParameterScope::None,
+                closure: None,
+                destroy: None,
+            },
+            is_action: false,
+            is_detailed: false,
+            version: None,
+            deprecated_version: None,
+            stability: Stability::Stable,
+            doc: Some("Emitted when the button is activated, like #GtkFrobnicator.".to_owned()),
+            doc_deprecated: None,
+        };
+        let type_id = library.add_type(
+            MAIN_NAMESPACE,
+            "Button",
+            LType::Class(Class {
+                name: "Button".to_owned(),
+                c_type: "GtkButton".to_owned(),
+                signals: vec![clicked],
+                ..Default::default()
+            }),
+        );
+        let mut env = test_env(library, DocConfig::default());
+        let object = GObject {
+            name: "Gtk.Button".to_owned(),
+            status: GStatus::Generate,
+            ..Default::default()
+        };
+        env.config.objects.insert("Gtk.Button".to_owned(), object);
+        env.analysis.objects.insert(
+            "Gtk.Button".to_owned(),
+            object_info(type_id, "GtkButton", false, &[]),
+        );
+
+        assert_eq!(
+            preview(&env, "Gtk.Button.clicked").unwrap().as_deref(),
+            Some("Emitted when the button is activated, like `GtkFrobnicator`.\n")
+        );
+        assert_eq!(
+            preview(&env, "Button.clicked").unwrap(),
+            preview(&env, "Gtk.Button.clicked").unwrap()
+        );
+        assert_eq!(preview(&env, "Gtk.Button.released").unwrap(), None);
+        // The previews leave the unresolved references of the env alone
+        assert!(env.doc_logged.first(RefKind::Type, "GtkFrobnicator"));
+    }
+
+    #[test]
+    fn test_insert_examples() {
+        let mut config = DocConfig::default();
+        let examples = &mut config.examples;
+        examples.insert(
+            "Button.new".to_owned(),
+            DocExample {
+                code: "let button = Button::new();\n".to_owned(),
+                no_run: false,
+            },
+        );
+        examples.insert(
+            "Button.set_label".to_owned(),
+            DocExample {
+                code: "button.set_label(\"Ok\");".to_owned(),
+                no_run: true,
+            },
+        );
+        let doc = insert_examples(DOC, &config);
+        assert_eq!(
+            find_item_doc(&doc, &["Button", "new"]).unwrap(),
+            "Creates a new button.
+
+# Examples
+
+```rust
+let button = Button::new();
+```
+"
+        );
+        assert!(doc.ends_with(
+            "Sets the label.
+
+# Examples
+
+```rust,no_run
+button.set_label(\"Ok\");
+```
+"
+        ));
+        assert_eq!(find_item_doc(&doc, &["Button"]).unwrap(), "A button.\n");
+
+        config.example_setup = vec!["use gtk::prelude::*;".to_owned()];
+        config.section_titles.examples = "Exemples".to_owned();
+        let doc = insert_examples(DOC, &config);
+        assert_eq!(
+            find_item_doc(&doc, &["Button", "new"]).unwrap(),
+            "Creates a new button.
+
+# Exemples
+
+```rust
+# use gtk::prelude::*;
+let button = Button::new();
+```
+"
+        );
+
+        config.doctest_prelude = Some("gtk::init().unwrap();".to_owned());
+        let doc = insert_examples(DOC, &config);
+        assert!(find_item_doc(&doc, &["Button", "new"])
+            .unwrap()
+            .contains("```rust\n# gtk::init().unwrap();\n# use gtk::prelude::*;\nlet button"));
+    }
+
+    #[test]
+    fn test_parameter_table() {
+        let rows = vec![
+            (
+                "widget".to_owned(),
+                "[`Widget`][crate::Widget]".to_owned(),
+                "the widget that\nchanged".to_owned(),
+            ),
+            (
+                "flags".to_owned(),
+                "[`StateFlags`][crate::StateFlags]".to_owned(),
+                "the previous | state".to_owned(),
+            ),
+        ];
+        assert_eq!(
+            gen_parameter_table(&rows),
+            "| Parameter | Type | Description |
+|---|---|---|
+| `widget` | [`Widget`][crate::Widget] | the widget that changed |
+| `flags` | [`StateFlags`][crate::StateFlags] | the previous \\| state |"
+        );
+    }
+
+    #[test]
+    fn test_argument_list() {
+        let rows = vec![
+            (
+                "widget".to_owned(),
+                "[`Widget`][crate::Widget]".to_owned(),
+                "the widget to add\nto the box\n\nIt must not have a parent.".to_owned(),
+            ),
+            (
+                "position".to_owned(),
+                "[`PositionType`][crate::PositionType]".to_owned(),
+                String::new(),
+            ),
+        ];
+        assert_eq!(
+            gen_argument_list(&rows),
+            "- `widget` ([`Widget`][crate::Widget]): the widget to add
+  to the box
+
+  It must not have a parent.
+- `position` ([`PositionType`][crate::PositionType])"
+        );
+    }
+
+    #[test]
+    fn test_link_construct_properties() {
+        let properties = ["label", "use-underline"];
+        assert_eq!(
+            link_construct_properties(
+                "Creates a button with @label set, see @use_underline.",
+                &[],
+                "GtkButton",
+                &properties
+            ),
+            "Creates a button with #GtkButton:label set, see #GtkButton:use-underline."
+        );
+        // The parameters of the constructor take precedence
+        assert_eq!(
+            link_construct_properties(
+                "Creates a button with the @label text and @icon_name",
+                &["label"],
+                "GtkButton",
+                &properties
+            ),
+            "Creates a button with the @label text and @icon_name"
+        );
+    }
+
+    #[test]
+    fn test_describe_handler_return() {
+        assert_eq!(
+            describe_handler_return(
+                "Emitted on a key press, @return decides whether it is handled.",
+                "`bool`"
+            ),
+            "Emitted on a key press, the return value of the handler (`bool`) decides whether \
+             it is handled."
+        );
+        assert_eq!(
+            describe_handler_return("@returns is ignored but @return_value isn't", "`bool`"),
+            "the return value of the handler (`bool`) is ignored but @return_value isn't"
+        );
+    }
+
+    #[test]
+    fn test_split_doc_fields() {
+        let doc = "Gets the label of @button.
+
+Returns: the label, or %NULL if
+  there is none
+Since: 3.10
+
+Deprecated: 4.10: Use gtk_button_get_child()";
+        let (body, returns) = split_doc_fields(doc);
+        assert_eq!(body, "Gets the label of @button.");
+        assert_eq!(
+            returns.as_deref(),
+            Some("the label, or %NULL if\nthere is none")
+        );
+
+        let (body, returns) =
+            split_doc_fields("Sets the label.\nSince: 2.4\n\nThe label is shown next to the icon.");
+        assert_eq!(
+            body,
+            "Sets the label.\n\nThe label is shown next to the icon."
+        );
+        assert_eq!(returns, None);
+    }
+
+    #[test]
+    fn test_split_doc_fields_unchanged() {
+        for doc in [
+            "Returns the label, which is never %NULL.",
+            "The label it returns: never %NULL. Since: forever.",
+            "|[<!-- language=\"C\" -->\nReturns: in a code block\n]|",
+            "```c\nSince: 3.10\n```",
+        ] {
+            let (body, returns) = split_doc_fields(doc);
+            assert!(matches!(body, Cow::Borrowed(_)), "{}", doc);
+            assert_eq!(body, doc);
+            assert_eq!(returns, None);
+        }
+    }
+
+    #[test]
+    fn test_strip_doc_fields() {
+        assert_eq!(
+            strip_doc_fields("A widget with a label.\n\nSince: 3.10"),
+            "A widget with a label."
+        );
+        let doc = "A widget with a label.\n\nReturns: nothing";
+        assert_eq!(strip_doc_fields(doc), doc);
+    }
+
+    #[test]
+    fn test_merge_returns() {
+        let field = || Some("the label, or %NULL".to_owned());
+        assert_eq!(
+            merge_returns(Some("the label"), field()).as_deref(),
+            Some("the label\n\nthe label, or %NULL")
+        );
+        // The same text isn't repeated
+        assert_eq!(
+            merge_returns(Some("the label,\nor %NULL"), field()).as_deref(),
+            Some("the label,\nor %NULL")
+        );
+        assert_eq!(merge_returns(None, field()), field());
+        assert_eq!(
+            merge_returns(Some("the label"), None).as_deref(),
+            Some("the label")
+        );
+        assert_eq!(merge_returns(None, None), None);
+    }
+
+    #[test]
+    fn test_item_docs() {
+        let doc = "<!-- file * -->
+<!-- struct Button -->
+A widget emitting a signal when clicked.
+<!-- impl Button::fn new -->
+Creates a new [`Button`][crate::Button].
+<!-- file button.rs -->
+";
+        assert_eq!(
+            item_docs(doc),
+            [
+                (
+                    "Button".to_owned(),
+                    "A widget emitting a signal when clicked.\n".to_owned()
+                ),
+                (
+                    "Button.new".to_owned(),
+                    "Creates a new [`Button`][crate::Button].\n".to_owned()
+                ),
+            ]
+        );
+
+        let dir = std::env::temp_dir().join(format!("gir-doc-sidecars-{}", std::process::id()));
+        for (name, content) in item_docs(doc) {
+            save_to_file(dir.join(format!("{}.md", name)), false, |w| {
+                w.write_all(content.as_bytes())
+            });
+        }
+        assert_eq!(
+            std::fs::read_to_string(dir.join("Button.new.md")).unwrap(),
+            "Creates a new [`Button`][crate::Button].\n"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_trait_doc() {
+        assert_eq!(
+            trait_doc(None, "Button"),
+            "Trait containing all [`struct@Button`] methods."
+        );
+        assert_eq!(
+            trait_doc(Some("Methods of {type} and its subclasses."), "Button"),
+            "Methods of [`Button`][struct@crate::Button] and its subclasses."
+        );
+    }
+
+    #[test]
+    fn test_implements_doc() {
+        let implements = [
+            "[`ButtonExt`][trait@crate::prelude::ButtonExt]".to_owned(),
+            "[`WidgetExt`][trait@crate::prelude::WidgetExt]".to_owned(),
+            "[`ActionableExt`][trait@crate::prelude::ActionableExt]".to_owned(),
+        ];
+        let supertypes = [
+            "[`Widget`][crate::Widget]".to_owned(),
+            "[`Actionable`][crate::Actionable]".to_owned(),
+        ];
+        assert_eq!(
+            implements_doc(
+                ImplementsStyle::List,
+                &implements,
+                &supertypes,
+                "glib::prelude"
+            ),
+            "[`ButtonExt`][trait@crate::prelude::ButtonExt], \
+             [`WidgetExt`][trait@crate::prelude::WidgetExt], \
+             [`ActionableExt`][trait@crate::prelude::ActionableExt]"
+        );
+        assert_eq!(
+            implements_doc(
+                ImplementsStyle::IsA,
+                &implements,
+                &supertypes,
+                "glib::prelude"
+            ),
+            "[`ButtonExt`][trait@crate::prelude::ButtonExt], \
+             [`WidgetExt`][trait@crate::prelude::WidgetExt], \
+             [`ActionableExt`][trait@crate::prelude::ActionableExt]\n\n\
+             It implements [`IsA`][glib::prelude::IsA] for [`Widget`][crate::Widget], \
+             [`Actionable`][crate::Actionable], so it can be used where they are expected \
+             and upcast to them with [`upcast()`][glib::prelude::Cast::upcast()]."
+        );
+        assert_eq!(
+            implements_doc(ImplementsStyle::IsA, &implements[..1], &[], "glib::prelude"),
+            "[`ButtonExt`][trait@crate::prelude::ButtonExt]"
+        );
+    }
+
+    #[test]
+    fn test_fix_param_names() {
+        let self_name = Some("widget".to_owned());
+        assert_eq!(
+            fix_param_names(
+                "Sets the @type of @widget, see @priv",
+                &self_name,
+                Some(&["widget", "type"])
+            ),
+            "Sets the @type_ of @self, see @priv"
+        );
+        assert_eq!(
+            fix_param_names("The @type property", &None, None),
+            "The @type_ property"
+        );
+    }
+
+    #[test]
+    fn test_self_parameter() {
+        // The `gpointer` made the instance of a record method by the analysis
+        let self_name = self_parameter(Some("boxed"), &[]).map(str::to_owned);
+        assert_eq!(self_name.as_deref(), Some("boxed"));
+        assert_eq!(
+            fix_param_names(
+                "Frees @boxed, allocated like @src",
+                &self_name,
+                Some(&["boxed", "src"])
+            ),
+            "Frees @self, allocated like @src"
+        );
+        assert_eq!(self_parameter(None, &[]), None);
+    }
+
+    #[test]
+    fn test_rephrase_array_lengths() {
+        let lengths = [("n_attributes", Some("attributes")), ("n_children", None)];
+        assert_eq!(
+            rephrase_array_lengths(
+                "Sets @n_attributes values, @n_children is the number of children of @widget",
+                &lengths
+            ),
+            "Sets the length of @attributes values, the length of the returned array is the \
+             number of children of @widget"
+        );
+        assert_eq!(
+            rephrase_array_lengths("Uses @n_items", &[]),
+            "Uses @n_items"
+        );
+    }
+
+    #[test]
+    fn test_rephrase_error() {
+        // From the glib `g_file_get_contents` docs
+        let doc = "If the call was not successful, it returns %FALSE and sets @error. \
+                   The error domain is %G_FILE_ERROR. Possible error codes are those in the \
+                   #GFileError enumeration, like %G_FILE_ERROR_NOENT for a missing @filename.";
+        assert_eq!(
+            rephrase_error(doc, Some("error"), "glib::Error"),
+            "If the call was not successful, it returns %FALSE and sets the [`glib::Error`] \
+             of the returned `Result`. The error domain is %G_FILE_ERROR. Possible error codes \
+             are those in the #GFileError enumeration, like %G_FILE_ERROR_NOENT for a missing \
+             @filename."
+        );
+        assert_eq!(
+            rephrase_error("Sets @error", None, "glib::Error"),
+            "Sets @error"
+        );
+    }
+
+    #[test]
+    fn test_warn_missing_doc() {
+        let config = DocConfig {
+            missing_docs: vec![DocItemKind::Function],
+            ..Default::default()
+        };
+        let name = || "Gtk.Widget.show".to_owned();
+        assert!(warn_missing_doc(
+            &config,
+            DocItemKind::Function,
+            false,
+            name
+        ));
+        assert!(!warn_missing_doc(
+            &config,
+            DocItemKind::Function,
+            true,
+            name
+        ));
+        // Only the configured kinds are reported
+        assert!(!warn_missing_doc(&config, DocItemKind::Signal, false, name));
+        assert!(!warn_missing_doc(
+            &DocConfig::default(),
+            DocItemKind::Function,
+            false,
+            name
+        ));
+    }
+
+    #[test]
+    fn test_stability_banner() {
+        let config = DocConfig {
+            unstable_banner: Some("**Experimental:** this API may change".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            stability_banner(&config, Stability::Unstable),
+            Some("**Experimental:** this API may change")
+        );
+        assert_eq!(stability_banner(&config, Stability::Stable), None);
+        assert_eq!(
+            stability_banner(&DocConfig::default(), Stability::Unstable),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reexport_summary() {
+        let doc = "A #GtkButton is a widget that emits a signal when clicked\non. It can hold any child.\n\nMore details.";
+        assert_eq!(
+            to_doc_comment(&summary(doc).unwrap()),
+            "/// A #GtkButton is a widget that emits a signal when clicked on."
+        );
+        assert_eq!(
+            summary("Sets the label\nof the button").unwrap(),
+            "Sets the label of the button"
+        );
+        assert_eq!(summary("  \n"), None);
+    }
+}
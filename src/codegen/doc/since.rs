@@ -0,0 +1,152 @@
+use crate::{
+    codegen::doc::format::{
+        gen_const_doc_link, gen_object_fn_doc_link, gen_symbol_doc_link, gen_type_fn_doc_link,
+        DocContext,
+    },
+    library::{TypeId, MAIN_NAMESPACE},
+    version::Version,
+    Env,
+};
+use std::{
+    collections::BTreeMap,
+    io::{Result, Write},
+};
+
+/// Writes the index of the items of the library with a section for each version
+/// introducing some, see `doc.since_index`
+pub fn generate(w: &mut dyn Write, env: &Env) -> Result<()> {
+    let symbols = env.symbols.borrow();
+    let ctx = DocContext::new(env, &symbols);
+    writeln!(w, "# Since")?;
+    for (version, links) in by_version(items(&ctx)) {
+        writeln!(w, "\n## {}\n", version)?;
+        for link in links {
+            writeln!(w, "- {}", link)?;
+        }
+    }
+    Ok(())
+}
+
+/// The links to the items with the version they were introduced in
+fn items(env: &DocContext<'_>) -> Vec<(Option<Version>, String)> {
+    let mut items = Vec::new();
+
+    for info in env.analysis.objects.values() {
+        if info.type_id.ns_id != MAIN_NAMESPACE {
+            continue;
+        }
+        items.push((info.version, gen_symbol_doc_link(info.type_id, env)));
+        items.extend(
+            info.functions
+                .iter()
+                .filter(|f| f.should_be_doc_linked(env.library))
+                .map(|f| {
+                    let link = gen_object_fn_doc_link(info, f, env, None, &info.name);
+                    (f.version, link)
+                }),
+        );
+    }
+
+    for info in env.analysis.records.values() {
+        if info.type_id.ns_id != MAIN_NAMESPACE {
+            continue;
+        }
+        items.push((info.version, gen_symbol_doc_link(info.type_id, env)));
+        items.extend(type_functions(env, info.type_id, &info.functions));
+    }
+
+    for info in &env.analysis.enumerations {
+        let version = info.type_(env.library).version;
+        items.push((version, gen_symbol_doc_link(info.type_id, env)));
+        items.extend(type_functions(env, info.type_id, &info.functions));
+    }
+
+    for info in &env.analysis.flags {
+        let version = info.type_(env.library).version;
+        items.push((version, gen_symbol_doc_link(info.type_id, env)));
+        items.extend(type_functions(env, info.type_id, &info.functions));
+    }
+
+    if let Some(ref global_functions) = env.analysis.global_functions {
+        items.extend(
+            global_functions
+                .functions
+                .iter()
+                .filter(|f| f.should_be_doc_linked(env.library))
+                .map(|f| (f.version, f.doc_link(None, None, false))),
+        );
+    }
+
+    items.extend(
+        env.analysis
+            .constants
+            .iter()
+            .map(|c| (c.version, gen_const_doc_link(c))),
+    );
+
+    items
+}
+
+fn type_functions<'a>(
+    env: &'a DocContext<'a>,
+    type_id: TypeId,
+    functions: &'a [crate::analysis::functions::Info],
+) -> impl Iterator<Item = (Option<Version>, String)> + 'a {
+    functions
+        .iter()
+        .filter(move |f| f.should_be_doc_linked(env.library))
+        .map(move |f| (f.version, gen_type_fn_doc_link(type_id, f, env, None)))
+}
+
+/// Group the items by version, the oldest first, the ones without one are left out
+fn by_version(
+    items: impl IntoIterator<Item = (Option<Version>, String)>,
+) -> BTreeMap<Version, Vec<String>> {
+    let mut versions: BTreeMap<Version, Vec<String>> = BTreeMap::new();
+    for (version, link) in items {
+        if let Some(version) = version {
+            versions.entry(version).or_default().push(link);
+        }
+    }
+    versions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_version() {
+        let versions = by_version([
+            (
+                Some(Version(4, 2, 0)),
+                "[`Widget::width()`][crate::prelude::WidgetExt::width()]".to_owned(),
+            ),
+            (None, "[`Widget`][crate::Widget]".to_owned()),
+            (
+                Some(Version(4, 0, 0)),
+                "[`Button::new()`][crate::Button::new()]".to_owned(),
+            ),
+            (
+                Some(Version(4, 2, 0)),
+                "[`Picture`][crate::Picture]".to_owned(),
+            ),
+        ]);
+        assert_eq!(
+            versions.into_iter().collect::<Vec<_>>(),
+            [
+                (
+                    Version(4, 0, 0),
+                    vec!["[`Button::new()`][crate::Button::new()]".to_owned()]
+                ),
+                (
+                    Version(4, 2, 0),
+                    vec![
+                        "[`Widget::width()`][crate::prelude::WidgetExt::width()]".to_owned(),
+                        "[`Picture`][crate::Picture]".to_owned(),
+                    ]
+                ),
+            ]
+        );
+    }
+}
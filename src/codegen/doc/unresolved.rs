@@ -0,0 +1,316 @@
+use crate::{analysis::object::LocationInObject, file_saver::save_to_file, library::TypeId, Env};
+use log::{info, log, Level};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+    io::{self, Write},
+    mem,
+    sync::Mutex,
+};
+
+/// What an unresolved doc reference was looked up as
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RefKind {
+    Function,
+    Type,
+    Member,
+    Constant,
+}
+
+impl Display for RefKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RefKind::Function => "function",
+            RefKind::Type => "type",
+            RefKind::Member => "member",
+            RefKind::Constant => "constant",
+        })
+    }
+}
+
+/// A reference of the C docs without a Rust equivalent, `location` is the type whose
+/// docs contain it or empty for the global items
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnresolvedRef {
+    pub symbol: String,
+    pub kind: RefKind,
+    pub location: String,
+}
+
+/// The unresolved references met while formatting the docs, see `doc.unresolved_report`
+#[derive(Debug, Default)]
+pub struct UnresolvedRefs(Mutex<BTreeSet<UnresolvedRef>>);
+
+impl UnresolvedRefs {
+    pub fn insert(&self, unresolved: UnresolvedRef) {
+        self.0.lock().unwrap().insert(unresolved);
+    }
+}
+
+/// How many times each unresolved reference was met, see `doc.verbose_warnings`
+#[derive(Debug, Default)]
+pub struct LoggedRefs(Mutex<BTreeMap<(RefKind, String), usize>>);
+
+impl LoggedRefs {
+    /// Counts an occurrence of the reference, returns whether it's the first one
+    pub(super) fn first(&self, kind: RefKind, symbol: &str) -> bool {
+        let mut counts = self.0.lock().unwrap();
+        let count = counts.entry((kind, symbol.to_owned())).or_insert(0);
+        *count += 1;
+        *count == 1
+    }
+}
+
+/// A reference a doc couldn't resolve, returned with the formatted doc for the caller to
+/// log and record, see [`report`]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Miss {
+    pub kind: RefKind,
+    pub symbol: String,
+    /// The message logged at its first occurrence, if any
+    pub log: Option<(Level, String)>,
+    /// Whether it's kept for `doc.unresolved_report` and `doc.strict`, the expected misses
+    /// aren't
+    pub record: bool,
+}
+
+/// Logs and records the references a doc of `in_type` left unresolved
+pub(super) fn report(
+    env: &Env,
+    misses: &[Miss],
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) {
+    for miss in misses {
+        if let Some((level, ref message)) = miss.log {
+            if should_log(env, miss.kind, &miss.symbol) {
+                log!(level, "{}", message);
+            }
+        }
+        if miss.record {
+            record(env, miss.kind, &miss.symbol, in_type);
+        }
+    }
+}
+
+/// Whether to log that a reference couldn't be resolved, as the same one is usually
+/// met in many docs only its first occurrence is logged unless `doc.verbose_warnings`
+/// is set
+fn should_log(env: &Env, kind: RefKind, symbol: &str) -> bool {
+    env.doc_logged.first(kind, symbol) || env.config.doc.verbose_warnings
+}
+
+/// Runs `f` without keeping the references it leaves unresolved in `env`, they are
+/// still logged at their first occurrence in `f`
+pub(super) fn without_recording<T>(env: &Env, f: impl FnOnce() -> T) -> T {
+    let unresolved = mem::take(&mut *env.doc_unresolved.0.lock().unwrap());
+    let logged = mem::take(&mut *env.doc_logged.0.lock().unwrap());
+    let ret = f();
+    *env.doc_unresolved.0.lock().unwrap() = unresolved;
+    *env.doc_logged.0.lock().unwrap() = logged;
+    ret
+}
+
+/// Logs how many times the unresolved references were met when it's more than once
+pub(super) fn log_summary(env: &Env) {
+    if let Some(summary) = summary(&env.doc_logged.0.lock().unwrap()) {
+        info!("{}", summary);
+    }
+}
+
+fn summary(counts: &BTreeMap<(RefKind, String), usize>) -> Option<String> {
+    let mut repeated: Vec<_> = counts.iter().filter(|(_, count)| **count > 1).collect();
+    if repeated.is_empty() {
+        return None;
+    }
+    repeated.sort_by(|(_, a), (_, b)| b.cmp(a));
+    let mut out = format!(
+        "{} unresolved doc references were met several times:",
+        repeated.len()
+    );
+    for ((kind, symbol), count) in repeated {
+        out.push_str(&format!("\n    {} `{}`: {} times", kind, symbol, count));
+    }
+    Some(out)
+}
+
+/// Keeps track of an unresolved reference when `doc.unresolved_report` or `doc.strict`
+/// is set
+fn record(
+    env: &Env,
+    kind: RefKind,
+    symbol: &str,
+    in_type: Option<(&TypeId, Option<LocationInObject>)>,
+) {
+    if env.config.doc.unresolved_report.is_none() && !env.config.doc.strict {
+        return;
+    }
+    env.doc_unresolved.insert(UnresolvedRef {
+        symbol: symbol.to_owned(),
+        kind,
+        location: in_type.map_or_else(String::new, |(tid, _)| tid.full_name(&env.library)),
+    });
+}
+
+/// Writes the unresolved references as JSON or TSV depending on the extension of
+/// `doc.unresolved_report`
+pub(super) fn generate(env: &Env) {
+    let path = match env.config.doc.unresolved_report {
+        Some(ref path) => path,
+        None => return,
+    };
+    info!("Generating unresolved references report {:?}", path);
+    let refs = env.doc_unresolved.0.lock().unwrap();
+    let json = path.extension().map_or(false, |ext| ext == "json");
+    save_to_file(path, env.config.make_backup, |w| {
+        if json {
+            write_json(w, &refs)
+        } else {
+            write_tsv(w, &refs)
+        }
+    });
+}
+
+/// Fails with the list of the unresolved references in `doc.strict` mode
+pub fn check_strict(env: &Env) -> Result<(), String> {
+    if !env.config.doc.strict {
+        return Ok(());
+    }
+    check(&env.doc_unresolved.0.lock().unwrap())
+}
+
+fn check(refs: &BTreeSet<UnresolvedRef>) -> Result<(), String> {
+    if refs.is_empty() {
+        return Ok(());
+    }
+    let mut err = format!("{} unresolved doc references:", refs.len());
+    for r in refs {
+        err.push_str(&format!("\n    {} `{}`", r.kind, r.symbol));
+        if !r.location.is_empty() {
+            err.push_str(&format!(" in {}", r.location));
+        }
+    }
+    Err(err)
+}
+
+fn write_tsv(w: &mut dyn Write, refs: &BTreeSet<UnresolvedRef>) -> io::Result<()> {
+    writeln!(w, "symbol\tkind\tlocation")?;
+    for r in refs {
+        writeln!(w, "{}\t{}\t{}", r.symbol, r.kind, r.location)?;
+    }
+    Ok(())
+}
+
+fn write_json(w: &mut dyn Write, refs: &BTreeSet<UnresolvedRef>) -> io::Result<()> {
+    writeln!(w, "[")?;
+    for (i, r) in refs.iter().enumerate() {
+        let comma = if i + 1 < refs.len() { "," } else { "" };
+        writeln!(
+            w,
+            "  {{\"symbol\": {}, \"kind\": \"{}\", \"location\": {}}}{}",
+            json_string(&r.symbol),
+            r.kind,
+            json_string(&r.location),
+            comma
+        )?;
+    }
+    writeln!(w, "]")
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refs() -> UnresolvedRefs {
+        let refs = UnresolvedRefs::default();
+        refs.insert(UnresolvedRef {
+            symbol: "gtk_widget_foo".to_owned(),
+            kind: RefKind::Function,
+            location: "Gtk.Widget".to_owned(),
+        });
+        refs.insert(UnresolvedRef {
+            symbol: "GtkBar".to_owned(),
+            kind: RefKind::Type,
+            location: String::new(),
+        });
+        refs.insert(UnresolvedRef {
+            symbol: "gtk_widget_foo".to_owned(),
+            kind: RefKind::Function,
+            location: "Gtk.Widget".to_owned(),
+        });
+        refs
+    }
+
+    #[test]
+    fn test_logged_once() {
+        let logged = LoggedRefs::default();
+        // The same missing type in two docs
+        assert!(logged.first(RefKind::Type, "GtkBar"));
+        assert!(!logged.first(RefKind::Type, "GtkBar"));
+        assert!(logged.first(RefKind::Function, "GtkBar"));
+        assert!(logged.first(RefKind::Function, "gtk_widget_foo"));
+        assert!(!logged.first(RefKind::Function, "gtk_widget_foo"));
+        assert!(!logged.first(RefKind::Function, "gtk_widget_foo"));
+
+        assert_eq!(
+            summary(&logged.0.lock().unwrap()).as_deref(),
+            Some(
+                "2 unresolved doc references were met several times:
+    function `gtk_widget_foo`: 3 times
+    type `GtkBar`: 2 times"
+            )
+        );
+        assert_eq!(summary(&BTreeMap::new()), None);
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check(&BTreeSet::new()), Ok(()));
+        assert_eq!(
+            check(&refs().0.lock().unwrap()),
+            Err("2 unresolved doc references:
+    type `GtkBar`
+    function `gtk_widget_foo` in Gtk.Widget"
+                .to_owned())
+        );
+    }
+
+    #[test]
+    fn test_write_tsv() {
+        let mut w = Vec::new();
+        write_tsv(&mut w, &refs().0.lock().unwrap()).unwrap();
+        assert_eq!(
+            String::from_utf8(w).unwrap(),
+            "symbol\tkind\tlocation\nGtkBar\ttype\t\ngtk_widget_foo\tfunction\tGtk.Widget\n"
+        );
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut w = Vec::new();
+        write_json(&mut w, &refs().0.lock().unwrap()).unwrap();
+        assert_eq!(
+            String::from_utf8(w).unwrap(),
+            r#"[
+  {"symbol": "GtkBar", "kind": "type", "location": ""},
+  {"symbol": "gtk_widget_foo", "kind": "function", "location": "Gtk.Widget"}
+]
+"#
+        );
+        assert_eq!(json_string("a \"b\"\n"), r#""a \"b\"\u000a""#);
+    }
+}
@@ -34,6 +34,7 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
     let path = root_path.join("enums.rs");
     file_saver::save_to_file(path, env.config.make_backup, |w| {
         general::start_comments(w, &env.config)?;
+        general::module_doc(w, env, "enums", "Enumerations")?;
         general::uses(w, env, &env.analysis.enum_imports, None)?;
         writeln!(w)?;
 
@@ -52,6 +53,9 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
             if let Some(cfg) = cfg_condition_string(config.cfg_condition.as_ref(), false, 0) {
                 mod_rs.push(cfg);
             }
+            if let Some(doc) = super::doc::reexport_doc(env, enum_analysis.type_id) {
+                mod_rs.push(doc);
+            }
             mod_rs.push(format!(
                 "{} use self::enums::{};",
                 enum_analysis.visibility.export_visibility(),
@@ -66,7 +66,7 @@ pub fn generate(
         return Ok(());
     }
 
-    if analysis.is_async_finish(env) {
+    if analysis.is_async_finish(&env.library) {
         return Ok(());
     }
 
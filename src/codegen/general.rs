@@ -46,6 +46,38 @@ pub fn start_comments_no_version(w: &mut dyn Write, conf: &Config) -> Result<()>
     )
 }
 
+/// Writes the `//!` docs of a generated submodule like `functions`, see `doc.module_docs`
+pub fn module_doc(w: &mut dyn Write, env: &Env, module: &str, contents: &str) -> Result<()> {
+    match env.config.doc.module_doc_template {
+        Some(ref template) => {
+            write_module_doc(w, template, &env.config.library_name, module, contents)
+        }
+        None => Ok(()),
+    }
+}
+
+fn write_module_doc(
+    w: &mut dyn Write,
+    template: &str,
+    library: &str,
+    module: &str,
+    contents: &str,
+) -> Result<()> {
+    let doc = template
+        .replace("{contents}", contents)
+        .replace("{module}", module)
+        .replace("{library}", library);
+    writeln!(w)?;
+    for line in doc.lines() {
+        if line.is_empty() {
+            writeln!(w, "//!")?;
+        } else {
+            writeln!(w, "//! {}", line)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn single_version_file(w: &mut dyn Write, conf: &Config, prefix: &str) -> Result<()> {
     write!(
         w,
@@ -999,4 +1031,43 @@ mod tests {
         assert_eq!(escape_string("no escaping here"), "no escaping here");
         assert_eq!(escape_string(r#"'"\"#), r#"'\"\\"#);
     }
+
+    #[test]
+    fn test_module_doc() {
+        let mut w = Vec::new();
+        write_module_doc(
+            &mut w,
+            "{contents} of {library}.\n\nSee the `{module}` module.",
+            "Gtk",
+            "functions",
+            "Global functions",
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(w).unwrap(),
+            "
+//! Global functions of Gtk.
+//!
+//! See the `functions` module.
+"
+        );
+    }
+
+    #[test]
+    fn test_cfg_condition_string() {
+        // The feature-gated items also get their `doc(cfg)` when building the docs with
+        // the `dox` feature, which enables the `doc_cfg` crate feature
+        let cfg = Version(4, 2, 0).to_cfg(None);
+        assert_eq!(
+            cfg_condition_string(Some(&cfg), false, 1).unwrap(),
+            "    #[cfg(any(feature = \"v4_2\", feature = \"dox\"))]
+    #[cfg_attr(feature = \"dox\", doc(cfg(feature = \"v4_2\")))]"
+        );
+        assert_eq!(
+            cfg_condition_string(Some(&cfg), true, 0).unwrap(),
+            "//#[cfg(any(feature = \"v4_2\", feature = \"dox\"))]
+//#[cfg_attr(feature = \"dox\", doc(cfg(feature = \"v4_2\")))]"
+        );
+        assert_eq!(cfg_condition_string(None::<&String>, false, 0), None);
+    }
 }
@@ -11,6 +11,10 @@ mod bound;
 mod child_properties;
 mod constants;
 mod doc;
+pub use doc::{
+    check_strict as check_strict_doc, preview as preview_doc, reformat_standalone_doc, DocCache,
+    DocResolver, LoggedRefs, UnresolvedRefs,
+};
 mod enums;
 mod flags;
 pub mod function;
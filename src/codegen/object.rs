@@ -621,6 +621,9 @@ pub fn generate_reexports(
     contents.extend_from_slice(&cfgs);
     contents.push(format!("mod {};", module_name));
     contents.extend_from_slice(&cfgs);
+    if let Some(doc) = super::doc::reexport_doc(env, analysis.type_id) {
+        contents.push(doc);
+    }
 
     contents.push(format!(
         "{} use self::{}::{};",
@@ -171,6 +171,10 @@ pub fn generate_reexports(
     };
     contents.push("".to_owned());
     contents.push(format!("{}mod {};", cfg, module_name));
+    if let Some(doc) = super::doc::reexport_doc(env, analysis.type_id) {
+        cfg.push_str(&doc);
+        cfg.push('\n');
+    }
     contents.push(format!(
         "{}{} use self::{}::{};",
         cfg,
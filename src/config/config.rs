@@ -1,4 +1,5 @@
 use super::{
+    doc::{read_doc_config, read_doc_examples, DocConfig},
     external_libraries::{read_external_libraries, ExternalLibrary},
     gobjects, WorkMode,
 };
@@ -91,6 +92,7 @@ impl GirVersion {
 #[derive(Debug)]
 pub struct Config {
     pub work_mode: WorkMode,
+    pub config_file: PathBuf,
     pub girs_dirs: Vec<PathBuf>,
     // Version in girs_dirs, detected by git
     pub girs_version: Vec<GirVersion>,
@@ -119,6 +121,7 @@ pub struct Config {
     pub lib_version_overrides: HashMap<Version, Version>,
     pub feature_dependencies: HashMap<Version, Vec<String>>,
     pub dox_feature_dependencies: Vec<String>,
+    pub doc: DocConfig,
 }
 
 impl Config {
@@ -346,9 +349,15 @@ impl Config {
         let lib_version_overrides = read_lib_version_overrides(&toml)?;
         let feature_dependencies = read_feature_dependencies(&toml)?;
         let dox_feature_dependencies = read_dox_feature_dependencies(&toml)?;
+        let mut doc = read_doc_config(&toml)?;
+        doc.examples = read_doc_examples(&toml, &config_dir)?;
+        doc.sidecar_dir = doc.sidecar_dir.map(|dir| config_dir.join(dir));
+        doc.unresolved_report = doc.unresolved_report.map(|path| config_dir.join(path));
+        doc.cache = doc.cache.map(|path| config_dir.join(path));
 
         Ok(Config {
             work_mode,
+            config_file,
             girs_dirs,
             girs_version,
             library_name,
@@ -375,6 +384,7 @@ impl Config {
             lib_version_overrides,
             feature_dependencies,
             dox_feature_dependencies,
+            doc,
         })
     }
 
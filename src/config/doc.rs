@@ -0,0 +1,1148 @@
+use super::error::TomlHelper;
+use crate::version::Version;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Which generated accessor a property reference links to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PropertyLinkTarget {
+    Getter,
+    Setter,
+}
+
+impl FromStr for PropertyLinkTarget {
+    type Err = String;
+    fn from_str(name: &str) -> Result<PropertyLinkTarget, String> {
+        match name {
+            "getter" => Ok(PropertyLinkTarget::Getter),
+            "setter" => Ok(PropertyLinkTarget::Setter),
+            _ => Err(format!("Unknown property link target '{}'", name)),
+        }
+    }
+}
+
+impl Default for PropertyLinkTarget {
+    fn default() -> PropertyLinkTarget {
+        PropertyLinkTarget::Getter
+    }
+}
+
+/// How the C functions referenced in the docs that can't be found are logged
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissingFunctionLog {
+    Info,
+    Debug,
+    Off,
+}
+
+impl MissingFunctionLog {
+    pub fn level(self) -> Option<log::Level> {
+        match self {
+            MissingFunctionLog::Info => Some(log::Level::Info),
+            MissingFunctionLog::Debug => Some(log::Level::Debug),
+            MissingFunctionLog::Off => None,
+        }
+    }
+}
+
+impl FromStr for MissingFunctionLog {
+    type Err = String;
+    fn from_str(name: &str) -> Result<MissingFunctionLog, String> {
+        match name {
+            "info" => Ok(MissingFunctionLog::Info),
+            "debug" => Ok(MissingFunctionLog::Debug),
+            "off" => Ok(MissingFunctionLog::Off),
+            _ => Err(format!("Unknown missing function log level '{}'", name)),
+        }
+    }
+}
+
+impl Default for MissingFunctionLog {
+    fn default() -> MissingFunctionLog {
+        MissingFunctionLog::Info
+    }
+}
+
+/// How references to the enum and flags members that aren't generated are resolved
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeprecatedMemberLink {
+    /// Render them as inline code
+    Off,
+    /// Link them to their variant anyway
+    Variant,
+    /// Add a note that the member is deprecated
+    Note,
+}
+
+impl FromStr for DeprecatedMemberLink {
+    type Err = String;
+    fn from_str(name: &str) -> Result<DeprecatedMemberLink, String> {
+        match name {
+            "off" => Ok(DeprecatedMemberLink::Off),
+            "variant" => Ok(DeprecatedMemberLink::Variant),
+            "note" => Ok(DeprecatedMemberLink::Note),
+            _ => Err(format!("Unknown deprecated member link '{}'", name)),
+        }
+    }
+}
+
+impl Default for DeprecatedMemberLink {
+    fn default() -> DeprecatedMemberLink {
+        DeprecatedMemberLink::Off
+    }
+}
+
+/// How the `# Implements` section of the docs of objects is written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImplementsStyle {
+    /// Only list the implemented traits
+    List,
+    /// Also explain the `IsA` relationship with the supertypes
+    IsA,
+}
+
+impl FromStr for ImplementsStyle {
+    type Err = String;
+    fn from_str(name: &str) -> Result<ImplementsStyle, String> {
+        match name {
+            "list" => Ok(ImplementsStyle::List),
+            "is_a" => Ok(ImplementsStyle::IsA),
+            _ => Err(format!("Unknown implements style '{}'", name)),
+        }
+    }
+}
+
+impl Default for ImplementsStyle {
+    fn default() -> ImplementsStyle {
+        ImplementsStyle::List
+    }
+}
+
+/// How the references to C symbols that can't be linked are written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnresolvedStyle {
+    /// Keep the C name as inline code
+    Backticks,
+    /// Keep the C name as plain text
+    Plain,
+    /// Remove the reference
+    Drop,
+}
+
+impl FromStr for UnresolvedStyle {
+    type Err = String;
+    fn from_str(name: &str) -> Result<UnresolvedStyle, String> {
+        match name {
+            "backticks" => Ok(UnresolvedStyle::Backticks),
+            "plain" => Ok(UnresolvedStyle::Plain),
+            "drop" => Ok(UnresolvedStyle::Drop),
+            _ => Err(format!("Unknown unresolved style '{}'", name)),
+        }
+    }
+}
+
+impl Default for UnresolvedStyle {
+    fn default() -> UnresolvedStyle {
+        UnresolvedStyle::Backticks
+    }
+}
+
+/// The text of the links to the C types, functions and constants
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkText {
+    /// The name of the Rust item, like `Widget`
+    Rust,
+    /// The C name of the reference, like `GtkWidget`
+    C,
+}
+
+impl FromStr for LinkText {
+    type Err = String;
+    fn from_str(name: &str) -> Result<LinkText, String> {
+        match name {
+            "rust" => Ok(LinkText::Rust),
+            "c" => Ok(LinkText::C),
+            _ => Err(format!("Unknown link text '{}'", name)),
+        }
+    }
+}
+
+impl Default for LinkText {
+    fn default() -> LinkText {
+        LinkText::Rust
+    }
+}
+
+/// The kinds of items `doc.missing_docs` warns about
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocItemKind {
+    /// The objects, interfaces, records, enums and flags
+    Type,
+    Function,
+    Signal,
+    Property,
+    /// The enum and flags members
+    Member,
+}
+
+impl FromStr for DocItemKind {
+    type Err = String;
+    fn from_str(name: &str) -> Result<DocItemKind, String> {
+        match name {
+            "type" => Ok(DocItemKind::Type),
+            "function" => Ok(DocItemKind::Function),
+            "signal" => Ok(DocItemKind::Signal),
+            "property" => Ok(DocItemKind::Property),
+            "member" => Ok(DocItemKind::Member),
+            _ => Err(format!("Unknown documented item kind '{}'", name)),
+        }
+    }
+}
+
+impl fmt::Display for DocItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DocItemKind::Type => "Type",
+            DocItemKind::Function => "Function",
+            DocItemKind::Signal => "Signal",
+            DocItemKind::Property => "Property",
+            DocItemKind::Member => "Member",
+        })
+    }
+}
+
+/// The `//!` docs of the generated submodules if `doc.module_docs` is set without a template
+const DEFAULT_MODULE_DOC_TEMPLATE: &str = "{contents} of {library}.";
+
+/// The C types that are never linked unless `doc.ignored_types` removes them
+pub const DEFAULT_IGNORED_TYPES: [&str; 6] = [
+    "gconstpointer",
+    "guint16",
+    "guint",
+    "gunicode",
+    "gchararray",
+    "GList",
+];
+
+/// A Rust example added to the docs of an item as a doctest
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocExample {
+    pub code: String,
+    pub no_run: bool,
+}
+
+/// The Rust path a C symbol of the docs is linked to, read from `[[doc.link_overrides]]`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocLinkOverride {
+    /// Without the `crate::` prefix, like `Widget::show`
+    pub rust_link: String,
+    /// Also replace the link found automatically, else only the unresolved references
+    pub force: bool,
+}
+
+/// The titles of the sections added to the docs, read from `[doc.section_titles]`
+#[derive(Debug, PartialEq, Eq)]
+pub struct SectionTitles {
+    pub implements: String,
+    pub implementors: String,
+    pub deprecated: String,
+    /// Where `{version}` is replaced by the version the item is deprecated since
+    pub deprecated_since: String,
+    pub returns: String,
+    pub examples: String,
+    pub arguments: String,
+}
+
+impl SectionTitles {
+    pub fn deprecated_since(&self, version: impl Display) -> String {
+        self.deprecated_since
+            .replace("{version}", &version.to_string())
+    }
+}
+
+impl Default for SectionTitles {
+    fn default() -> SectionTitles {
+        SectionTitles {
+            implements: "Implements".to_owned(),
+            implementors: "Implementors".to_owned(),
+            deprecated: "Deprecated".to_owned(),
+            deprecated_since: "Deprecated since {version}".to_owned(),
+            returns: "Returns".to_owned(),
+            examples: "Examples".to_owned(),
+            arguments: "Arguments".to_owned(),
+        }
+    }
+}
+
+/// Options controlling how the upstream C documentation is converted, read
+/// from the `[doc]` table.
+#[derive(Debug)]
+pub struct DocConfig {
+    /// Only link to items that are available in every version the documented
+    /// item is available in.
+    pub version_aware_links: bool,
+    pub property_link_target: PropertyLinkTarget,
+    /// Path linked from type check macros like `GTK_IS_WIDGET()`, an empty
+    /// string renders them as inline code.
+    pub type_check_macro_link: Option<String>,
+    /// Path linked from type cast macros like `GTK_WIDGET()`, an empty
+    /// string renders them as inline code.
+    pub type_cast_macro_link: Option<String>,
+    /// Expand tab characters to this many spaces, tabs are kept if unset.
+    pub tab_width: Option<usize>,
+    /// Put the summary of a type's docs on its `pub use` re-export.
+    pub reexport_docs: bool,
+    /// Link references to async functions to their `_future` variant.
+    pub prefer_future_links: bool,
+    /// Add a table of the parameters to the docs of signals with several parameters.
+    pub signal_parameter_tables: bool,
+    /// Put on top of the docs of items the GIR marks as unstable, no banner if unset.
+    pub unstable_banner: Option<String>,
+    /// Rust paths of C types from other crates, like `GMainContext = "glib::MainContext"`,
+    /// in addition to the built-in glib ones.
+    pub type_links: BTreeMap<String, String>,
+    /// Turn the bare URLs of the docs into autolinks.
+    pub autolink_urls: bool,
+    pub missing_function_log: MissingFunctionLog,
+    /// Examples read from the `doc.examples` file, by item path like `Button.new`.
+    pub examples: BTreeMap<String, DocExample>,
+    /// Template of the `//!` docs of the generated submodules, no docs if unset.
+    pub module_doc_template: Option<String>,
+    /// Lines put hidden with `# ` at the start of the Rust examples, like imports.
+    pub example_setup: Vec<String>,
+    pub deprecated_member_links: DeprecatedMemberLink,
+    /// Turn the "See A and B." sentences made only of links into a list of the links.
+    pub see_also_lists: bool,
+    /// Render the absolute file paths like `/etc/foo.conf` as inline code.
+    pub inline_file_paths: bool,
+    pub section_titles: SectionTitles,
+    /// Write a `c_symbols.md` page next to the docs linking each C symbol to its Rust equivalent.
+    pub c_symbols_index: bool,
+    /// Render the DocBook `<keycombo>` key combinations like `` `Ctrl`+`C` ``.
+    pub key_combos: bool,
+    pub implements_style: ImplementsStyle,
+    /// Document the parameters of functions as a list in an `# Arguments` section
+    /// instead of a heading each.
+    pub argument_lists: bool,
+    /// Move the code blocks of several lines to an `# Examples` section at the end of
+    /// the docs.
+    pub examples_section: bool,
+    /// The version the C symbols of `[doc.removed_items]` were removed in, their
+    /// references get a "removed in" note.
+    pub removed_items: BTreeMap<String, Version>,
+    /// Trim the trailing whitespace of the lines outside of the code blocks.
+    pub trim_trailing_whitespace: bool,
+    /// Link the backticked types like `` `GtkWidget` ``, else they are kept as inline code.
+    pub link_backticked_types: bool,
+    /// Template of the summary of the `Ext` traits, where `{type}` is replaced by
+    /// the link to their type.
+    pub trait_doc_template: Option<String>,
+    /// Turn the docs made of a single heading into a summary line.
+    pub demote_lone_headings: bool,
+    /// Rust paths of the `%SYMBOL` references without a generated constant, like the
+    /// log domains or the error quark macros.
+    pub symbol_links: BTreeMap<String, String>,
+    /// Directory where the docs of each item are also written to a `Button.new.md` like
+    /// file, relative to the config file.
+    pub sidecar_dir: Option<PathBuf>,
+    /// Replace the non ASCII characters of the docs outside of the code blocks.
+    pub ascii_only: bool,
+    /// Rust paths of the C functions without a generated equivalent, the glib signal
+    /// functions like `g_signal_emit_by_name` are already known.
+    pub function_links: BTreeMap<String, String>,
+    /// Write a `since.md` page next to the docs listing the items introduced in each version.
+    pub since_index: bool,
+    /// The kinds of items to warn about when they have no documentation at all.
+    pub missing_docs: Vec<DocItemKind>,
+    /// Code hidden at the start of every generated Rust doctest, before `example_setup`.
+    pub doctest_prelude: Option<String>,
+    /// Mark the code blocks in another language than Rust `ignore` for rustdoc.
+    pub ignore_foreign_examples: bool,
+    /// File where the doc references without a Rust equivalent are listed, as JSON if
+    /// it has the `.json` extension or as TSV otherwise, relative to the config file.
+    pub unresolved_report: Option<PathBuf>,
+    /// Fail the generation when some doc references can't be resolved.
+    pub strict: bool,
+    /// Log the unresolved doc references every time they are met, not only the first one.
+    pub verbose_warnings: bool,
+    /// C functions and function-like macros never reported as unresolved, on top of the
+    /// usual `g_free` and `G_DEFINE_TYPE` like ones.
+    pub ignored_functions: Vec<String>,
+    /// C types never linked nor reported as unresolved, on top of the
+    /// [`DEFAULT_IGNORED_TYPES`]. The ones starting with `!` remove a default instead, the
+    /// `GLib.GVariant` like entries only apply to the types of this namespace.
+    pub ignored_types: Vec<String>,
+    /// C constants and enum members never reported as unresolved, the ones ending with `*`
+    /// cover all the constants with this prefix. The `Gst.GST_PAD_*` like entries only
+    /// apply to the constants of this namespace.
+    pub ignored_constants: Vec<String>,
+    /// How the C functions, types and constants that can't be linked are written.
+    pub unresolved_style: UnresolvedStyle,
+    /// Rust paths of the C functions and types, by C name, that are used when they
+    /// can't be linked automatically.
+    pub link_overrides: BTreeMap<String, DocLinkOverride>,
+    /// Backslash-escape the markdown characters of the plain text that would be taken
+    /// for formatting, like the `*` of `*nix`.
+    pub escape_markdown: bool,
+    /// Namespaces whose docs predate gi-docgen, they skip the gi-docgen pass even with
+    /// `options.use_gi_docgen`.
+    pub skip_gi_docgen: Vec<String>,
+    /// Wrap the prose lines of the docs longer than this many columns.
+    pub wrap_columns: Option<usize>,
+    /// File where the formatted docs are kept between the runs, relative to the config
+    /// file. It's not used with `doc.strict` nor `doc.unresolved_report`.
+    pub cache: Option<PathBuf>,
+    /// Only keep the first paragraph of the docs, unless overridden with the
+    /// `doc_summary_only` of the object.
+    pub summary_only: bool,
+    /// Note after the links to types and functions the version they were introduced in,
+    /// when newer than the minimum supported one, and the one they are deprecated since.
+    pub availability_notes: bool,
+    /// Whether the links to the C types, functions and constants show their Rust or C name.
+    pub link_text: LinkText,
+    /// Link the C types of the namespace the prose mentions without a sigil or backticks.
+    pub bare_type_links: bool,
+}
+
+impl Default for DocConfig {
+    fn default() -> DocConfig {
+        DocConfig {
+            version_aware_links: false,
+            property_link_target: Default::default(),
+            type_check_macro_link: None,
+            type_cast_macro_link: None,
+            tab_width: None,
+            reexport_docs: false,
+            prefer_future_links: false,
+            signal_parameter_tables: false,
+            unstable_banner: None,
+            type_links: BTreeMap::new(),
+            autolink_urls: false,
+            missing_function_log: Default::default(),
+            examples: BTreeMap::new(),
+            module_doc_template: None,
+            example_setup: Vec::new(),
+            deprecated_member_links: Default::default(),
+            see_also_lists: false,
+            inline_file_paths: false,
+            section_titles: Default::default(),
+            c_symbols_index: false,
+            key_combos: false,
+            implements_style: Default::default(),
+            argument_lists: false,
+            examples_section: false,
+            removed_items: BTreeMap::new(),
+            trim_trailing_whitespace: false,
+            link_backticked_types: true,
+            trait_doc_template: None,
+            demote_lone_headings: false,
+            symbol_links: BTreeMap::new(),
+            sidecar_dir: None,
+            ascii_only: false,
+            function_links: BTreeMap::new(),
+            since_index: false,
+            missing_docs: Vec::new(),
+            doctest_prelude: None,
+            ignore_foreign_examples: false,
+            unresolved_report: None,
+            strict: false,
+            verbose_warnings: false,
+            ignored_functions: Vec::new(),
+            ignored_types: Vec::new(),
+            ignored_constants: Vec::new(),
+            unresolved_style: Default::default(),
+            link_overrides: BTreeMap::new(),
+            escape_markdown: false,
+            skip_gi_docgen: Vec::new(),
+            wrap_columns: None,
+            cache: None,
+            summary_only: false,
+            availability_notes: false,
+            link_text: Default::default(),
+            bare_type_links: false,
+        }
+    }
+}
+
+impl DocConfig {
+    /// The hidden lines of the Rust doctests, from `doctest_prelude` and `example_setup`
+    pub fn doctest_setup(&self) -> Vec<String> {
+        self.doctest_prelude
+            .iter()
+            .flat_map(|prelude| prelude.lines().map(str::to_owned))
+            .chain(self.example_setup.iter().cloned())
+            .collect()
+    }
+}
+
+fn lookup_string(toml: &toml::Value, option: &str) -> Result<Option<String>, String> {
+    match toml.lookup(option) {
+        Some(v) => Ok(Some(v.as_result_str(option)?.to_owned())),
+        None => Ok(None),
+    }
+}
+
+fn lookup_bool(toml: &toml::Value, option: &str, default: bool) -> Result<bool, String> {
+    match toml.lookup(option) {
+        Some(v) => v.as_result_bool(option),
+        None => Ok(default),
+    }
+}
+
+fn lookup_width(toml: &toml::Value, option: &str) -> Result<Option<usize>, String> {
+    match toml.lookup(option) {
+        Some(v) => match v.as_integer() {
+            Some(width) if width > 0 => Ok(Some(width as usize)),
+            _ => Err(format!(
+                "Invalid `{}` value, expected a positive integer, found {}",
+                option, v
+            )),
+        },
+        None => Ok(None),
+    }
+}
+
+fn lookup_strings(toml: &toml::Value, option: &str) -> Result<Vec<String>, String> {
+    match toml.lookup(option) {
+        Some(v) => v
+            .as_result_vec(option)?
+            .iter()
+            .map(|line| line.as_result_str(option).map(ToOwned::to_owned))
+            .collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn lookup_section_titles(toml: &toml::Value) -> Result<SectionTitles, String> {
+    let mut titles = SectionTitles::default();
+    if let Some(table) = toml.lookup("doc.section_titles") {
+        table.check_unwanted(
+            &[
+                "implements",
+                "implementors",
+                "deprecated",
+                "deprecated_since",
+                "returns",
+                "examples",
+                "arguments",
+            ],
+            "doc.section_titles",
+        );
+        for (key, title) in [
+            ("implements", &mut titles.implements),
+            ("implementors", &mut titles.implementors),
+            ("deprecated", &mut titles.deprecated),
+            ("deprecated_since", &mut titles.deprecated_since),
+            ("returns", &mut titles.returns),
+            ("examples", &mut titles.examples),
+            ("arguments", &mut titles.arguments),
+        ] {
+            if let Some(value) = lookup_string(toml, &format!("doc.section_titles.{}", key))? {
+                *title = value;
+            }
+        }
+    }
+    Ok(titles)
+}
+
+fn lookup_string_table(
+    toml: &toml::Value,
+    option: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut values = BTreeMap::new();
+    if let Some(table) = toml.lookup(option) {
+        let table = table.as_table().ok_or_else(|| {
+            format!(
+                "Invalid `{}` value, expected a table, found {}",
+                option,
+                table.type_str()
+            )
+        })?;
+        for (key, value) in table {
+            let value = value.as_result_str(option)?;
+            values.insert(key.clone(), value.to_owned());
+        }
+    }
+    Ok(values)
+}
+
+fn lookup_removed_items(toml: &toml::Value) -> Result<BTreeMap<String, Version>, String> {
+    lookup_string_table(toml, "doc.removed_items")?
+        .into_iter()
+        .map(|(symbol, version)| Ok((symbol, version.parse()?)))
+        .collect()
+}
+
+fn lookup_link_overrides(toml: &toml::Value) -> Result<BTreeMap<String, DocLinkOverride>, String> {
+    let option = "doc.link_overrides";
+    let mut overrides = BTreeMap::new();
+    let entries = match toml.lookup(option) {
+        Some(v) => v.as_result_vec(option)?,
+        None => return Ok(overrides),
+    };
+    for entry in entries {
+        entry.check_unwanted(&["c_name", "rust_link", "force"], option);
+        let c_name = entry.lookup_str("c_name", "No `c_name` for a doc link override")?;
+        let rust_link = entry.lookup_str(
+            "rust_link",
+            &format!("No `rust_link` for the doc link override of `{}`", c_name),
+        )?;
+        overrides.insert(
+            c_name.to_owned(),
+            DocLinkOverride {
+                rust_link: rust_link
+                    .strip_prefix("crate::")
+                    .unwrap_or(rust_link)
+                    .to_owned(),
+                force: lookup_bool(entry, "force", false)?,
+            },
+        );
+    }
+    Ok(overrides)
+}
+
+pub fn read_doc_config(toml: &toml::Value) -> Result<DocConfig, String> {
+    Ok(DocConfig {
+        version_aware_links: lookup_bool(toml, "doc.version_aware_links", false)?,
+        property_link_target: match toml.lookup("doc.property_link_target") {
+            Some(v) => v.as_result_str("doc.property_link_target")?.parse()?,
+            None => Default::default(),
+        },
+        type_check_macro_link: lookup_string(toml, "doc.type_check_macro_link")?,
+        type_cast_macro_link: lookup_string(toml, "doc.type_cast_macro_link")?,
+        tab_width: lookup_width(toml, "doc.tab_width")?,
+        reexport_docs: lookup_bool(toml, "doc.reexport_docs", false)?,
+        prefer_future_links: lookup_bool(toml, "doc.prefer_future_links", false)?,
+        signal_parameter_tables: lookup_bool(toml, "doc.signal_parameter_tables", false)?,
+        unstable_banner: lookup_string(toml, "doc.unstable_banner")?,
+        type_links: lookup_string_table(toml, "doc.type_links")?,
+        autolink_urls: lookup_bool(toml, "doc.autolink_urls", false)?,
+        missing_function_log: match toml.lookup("doc.missing_function_log") {
+            Some(v) => v.as_result_str("doc.missing_function_log")?.parse()?,
+            None => Default::default(),
+        },
+        examples: BTreeMap::new(),
+        module_doc_template: if lookup_bool(toml, "doc.module_docs", false)? {
+            Some(
+                lookup_string(toml, "doc.module_doc_template")?
+                    .unwrap_or_else(|| DEFAULT_MODULE_DOC_TEMPLATE.to_owned()),
+            )
+        } else {
+            None
+        },
+        example_setup: lookup_strings(toml, "doc.example_setup")?,
+        deprecated_member_links: match toml.lookup("doc.deprecated_member_links") {
+            Some(v) => v.as_result_str("doc.deprecated_member_links")?.parse()?,
+            None => Default::default(),
+        },
+        see_also_lists: lookup_bool(toml, "doc.see_also_lists", false)?,
+        inline_file_paths: lookup_bool(toml, "doc.inline_file_paths", false)?,
+        section_titles: lookup_section_titles(toml)?,
+        c_symbols_index: lookup_bool(toml, "doc.c_symbols_index", false)?,
+        key_combos: lookup_bool(toml, "doc.key_combos", false)?,
+        implements_style: match toml.lookup("doc.implements_style") {
+            Some(v) => v.as_result_str("doc.implements_style")?.parse()?,
+            None => Default::default(),
+        },
+        argument_lists: lookup_bool(toml, "doc.argument_lists", false)?,
+        examples_section: lookup_bool(toml, "doc.examples_section", false)?,
+        removed_items: lookup_removed_items(toml)?,
+        trim_trailing_whitespace: lookup_bool(toml, "doc.trim_trailing_whitespace", false)?,
+        link_backticked_types: lookup_bool(toml, "doc.link_backticked_types", true)?,
+        trait_doc_template: lookup_string(toml, "doc.trait_doc_template")?,
+        demote_lone_headings: lookup_bool(toml, "doc.demote_lone_headings", false)?,
+        symbol_links: lookup_string_table(toml, "doc.symbol_links")?,
+        sidecar_dir: lookup_string(toml, "doc.sidecar_dir")?.map(PathBuf::from),
+        ascii_only: lookup_bool(toml, "doc.ascii_only", false)?,
+        function_links: lookup_string_table(toml, "doc.function_links")?,
+        since_index: lookup_bool(toml, "doc.since_index", false)?,
+        missing_docs: lookup_strings(toml, "doc.missing_docs")?
+            .iter()
+            .map(|kind| kind.parse())
+            .collect::<Result<_, _>>()?,
+        doctest_prelude: lookup_string(toml, "doc.doctest_prelude")?,
+        ignore_foreign_examples: lookup_bool(toml, "doc.ignore_foreign_examples", false)?,
+        unresolved_report: lookup_string(toml, "doc.unresolved_report")?.map(PathBuf::from),
+        strict: lookup_bool(toml, "doc.strict", false)?,
+        verbose_warnings: lookup_bool(toml, "doc.verbose_warnings", false)?,
+        ignored_functions: lookup_strings(toml, "doc.ignored_functions")?,
+        ignored_types: lookup_strings(toml, "doc.ignored_types")?,
+        ignored_constants: lookup_strings(toml, "doc.ignored_constants")?,
+        unresolved_style: match toml.lookup("doc.unresolved_style") {
+            Some(v) => v.as_result_str("doc.unresolved_style")?.parse()?,
+            None => Default::default(),
+        },
+        link_overrides: lookup_link_overrides(toml)?,
+        escape_markdown: lookup_bool(toml, "doc.escape_markdown", false)?,
+        skip_gi_docgen: lookup_strings(toml, "doc.skip_gi_docgen")?,
+        wrap_columns: lookup_width(toml, "doc.wrap_columns")?,
+        cache: lookup_string(toml, "doc.cache")?.map(PathBuf::from),
+        summary_only: lookup_bool(toml, "doc.summary_only", false)?,
+        availability_notes: lookup_bool(toml, "doc.availability_notes", false)?,
+        link_text: match toml.lookup("doc.link_text") {
+            Some(v) => v.as_result_str("doc.link_text")?.parse()?,
+            None => Default::default(),
+        },
+        bare_type_links: lookup_bool(toml, "doc.bare_type_links", false)?,
+    })
+}
+
+/// Reads the examples file configured in `doc.examples`, relative to the config file
+pub fn read_doc_examples(
+    toml: &toml::Value,
+    config_dir: &Path,
+) -> Result<BTreeMap<String, DocExample>, String> {
+    let path = match lookup_string(toml, "doc.examples")? {
+        Some(path) => config_dir.join(path),
+        None => return Ok(BTreeMap::new()),
+    };
+    let no_run = lookup_bool(toml, "doc.examples_no_run", false)?;
+    let input = fs::read(&path)
+        .map_err(|e| format!("Failed to read file \"{}\": {}", path.display(), e))?;
+    let examples = toml::from_slice(&input)
+        .map_err(|e| format!("Invalid toml format in \"{}\": {}", path.display(), e))?;
+    parse_doc_examples(&examples, no_run)
+}
+
+/// Each example is either the code itself or a table with the `code` and `no_run` keys
+fn parse_doc_examples(
+    examples: &toml::Value,
+    no_run: bool,
+) -> Result<BTreeMap<String, DocExample>, String> {
+    let mut ret = BTreeMap::new();
+    let table = match examples.as_table() {
+        Some(table) => table,
+        None => return Ok(ret),
+    };
+    for (item, example) in table {
+        let example = if let Some(code) = example.as_str() {
+            DocExample {
+                code: code.to_owned(),
+                no_run,
+            }
+        } else {
+            example.check_unwanted(&["code", "no_run"], &format!("example {}", item));
+            DocExample {
+                code: example
+                    .lookup_str("code", &format!("No `code` for the example of `{}`", item))?
+                    .to_owned(),
+                no_run: lookup_bool(example, "no_run", no_run)?,
+            }
+        };
+        ret.insert(item.clone(), example);
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toml(input: &str) -> ::toml::Value {
+        let value = ::toml::from_str(input);
+        assert!(value.is_ok());
+        value.unwrap()
+    }
+
+    #[test]
+    fn test_read_doc_config_defaults() {
+        let config = read_doc_config(&toml("")).unwrap();
+        assert!(!config.version_aware_links);
+        assert_eq!(config.property_link_target, PropertyLinkTarget::Getter);
+        assert_eq!(config.type_check_macro_link, None);
+        assert_eq!(config.tab_width, None);
+        assert!(!config.reexport_docs);
+        assert!(!config.prefer_future_links);
+        assert_eq!(config.module_doc_template, None);
+        assert!(config.example_setup.is_empty());
+        assert!(!config.see_also_lists);
+        assert!(!config.inline_file_paths);
+        assert_eq!(config.section_titles, SectionTitles::default());
+        assert!(!config.c_symbols_index);
+        assert!(!config.key_combos);
+        assert_eq!(config.implements_style, ImplementsStyle::List);
+        assert!(!config.argument_lists);
+        assert!(!config.examples_section);
+        assert!(!config.trim_trailing_whitespace);
+        assert!(config.link_backticked_types);
+        assert_eq!(config.trait_doc_template, None);
+        assert!(!config.demote_lone_headings);
+        assert_eq!(config.sidecar_dir, None);
+        assert!(!config.ascii_only);
+        assert!(config.function_links.is_empty());
+        assert!(!config.since_index);
+        assert!(config.missing_docs.is_empty());
+        assert_eq!(config.doctest_prelude, None);
+        assert!(!config.ignore_foreign_examples);
+        assert_eq!(config.unresolved_report, None);
+        assert!(!config.strict);
+        assert!(!config.verbose_warnings);
+        assert!(config.ignored_functions.is_empty());
+        assert!(config.ignored_types.is_empty());
+        assert!(config.ignored_constants.is_empty());
+        assert_eq!(config.unresolved_style, UnresolvedStyle::Backticks);
+        assert!(config.link_overrides.is_empty());
+        assert!(!config.escape_markdown);
+        assert!(config.skip_gi_docgen.is_empty());
+        assert_eq!(config.wrap_columns, None);
+        assert_eq!(config.cache, None);
+        assert!(!config.summary_only);
+        assert!(!config.availability_notes);
+        assert_eq!(config.link_text, LinkText::Rust);
+        assert!(!config.bare_type_links);
+    }
+
+    #[test]
+    fn test_doc_config_default() {
+        // The defaults of the struct are the ones of an empty `[doc]` table
+        let config = DocConfig::default();
+        assert!(config.link_backticked_types);
+        assert!(!config.version_aware_links);
+        assert_eq!(config.section_titles, SectionTitles::default());
+    }
+
+    #[test]
+    fn test_section_titles() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc.section_titles]
+implements = "Implémente"
+deprecated_since = "Obsolète depuis {version}"
+"#,
+        ))
+        .unwrap();
+        let titles = config.section_titles;
+        assert_eq!(titles.implements, "Implémente");
+        assert_eq!(titles.deprecated_since("4.10"), "Obsolète depuis 4.10");
+        assert_eq!(titles.returns, "Returns");
+        assert_eq!(
+            SectionTitles::default().deprecated_since("4.10"),
+            "Deprecated since 4.10"
+        );
+    }
+
+    #[test]
+    fn test_deprecated_member_links() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc]
+deprecated_member_links = "note"
+implements_style = "is_a"
+"#,
+        ))
+        .unwrap();
+        assert_eq!(config.deprecated_member_links, DeprecatedMemberLink::Note);
+        assert_eq!(config.implements_style, ImplementsStyle::IsA);
+        assert_eq!(
+            "plain".parse::<UnresolvedStyle>(),
+            Ok(UnresolvedStyle::Plain)
+        );
+        assert_eq!("drop".parse::<UnresolvedStyle>(), Ok(UnresolvedStyle::Drop));
+        assert!("hidden".parse::<UnresolvedStyle>().is_err());
+        assert_eq!("c".parse::<LinkText>(), Ok(LinkText::C));
+        assert!("C".parse::<LinkText>().is_err());
+        assert_eq!(
+            read_doc_config(&toml("")).unwrap().deprecated_member_links,
+            DeprecatedMemberLink::Off
+        );
+        assert!(read_doc_config(&toml(
+            r#"
+[doc]
+deprecated_member_links = "link"
+"#,
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_example_setup() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc]
+example_setup = ["use gtk::prelude::*;", ""]
+"#,
+        ))
+        .unwrap();
+        assert_eq!(config.example_setup, ["use gtk::prelude::*;", ""]);
+        assert_eq!(config.doctest_setup(), ["use gtk::prelude::*;", ""]);
+
+        let config = read_doc_config(&toml(
+            r#"
+[doc]
+doctest_prelude = "use gtk::prelude::*;\ngtk::init().unwrap();"
+example_setup = ["use gtk::Button;"]
+"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config.doctest_setup(),
+            [
+                "use gtk::prelude::*;",
+                "gtk::init().unwrap();",
+                "use gtk::Button;"
+            ]
+        );
+
+        assert!(read_doc_config(&toml(
+            r#"
+[doc]
+example_setup = [1]
+"#,
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_module_doc_template() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc]
+module_docs = true
+"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config.module_doc_template.as_deref(),
+            Some(DEFAULT_MODULE_DOC_TEMPLATE)
+        );
+
+        let config = read_doc_config(&toml(
+            r#"
+[doc]
+module_docs = true
+module_doc_template = "{contents} of the `{module}` module"
+"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config.module_doc_template.as_deref(),
+            Some("{contents} of the `{module}` module")
+        );
+    }
+
+    #[test]
+    fn test_missing_function_log() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc]
+missing_function_log = "off"
+"#,
+        ))
+        .unwrap();
+        assert_eq!(config.missing_function_log, MissingFunctionLog::Off);
+        assert_eq!(config.missing_function_log.level(), None);
+        assert_eq!(
+            MissingFunctionLog::default().level(),
+            Some(log::Level::Info)
+        );
+        assert_eq!(MissingFunctionLog::Debug.level(), Some(log::Level::Debug));
+    }
+
+    #[test]
+    fn test_read_type_links() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc.type_links]
+GtkSnapshot = "gtk::Snapshot"
+"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config.type_links.get("GtkSnapshot").map(String::as_str),
+            Some("gtk::Snapshot")
+        );
+
+        assert!(read_doc_config(&toml(
+            r#"
+[doc.type_links]
+GtkSnapshot = 1
+"#,
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_symbol_links() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc.symbol_links]
+G_LOG_DOMAIN = "glib::LOG_DOMAIN"
+GTK_BUILDER_ERROR = "BuilderError"
+"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config.symbol_links.get("G_LOG_DOMAIN").map(String::as_str),
+            Some("glib::LOG_DOMAIN")
+        );
+        assert_eq!(
+            config
+                .symbol_links
+                .get("GTK_BUILDER_ERROR")
+                .map(String::as_str),
+            Some("BuilderError")
+        );
+    }
+
+    #[test]
+    fn test_read_removed_items() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc.removed_items]
+gtk_widget_set_double_buffered = "4.0"
+"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config.removed_items.get("gtk_widget_set_double_buffered"),
+            Some(&Version(4, 0, 0))
+        );
+        assert!(read_doc_config(&toml("")).unwrap().removed_items.is_empty());
+    }
+
+    #[test]
+    fn test_read_link_overrides() {
+        let config = read_doc_config(&toml(
+            r#"
+[[doc.link_overrides]]
+c_name = "g_foo_bar"
+rust_link = "crate::Foo::bar"
+
+[[doc.link_overrides]]
+c_name = "GtkFoo"
+rust_link = "Foo"
+force = true
+"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config.link_overrides.get("g_foo_bar"),
+            Some(&DocLinkOverride {
+                rust_link: "Foo::bar".to_owned(),
+                force: false,
+            })
+        );
+        assert_eq!(
+            config.link_overrides.get("GtkFoo"),
+            Some(&DocLinkOverride {
+                rust_link: "Foo".to_owned(),
+                force: true,
+            })
+        );
+
+        assert!(read_doc_config(&toml(
+            r#"
+[[doc.link_overrides]]
+c_name = "g_foo_bar"
+"#,
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_missing_docs() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc]
+missing_docs = ["type", "signal"]
+"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config.missing_docs,
+            [DocItemKind::Type, DocItemKind::Signal]
+        );
+        assert!(read_doc_config(&toml(
+            r#"
+[doc]
+missing_docs = ["types"]
+"#,
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_doc_examples() {
+        let examples = parse_doc_examples(
+            &toml(
+                r#"
+"Button.new" = "let button = Button::new();"
+
+["Button.set_label"]
+code = "button.set_label(\"Ok\");"
+no_run = true
+"#,
+            ),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            examples["Button.new"],
+            DocExample {
+                code: "let button = Button::new();".to_owned(),
+                no_run: false,
+            }
+        );
+        assert_eq!(
+            examples["Button.set_label"],
+            DocExample {
+                code: "button.set_label(\"Ok\");".to_owned(),
+                no_run: true,
+            }
+        );
+
+        assert!(parse_doc_examples(&toml("[Button]\nno_run = true"), false).is_err());
+    }
+
+    #[test]
+    fn test_read_doc_config() {
+        let config = read_doc_config(&toml(
+            r#"
+[doc]
+version_aware_links = true
+property_link_target = "setter"
+type_check_macro_link = ""
+tab_width = 4
+reexport_docs = true
+prefer_future_links = true
+"#,
+        ))
+        .unwrap();
+        assert!(config.version_aware_links);
+        assert_eq!(config.property_link_target, PropertyLinkTarget::Setter);
+        assert_eq!(config.type_check_macro_link.as_deref(), Some(""));
+        assert_eq!(config.type_cast_macro_link, None);
+        assert_eq!(config.tab_width, Some(4));
+        assert!(config.reexport_docs);
+        assert!(config.prefer_future_links);
+
+        assert!(read_doc_config(&toml(
+            r#"
+[doc]
+version_aware_links = "yes"
+"#,
+        ))
+        .is_err());
+
+        assert!(read_doc_config(&toml(
+            r#"
+[doc]
+property_link_target = "notify"
+"#,
+        ))
+        .is_err());
+
+        assert!(read_doc_config(&toml(
+            r#"
+[doc]
+tab_width = 0
+"#,
+        ))
+        .is_err());
+    }
+}
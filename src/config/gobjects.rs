@@ -98,6 +98,7 @@ pub struct GObject {
     pub clear_function_expression: Option<String>,
     pub visibility: Visibility,
     pub default_value: Option<String>,
+    pub doc_summary_only: Option<bool>,
 }
 
 impl Default for GObject {
@@ -135,6 +136,7 @@ impl Default for GObject {
             clear_function_expression: None,
             visibility: Default::default(),
             default_value: None,
+            doc_summary_only: None,
         }
     }
 }
@@ -288,6 +290,7 @@ fn parse_object(
             "clear_function_expression",
             "visibility",
             "default_value",
+            "doc_summary_only",
         ],
         &format!("object {}", name),
     );
@@ -360,6 +363,9 @@ fn parse_object(
         .and_then(ref_mode_from_str);
     let conversion_type = parse_conversion_type(toml_object.lookup("conversion_type"), &name);
     let child_properties = ChildProperties::parse(toml_object, &name);
+    let doc_summary_only = toml_object
+        .lookup("doc_summary_only")
+        .and_then(Value::as_bool);
     let must_use = toml_object
         .lookup("must_use")
         .and_then(Value::as_bool)
@@ -512,6 +518,7 @@ fn parse_object(
         clear_function_expression,
         visibility,
         default_value,
+        doc_summary_only,
     }
 }
 
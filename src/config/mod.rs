@@ -3,6 +3,7 @@ mod child_properties;
 pub mod config;
 pub mod constants;
 pub mod derives;
+pub mod doc;
 pub mod error;
 mod external_libraries;
 pub mod functions;
@@ -21,6 +22,7 @@ pub mod work_mode;
 pub use self::{
     child_properties::{ChildProperties, ChildProperty},
     config::Config,
+    doc::DocConfig,
     external_libraries::ExternalLibrary,
     gobjects::GObject,
     property_generate_flags::PropertyGenerateFlags,
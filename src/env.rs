@@ -1,5 +1,6 @@
 use crate::{
     analysis::{self, namespaces::NsId},
+    codegen::{DocCache, LoggedRefs, UnresolvedRefs},
     config::{gobjects::GStatus, Config},
     library::*,
     nameutil::use_glib_type,
@@ -15,6 +16,12 @@ pub struct Env {
     pub symbols: RefCell<analysis::symbols::Info>,
     pub class_hierarchy: analysis::class_hierarchy::Info,
     pub analysis: analysis::Analysis,
+    /// The doc references left unresolved, see `doc.unresolved_report`
+    pub doc_unresolved: UnresolvedRefs,
+    /// The unresolved doc references already logged, see `doc.verbose_warnings`
+    pub doc_logged: LoggedRefs,
+    /// The docs formatted by the previous runs, see `doc.cache`
+    pub doc_cache: DocCache,
 }
 
 impl Env {
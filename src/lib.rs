@@ -45,7 +45,10 @@ pub use crate::{
         class_hierarchy::run as class_hierarchy_run, namespaces::run as namespaces_run,
         run as analysis_run, symbols::run as symbols_run,
     },
-    codegen::generate as codegen_generate,
+    codegen::{
+        check_strict_doc, generate as codegen_generate, preview_doc, reformat_standalone_doc,
+        DocResolver,
+    },
     config::{Config, WorkMode},
     env::Env,
     library::Library,
@@ -205,6 +205,32 @@ impl Default for Concurrency {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stability {
+    Stable,
+    Unstable,
+    Private,
+}
+
+impl FromStr for Stability {
+    type Err = String;
+    fn from_str(name: &str) -> Result<Stability, String> {
+        use self::Stability::*;
+        match name {
+            "Stable" => Ok(Stable),
+            "Unstable" => Ok(Unstable),
+            "Private" => Ok(Private),
+            _ => Err(format!("Unknown stability '{}'", name)),
+        }
+    }
+}
+
+impl Default for Stability {
+    fn default() -> Stability {
+        Stability::Stable
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Fundamental {
     None,
@@ -408,6 +434,7 @@ pub struct Enumeration {
     pub functions: Vec<Function>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
     pub error_domain: Option<ErrorDomain>,
@@ -423,6 +450,7 @@ pub struct Bitfield {
     pub functions: Vec<Function>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
     pub glib_get_type: Option<String>,
@@ -434,11 +462,14 @@ pub struct Record {
     pub c_type: String,
     pub symbol_prefix: Option<String>,
     pub glib_get_type: Option<String>,
+    /// The `glib:type-name` the type is registered with
+    pub glib_type_name: Option<String>,
     pub gtype_struct_for: Option<String>,
     pub fields: Vec<Field>,
     pub functions: Vec<Function>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
     /// A 'disguised' record is one where the c:type is a typedef that
@@ -539,6 +570,7 @@ pub struct Function {
     pub throws: bool,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
 }
@@ -552,6 +584,7 @@ pub struct Signal {
     pub is_detailed: bool,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
 }
@@ -564,12 +597,15 @@ pub struct Interface {
     pub type_struct: Option<String>,
     pub c_class_type: Option<String>,
     pub glib_get_type: String,
+    /// The `glib:type-name` the type is registered with
+    pub glib_type_name: Option<String>,
     pub functions: Vec<Function>,
     pub signals: Vec<Signal>,
     pub properties: Vec<Property>,
     pub prerequisites: Vec<TypeId>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
 }
@@ -582,6 +618,8 @@ pub struct Class {
     pub type_struct: Option<String>,
     pub c_class_type: Option<String>,
     pub glib_get_type: String,
+    /// The `glib:type-name` the type is registered with
+    pub glib_type_name: Option<String>,
     pub fields: Vec<Field>,
     pub functions: Vec<Function>,
     pub signals: Vec<Signal>,
@@ -591,6 +629,7 @@ pub struct Class {
     pub final_type: bool,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub stability: Stability,
     pub doc: Option<String>,
     pub doc_deprecated: Option<String>,
     pub is_abstract: bool,
@@ -717,6 +756,29 @@ impl Type {
         }
     }
 
+    pub fn get_version(&self) -> Option<Version> {
+        use self::Type::*;
+        match self {
+            Enumeration(enum_) => enum_.version,
+            Bitfield(bit_field) => bit_field.version,
+            Record(rec) => rec.version,
+            Function(func) => func.version,
+            Interface(interface) => interface.version,
+            Class(class) => class.version,
+            _ => None,
+        }
+    }
+
+    pub fn glib_type_name(&self) -> Option<&str> {
+        use self::Type::*;
+        match self {
+            Record(rec) => rec.glib_type_name.as_deref(),
+            Interface(interface) => interface.glib_type_name.as_deref(),
+            Class(class) => class.glib_type_name.as_deref(),
+            _ => None,
+        }
+    }
+
     pub fn get_deprecated_version(&self) -> Option<Version> {
         use self::Type::*;
         match self {
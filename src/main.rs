@@ -30,6 +30,7 @@ impl<S: AsRef<str>> OptionStr for Option<S> {
 enum RunKind {
     Config(Config),
     CheckGirFile(String),
+    PreviewDoc(Config, String),
 }
 
 fn build_config() -> Result<RunKind, String> {
@@ -61,12 +62,33 @@ fn build_config() -> Result<RunKind, String> {
     options.optflag("b", "make-backup", "Make backup before generating");
     options.optflag("s", "stats", "Show statistics");
     options.optflag("", "disable-format", "Disable formatting generated code");
+    options.optflag(
+        "",
+        "strict-doc",
+        "Fail when some doc references can't be resolved",
+    );
+    options.optflag(
+        "",
+        "verbose-doc-warnings",
+        "Log each unresolved doc reference every time instead of once",
+    );
+    options.optflag(
+        "",
+        "no-doc-cache",
+        "Format all the docs again instead of reusing the `doc.cache` ones",
+    );
     options.optopt(
         "",
         "check-gir-file",
         "Check if the given `.gir` file is valid",
         "PATH",
     );
+    options.optopt(
+        "",
+        "preview-doc",
+        "Print the generated documentation of a single item (e.g. Gtk.Button.clicked)",
+        "ITEM",
+    );
 
     let matches = options.parse(&args[1..]).map_err(|e| e.to_string())?;
 
@@ -79,7 +101,9 @@ fn build_config() -> Result<RunKind, String> {
         process::exit(0);
     }
 
+    let preview_doc = matches.opt_str("preview-doc");
     let work_mode = match matches.opt_str("m") {
+        _ if preview_doc.is_some() => Some(WorkMode::Doc),
         None => None,
         Some(s) => match WorkMode::from_str(&s) {
             Ok(w) => Some(w),
@@ -102,7 +126,22 @@ fn build_config() -> Result<RunKind, String> {
         matches.opt_present("s"),
         matches.opt_present("disable-format"),
     )
-    .map(RunKind::Config)
+    .map(|mut cfg| {
+        if matches.opt_present("strict-doc") {
+            cfg.doc.strict = true;
+        }
+        if matches.opt_present("verbose-doc-warnings") {
+            cfg.doc.verbose_warnings = true;
+        }
+        if matches.opt_present("no-doc-cache") {
+            cfg.doc.cache = None;
+        }
+        cfg
+    })
+    .map(|cfg| match preview_doc {
+        Some(item) => RunKind::PreviewDoc(cfg, item),
+        None => RunKind::Config(cfg),
+    })
 }
 
 fn run_check(check_gir_file: &str) -> Result<(), String> {
@@ -131,9 +170,10 @@ fn main() -> Result<(), String> {
     }
     env_logger::init();
 
-    let mut cfg = match build_config() {
+    let (mut cfg, preview_doc) = match build_config() {
         Ok(RunKind::CheckGirFile(check_gir_file)) => return run_check(&check_gir_file),
-        Ok(RunKind::Config(cfg)) => cfg,
+        Ok(RunKind::Config(cfg)) => (cfg, None),
+        Ok(RunKind::PreviewDoc(cfg, item)) => (cfg, Some(item)),
         Err(err) => return Err(err),
     };
     cfg.check_disable_format();
@@ -190,6 +230,9 @@ fn main() -> Result<(), String> {
             symbols: RefCell::new(symbols),
             class_hierarchy,
             analysis: Default::default(),
+            doc_unresolved: Default::default(),
+            doc_logged: Default::default(),
+            doc_cache: Default::default(),
         }
     };
 
@@ -198,9 +241,24 @@ fn main() -> Result<(), String> {
         gir::analysis_run(&mut env);
     }
 
+    if let Some(item) = preview_doc {
+        return match gir::preview_doc(&env, &item) {
+            Ok(Some(doc)) => {
+                print!("{}", doc);
+                Ok(())
+            }
+            Ok(None) => Err(format!("No documentation found for `{}`", item)),
+            Err(e) => Err(format!(
+                "Couldn't generate the documentation of `{}`: {}",
+                item, e
+            )),
+        };
+    }
+
     if env.config.work_mode != WorkMode::DisplayNotBound {
         let _watcher = statistics.enter("Generating");
         gir::codegen_generate(&env);
+        gir::check_strict_doc(&env)?;
     }
 
     if !env.config.disable_format && env.config.work_mode.is_generate_rust_files() {
@@ -150,8 +150,10 @@ impl Library {
         let symbol_prefix = elem.attr_required("symbol-prefix").map(ToOwned::to_owned)?;
         let type_struct = elem.attr("type-struct").map(ToOwned::to_owned);
         let get_type = elem.attr_required("get-type")?;
+        let glib_type_name = elem.attr("type-name").map(ToOwned::to_owned);
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
+        let stability = self.read_stability(parser, elem)?;
         let is_fundamental = elem.attr("fundamental").map(|x| x == "1").unwrap_or(false);
         let (ref_fn, unref_fn) = if is_fundamental {
             (
@@ -235,6 +237,7 @@ impl Library {
             type_struct,
             c_class_type: None, // this will be resolved during postprocessing
             glib_get_type: get_type.into(),
+            glib_type_name,
             fields,
             functions: fns,
             signals,
@@ -246,6 +249,7 @@ impl Library {
             doc_deprecated,
             version,
             deprecated_version,
+            stability,
             symbol_prefix,
             is_abstract,
             is_fundamental,
@@ -286,9 +290,11 @@ impl Library {
         let c_type = elem.attr_required("type")?;
         let symbol_prefix = elem.attr("symbol-prefix").map(ToOwned::to_owned);
         let get_type = elem.attr("get-type").map(ToOwned::to_owned);
+        let glib_type_name = elem.attr("type-name").map(ToOwned::to_owned);
         let gtype_struct_for = elem.attr("is-gtype-struct-for");
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
+        let stability = self.read_stability(parser, elem)?;
         let disguised = elem.attr_bool("disguised", false);
 
         let mut fields = Vec::new();
@@ -385,11 +391,13 @@ impl Library {
             name: record_name.into(),
             c_type: c_type.into(),
             glib_get_type: get_type,
+            glib_type_name,
             gtype_struct_for: gtype_struct_for.map(|s| s.into()),
             fields,
             functions: fns,
             version,
             deprecated_version,
+            stability,
             doc,
             doc_deprecated,
             disguised,
@@ -603,8 +611,10 @@ impl Library {
         let symbol_prefix = elem.attr_required("symbol-prefix").map(ToOwned::to_owned)?;
         let type_struct = elem.attr("type-struct").map(ToOwned::to_owned);
         let get_type = elem.attr_required("get-type")?;
+        let glib_type_name = elem.attr("type-name").map(ToOwned::to_owned);
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
+        let stability = self.read_stability(parser, elem)?;
 
         let mut fns = Vec::new();
         let mut signals = Vec::new();
@@ -642,6 +652,7 @@ impl Library {
             type_struct,
             c_class_type: None, // this will be resolved during postprocessing
             glib_get_type: get_type.into(),
+            glib_type_name,
             functions: fns,
             signals,
             properties,
@@ -650,6 +661,7 @@ impl Library {
             doc_deprecated,
             version,
             deprecated_version,
+            stability,
             symbol_prefix,
         });
         self.add_type(ns_id, interface_name, typ);
@@ -668,6 +680,7 @@ impl Library {
         let get_type = elem.attr("get-type").map(|s| s.into());
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
+        let stability = self.read_stability(parser, elem)?;
 
         let mut members = Vec::new();
         let mut fns = Vec::new();
@@ -695,6 +708,7 @@ impl Library {
             functions: fns,
             version,
             deprecated_version,
+            stability,
             doc,
             doc_deprecated,
             glib_get_type: get_type,
@@ -716,6 +730,7 @@ impl Library {
         let get_type = elem.attr("get-type").map(|s| s.into());
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
+        let stability = self.read_stability(parser, elem)?;
         let error_domain = elem
             .attr("error-domain")
             .map(|s| ErrorDomain::Quark(String::from(s)));
@@ -746,6 +761,7 @@ impl Library {
             functions: fns,
             version,
             deprecated_version,
+            stability,
             doc,
             doc_deprecated,
             error_domain,
@@ -938,6 +954,7 @@ impl Library {
         let is_method = kind == FunctionKind::Method;
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
+        let stability = self.read_stability(parser, elem)?;
 
         let mut params = Vec::new();
         let mut ret = None;
@@ -996,6 +1013,7 @@ impl Library {
                 throws,
                 version,
                 deprecated_version,
+                stability,
                 doc,
                 doc_deprecated,
             })
@@ -1056,6 +1074,7 @@ impl Library {
         let is_detailed = elem.attr_bool("detailed", false);
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
+        let stability = self.read_stability(parser, elem)?;
 
         let mut params = Vec::new();
         let mut ret = None;
@@ -1090,6 +1109,7 @@ impl Library {
                 is_detailed,
                 version,
                 deprecated_version,
+                stability,
                 doc,
                 doc_deprecated,
             })
@@ -1377,6 +1397,15 @@ impl Library {
         self.read_version_attribute(parser, ns_id, elem, "deprecated-version")
     }
 
+    fn read_stability(&self, parser: &XmlParser<'_>, elem: &Element) -> Result<Stability, String> {
+        if let Some(v) = elem.attr("stability") {
+            v.parse()
+                .map_err(|e| parser.fail(&format!("Invalid `stability` attribute: {}", e)))
+        } else {
+            Ok(Stability::default())
+        }
+    }
+
     fn read_version_attribute(
         &mut self,
         parser: &XmlParser<'_>,
@@ -0,0 +1,76 @@
+//! Golden tests of the doc conversion: each directory of `tests/doc` holds C docs in
+//! `input.txt` and the rustdoc they are converted to in `expected.md`. Running the tests
+//! with `GIR_BLESS=1` writes the current output of the fixtures as their expectation.
+
+use libgir::{reformat_standalone_doc, DocResolver};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Links the few references the fixtures resolve
+struct FixtureResolver;
+
+impl DocResolver for FixtureResolver {
+    fn resolve_type(&self, c_type: &str) -> Option<String> {
+        (c_type == "GtkWidget").then(|| "[`Widget`][crate::Widget]".to_owned())
+    }
+
+    fn resolve_function(&self, c_name: &str) -> Option<String> {
+        (c_name == "gtk_widget_show")
+            .then(|| "[`WidgetExt::show()`][crate::prelude::WidgetExt::show()]".to_owned())
+    }
+
+    fn resolve_constant(&self, c_identifier: &str) -> Option<String> {
+        (c_identifier == "GTK_ALIGN_START")
+            .then(|| "[`Align::Start`][crate::Align::Start]".to_owned())
+    }
+}
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("doc");
+    let mut fixtures = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Couldn't read {:?}: {}", dir, e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    fixtures.sort();
+    fixtures
+}
+
+/// The content of a fixture file, without the newline ending it
+fn read(path: &Path) -> String {
+    let content =
+        fs::read_to_string(path).unwrap_or_else(|e| panic!("Couldn't read {:?}: {}", path, e));
+    match content.strip_suffix('\n') {
+        Some(content) => content.to_owned(),
+        None => content,
+    }
+}
+
+#[test]
+fn doc_fixtures() {
+    let bless = env::var_os("GIR_BLESS").is_some();
+    let mut failures = Vec::new();
+    for fixture in fixtures() {
+        let output = reformat_standalone_doc(&read(&fixture.join("input.txt")), &FixtureResolver);
+        let expected_path = fixture.join("expected.md");
+        if bless {
+            fs::write(&expected_path, format!("{}\n", output)).unwrap();
+        } else if read(&expected_path) != output {
+            failures.push(format!(
+                "{:?}\n--- expected\n{}\n--- output\n{}",
+                fixture,
+                read(&expected_path),
+                output
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "The doc fixtures differ, run with GIR_BLESS=1 to update them:\n\n{}",
+        failures.join("\n\n")
+    );
+}